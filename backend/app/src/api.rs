@@ -2,15 +2,23 @@ use axum::Router;
 
 use crate::common::ApiError;
 
+pub mod freshness;
+pub mod inventory;
 pub mod packages;
 pub mod projects;
+pub mod scans;
+pub mod vulnerabilities;
 
 pub fn create_router() -> Router<crate::app::AppState> {
     Router::new()
         .nest("/api", 
             Router::new().
+            nest("/freshness", freshness::routes()).
+            nest("/inventory", inventory::routes()).
             nest("/package", packages::routes()).
-            nest("/projects", projects::routes()))
+            nest("/projects", projects::routes()).
+            nest("/scans", scans::routes()).
+            nest("/vulnerabilities", vulnerabilities::routes()))
         .fallback(async || -> ApiError {
             tracing::info!("Not Found!");
             ApiError::NotFound
@@ -0,0 +1,9 @@
+use axum::{routing::get, Router};
+pub mod leaderboard;
+
+use crate::app::AppState;
+use leaderboard::freshness_leaderboard;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/leaderboard", get(freshness_leaderboard))
+}
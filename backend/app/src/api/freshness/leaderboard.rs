@@ -0,0 +1,122 @@
+use axum::extract::State;
+use axum_valid::Valid;
+use sea_orm::{DatabaseBackend, FromQueryResult, Statement, Value};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    app::AppState,
+    common::{ApiResponse, ApiResult},
+    freshness::{scan_summaries, FreshnessSummary},
+    params::{
+        pagination::{Page, PaginationParams},
+        query::Query,
+    },
+};
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct LeaderboardQuery {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub pagination: PaginationParams,
+    /// Only projects owned by this team.
+    pub team: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectFreshness {
+    /// Absent for projects without any measured component, which are listed last.
+    pub rank: Option<usize>,
+    pub project_id: i32,
+    pub project_name: String,
+    pub team: Option<String>,
+    pub scan_id: i32,
+    /// Libyears per component whose libyears are known.
+    pub libyears_per_component: Option<f64>,
+    pub summary: FreshnessSummary,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct LatestScan {
+    project_id: i32,
+    project_name: String,
+    team: Option<String>,
+    scan_id: i32,
+}
+
+/// Projects ranked by the freshness of their latest successful scan: fewest libyears per
+/// measured component first, then fewest outdated components. Projects with nothing
+/// measured follow unranked. Ranks are computed over all matching projects before the
+/// requested page is cut out.
+pub async fn freshness_leaderboard(
+    State(AppState { db, .. }): State<AppState>,
+    Valid(Query(params)): Valid<Query<LeaderboardQuery>>,
+) -> ApiResult<ApiResponse<Page<ProjectFreshness>>> {
+    let mut values: Vec<Value> = Vec::new();
+    let mut team_filter = String::new();
+    if let Some(team) = params.team.as_deref().filter(|team| !team.is_empty()) {
+        values.push(team.into());
+        team_filter = format!("AND pr.team = ${}", values.len());
+    }
+
+    let scans = LatestScan::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT pr.id AS project_id, pr.name AS project_name, pr.team, MAX(s.id) AS scan_id \
+             FROM projects pr \
+             JOIN scans s ON s.project_id = pr.id AND s.status = 'success' \
+             WHERE TRUE {team_filter} \
+             GROUP BY pr.id, pr.name, pr.team"
+        ),
+        values,
+    ))
+    .all(&db)
+    .await?;
+
+    let scan_ids: Vec<i32> = scans.iter().map(|scan| scan.scan_id).collect();
+    let mut summaries = scan_summaries(&db, &scan_ids).await?;
+
+    let mut entries = Vec::with_capacity(scans.len());
+    for scan in scans {
+        let summary = summaries.remove(&scan.scan_id).unwrap_or_default();
+        let libyears_per_component = (summary.measured > 0)
+            .then(|| (summary.libyears / summary.measured as f64 * 100.0).round() / 100.0);
+        entries.push(ProjectFreshness {
+            rank: None,
+            project_id: scan.project_id,
+            project_name: scan.project_name,
+            team: scan.team,
+            scan_id: scan.scan_id,
+            libyears_per_component,
+            summary,
+        });
+    }
+    entries.sort_by(|a, b| {
+        let (a_score, b_score) = (a.libyears_per_component, b.libyears_per_component);
+        a_score
+            .is_none()
+            .cmp(&b_score.is_none())
+            .then_with(|| a_score.unwrap_or(0.0).total_cmp(&b_score.unwrap_or(0.0)))
+            .then_with(|| a.summary.outdated.cmp(&b.summary.outdated))
+            .then_with(|| a.project_name.cmp(&b.project_name))
+    });
+    for (index, entry) in entries.iter_mut().enumerate() {
+        if entry.libyears_per_component.is_some() {
+            entry.rank = Some(index + 1);
+        }
+    }
+
+    let pagination = params.pagination;
+    let total = entries.len() as u64;
+    let offset = usize::try_from(pagination.offset()).unwrap_or(usize::MAX);
+    let items = entries
+        .into_iter()
+        .skip(offset)
+        .take(pagination.per_page as usize)
+        .collect();
+
+    Ok(ApiResponse::ok(
+        "freshness leaderboard",
+        Some(Page::from_pagination(pagination, total, items)),
+    ))
+}
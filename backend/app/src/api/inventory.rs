@@ -0,0 +1,9 @@
+use axum::{routing::get, Router};
+pub mod export;
+
+use crate::app::AppState;
+use export::export_inventory;
+
+pub fn routes() -> Router<AppState> {
+    Router::new().route("/", get(export_inventory))
+}
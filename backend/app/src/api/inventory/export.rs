@@ -0,0 +1,78 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    app::AppState,
+    common::ApiResult,
+    export::inventory::{render, InventoryFormat},
+    inventory::{build_inventory, InventoryFilter},
+    params::{query::Query, valid::Valid},
+};
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct InventoryQuery {
+    #[serde(default)]
+    pub format: InventoryFormat,
+    /// Only projects owned by this team.
+    #[validate(length(min = 1, max = 255))]
+    pub team: Option<String>,
+    /// Comma-separated project ids or names; all projects by default.
+    #[validate(length(max = 8192))]
+    pub projects: Option<String>,
+}
+
+/// Merge the latest scan of every selected project into one inventory.
+pub async fn export_inventory(
+    State(AppState { db, .. }): State<AppState>,
+    Valid(Query(params)): Valid<Query<InventoryQuery>>,
+) -> ApiResult<Response> {
+    let filter = inventory_filter(params.team.clone(), params.projects.as_deref());
+    let name = match &filter.team {
+        Some(team) => format!("{team} inventory"),
+        None => "Organization inventory".to_string(),
+    };
+
+    let inventory = build_inventory(&db, &filter).await?;
+    let body = render(&inventory, &name, params.format)?;
+    let filename = format!("inventory.{}", params.format.extension());
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                params.format.content_type().to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Numeric entries of `projects` select by id, anything else by name.
+pub(crate) fn inventory_filter(team: Option<String>, projects: Option<&str>) -> InventoryFilter {
+    let mut filter = InventoryFilter {
+        team,
+        ..Default::default()
+    };
+    for project in projects
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+    {
+        match project.parse::<i32>() {
+            Ok(id) => filter.project_ids.push(id),
+            Err(_) => filter.project_names.push(project.to_string()),
+        }
+    }
+    filter
+}
@@ -1,11 +1,25 @@
 use axum::{routing::get, Router};
+pub mod blast_radius;
+pub mod dependents;
+pub mod detail;
+pub mod search;
+pub mod vulnerabilities;
 
-use crate::{app::AppState, common::error::ApiError, common::response::ApiResponse};
+use crate::app::AppState;
+use blast_radius::blast_radius;
+use dependents::list_dependents;
+use detail::get_package;
+use search::list_packages;
+use vulnerabilities::list_package_vulnerabilities;
 
 pub fn routes() -> Router<AppState> {
-    Router::new().route("/packages", get(list_packages))
-}
-
-async fn list_packages() -> Result<ApiResponse<&'static str>, ApiError> {
-    Ok(ApiResponse::ok("package list", Some("stub")))
+    Router::new()
+        .route("/packages", get(list_packages))
+        .route("/packages/{id}", get(get_package))
+        .route("/packages/{id}/dependents", get(list_dependents))
+        .route(
+            "/packages/{id}/vulnerabilities",
+            get(list_package_vulnerabilities),
+        )
+        .route("/blast-radius", get(blast_radius))
 }
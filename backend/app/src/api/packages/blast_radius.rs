@@ -0,0 +1,259 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    str::FromStr,
+};
+
+use axum::extract::State;
+use packageurl::PackageUrl;
+use sea_orm::{DatabaseBackend, FromQueryResult, Statement, Value};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use validator::Validate;
+
+use crate::{
+    app::AppState,
+    common::{ApiError, ApiResponse, ApiResult},
+    graph::{DependencyPath, MAX_TRAVERSAL_DEPTH},
+    params::{query::Query, valid::Valid},
+    purl::base_purl,
+    version::{Ecosystem, VersionRange},
+};
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct BlastRadiusQuery {
+    /// Package purl without a version, e.g. `pkg:maven/org.apache.logging.log4j/log4j-core`.
+    #[validate(length(min = 1, max = 2048))]
+    pub purl: String,
+    /// Affected versions in the ecosystem's range syntax or as a `vers:` URI,
+    /// e.g. `<2.17.1`, `[2.0,2.17.1)` or `vers:maven/>=2.0|<2.17.1`.
+    #[validate(length(min = 1, max = 512))]
+    pub range: String,
+    /// Only projects owned by this team.
+    pub team: Option<String>,
+}
+
+/// How directly a project is exposed, most severe first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Exposure {
+    /// Declared directly in a runtime scope.
+    High,
+    /// Pulled in transitively, or the graph has no path for it.
+    Medium,
+    /// Optional or excluded scope only.
+    Low,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BlastRadius {
+    pub purl: String,
+    pub range: String,
+    pub project_count: usize,
+    pub teams: Vec<TeamExposure>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TeamExposure {
+    /// `None` collects projects without an owning team.
+    pub team: Option<String>,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    pub projects: Vec<AffectedProject>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AffectedProject {
+    pub project_id: i32,
+    pub project_name: String,
+    /// Latest successful scan that resolved the vulnerable version.
+    pub scan_id: i32,
+    pub exposure: Exposure,
+    pub versions: Vec<AffectedVersion>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AffectedVersion {
+    pub purl: String,
+    pub version: String,
+    pub scope: Option<String>,
+    /// Shortest distance from the project root; `None` when the graph has no path.
+    pub depth: Option<u32>,
+    /// Direct dependencies that pull this version in.
+    pub via: Vec<String>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CandidateRow {
+    project_id: i32,
+    project_name: String,
+    team: Option<String>,
+    scan_id: i32,
+    purl: String,
+    version: Option<String>,
+    scope: Option<String>,
+}
+
+/// Every project whose latest scan resolves the package to a version inside `range`.
+pub async fn blast_radius(
+    State(AppState { db, graph }): State<AppState>,
+    Valid(Query(params)): Valid<Query<BlastRadiusQuery>>,
+) -> ApiResult<ApiResponse<BlastRadius>> {
+    let parsed = PackageUrl::from_str(&params.purl)
+        .map_err(|err| ApiError::Biz(format!("invalid purl {}: {err}", params.purl)))?;
+    if parsed.version().is_some() {
+        return Err(ApiError::Biz(
+            "purl must not carry a version; use `range` instead".into(),
+        ));
+    }
+    let purl = base_purl(&parsed);
+    let ecosystem = Ecosystem::from_purl_type(parsed.ty());
+    let range = VersionRange::parse(ecosystem, &params.range)
+        .map_err(|err| ApiError::Biz(err.to_string()))?;
+
+    let mut values: Vec<Value> = vec![purl.clone().into()];
+    let mut team_filter = String::new();
+    if let Some(team) = params.team.as_deref().filter(|t| !t.is_empty()) {
+        values.push(team.to_string().into());
+        team_filter = format!("AND pr.team = ${}", values.len());
+    }
+
+    let candidates = CandidateRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT pr.id AS project_id, pr.name AS project_name, pr.team, s.id AS scan_id, \
+                    COALESCE(v.purl, p.purl) AS purl, d.resolved_version AS version, d.scope \
+             FROM direct_dependencies d \
+             JOIN packages p ON p.id = d.package_id \
+             LEFT JOIN package_versions v ON v.id = d.package_version_id \
+             JOIN scans s ON s.id = d.scan_id \
+             JOIN projects pr ON pr.id = s.project_id \
+             WHERE p.purl = $1 {team_filter} \
+               AND s.id IN (SELECT MAX(id) FROM scans WHERE status = 'success' GROUP BY project_id) \
+             ORDER BY pr.name, d.id"
+        ),
+        values,
+    ))
+    .all(&db)
+    .await?;
+
+    let matched: Vec<(CandidateRow, String)> = candidates
+        .into_iter()
+        .filter_map(|mut row| {
+            let version = row.version.take().filter(|v| range.contains(v))?;
+            Some((row, version))
+        })
+        .collect();
+
+    // One path lookup per affected scan; without the graph the versions are still
+    // reported, just without depth.
+    let mut scan_purls: BTreeMap<(i32, i32), Vec<String>> = BTreeMap::new();
+    for (row, _) in &matched {
+        scan_purls
+            .entry((row.project_id, row.scan_id))
+            .or_default()
+            .push(row.purl.clone());
+    }
+    let mut scan_paths: HashMap<i32, HashMap<String, Vec<DependencyPath>>> = HashMap::new();
+    for ((project_id, scan_id), purls) in scan_purls {
+        let paths = match graph
+            .dependency_paths_batch(project_id, Some(scan_id), &purls, MAX_TRAVERSAL_DEPTH)
+            .await
+        {
+            Ok(paths) => paths,
+            Err(err) => {
+                warn!(error = ?err, scan_id, "failed to load dependency paths");
+                HashMap::new()
+            }
+        };
+        scan_paths.insert(scan_id, paths);
+    }
+
+    let mut projects: BTreeMap<i32, (Option<String>, AffectedProject)> = BTreeMap::new();
+    for (row, version) in matched {
+        let paths = scan_paths
+            .get(&row.scan_id)
+            .and_then(|paths| paths.get(&row.purl))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let depth = paths.iter().map(|path| path.len() as u32).min();
+        let via: BTreeSet<String> = paths
+            .iter()
+            .filter_map(|path| path.first().map(|hop| hop.purl.clone()))
+            .collect();
+
+        let exposure = exposure(depth, row.scope.as_deref());
+        let (_, project) = projects.entry(row.project_id).or_insert_with(|| {
+            (
+                row.team.clone(),
+                AffectedProject {
+                    project_id: row.project_id,
+                    project_name: row.project_name.clone(),
+                    scan_id: row.scan_id,
+                    exposure,
+                    versions: Vec::new(),
+                },
+            )
+        });
+        project.exposure = project.exposure.min(exposure);
+        project.versions.push(AffectedVersion {
+            purl: row.purl,
+            version,
+            scope: row.scope,
+            depth,
+            via: via.into_iter().collect(),
+        });
+    }
+
+    let project_count = projects.len();
+    let mut teams: BTreeMap<Option<String>, Vec<AffectedProject>> = BTreeMap::new();
+    for (team, project) in projects.into_values() {
+        teams.entry(team).or_default().push(project);
+    }
+
+    let mut teams: Vec<TeamExposure> = teams
+        .into_iter()
+        .map(|(team, mut projects)| {
+            projects.sort_by(|a, b| {
+                a.exposure
+                    .cmp(&b.exposure)
+                    .then_with(|| a.project_name.cmp(&b.project_name))
+            });
+            let count = |level| projects.iter().filter(|p| p.exposure == level).count();
+            TeamExposure {
+                team,
+                high: count(Exposure::High),
+                medium: count(Exposure::Medium),
+                low: count(Exposure::Low),
+                projects,
+            }
+        })
+        .collect();
+    // Named teams first, most exposed first; unowned projects last.
+    teams.sort_by(|a, b| {
+        a.team
+            .is_none()
+            .cmp(&b.team.is_none())
+            .then_with(|| b.high.cmp(&a.high))
+            .then_with(|| b.medium.cmp(&a.medium))
+            .then_with(|| a.team.cmp(&b.team))
+    });
+
+    Ok(ApiResponse::ok(
+        "blast radius",
+        Some(BlastRadius {
+            purl,
+            range: range.to_string(),
+            project_count,
+            teams,
+        }),
+    ))
+}
+
+fn exposure(depth: Option<u32>, scope: Option<&str>) -> Exposure {
+    match (depth, scope) {
+        (_, Some("optional" | "excluded")) => Exposure::Low,
+        (Some(1), _) => Exposure::High,
+        _ => Exposure::Medium,
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use axum::extract::State;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::detail::find_package;
+use crate::{
+    app::AppState,
+    common::{ApiError, ApiResponse, ApiResult},
+    entity::{package_version, project},
+    graph::MAX_TRAVERSAL_DEPTH,
+    params::{path::Path, query::Query, valid::Valid},
+};
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct DependentsQuery {
+    /// Restrict to one version; every version matches when omitted.
+    #[validate(length(min = 1, max = 255))]
+    pub version: Option<String>,
+    #[validate(range(min = 1, max = 16))]
+    pub max_depth: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PackageDependents {
+    pub package_id: i32,
+    /// Purls that were looked up in the graph: the package purl, or every scanned purl of
+    /// `version` (they may differ in qualifiers) when it was given.
+    pub purls: Vec<String>,
+    pub items: Vec<PackageDependent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PackageDependent {
+    pub project_id: i32,
+    pub project_name: String,
+    /// Latest successful scan of the project.
+    pub scan_id: i32,
+    /// Hops from the project root; 1 means a direct dependency.
+    pub depth: u32,
+    pub direct: bool,
+}
+
+/// Projects whose latest scan uses the package, directly or transitively.
+pub async fn list_dependents(
+    State(AppState { db, graph }): State<AppState>,
+    Path(package_id): Path<i32>,
+    Valid(Query(params)): Valid<Query<DependentsQuery>>,
+) -> ApiResult<ApiResponse<PackageDependents>> {
+    let package = find_package(&db, package_id).await?;
+
+    let purls = match params.version.as_deref() {
+        Some(version) => {
+            let purls: Vec<String> = package_version::Entity::find()
+                .select_only()
+                .column(package_version::Column::Purl)
+                .filter(package_version::Column::PackageId.eq(package.id))
+                .filter(package_version::Column::Version.eq(version))
+                .order_by_asc(package_version::Column::Id)
+                .into_tuple()
+                .all(&db)
+                .await?;
+            if purls.is_empty() {
+                return Err(ApiError::Biz(format!(
+                    "version {version} of {} was never scanned",
+                    package.purl
+                )));
+            }
+            purls
+        }
+        None => vec![package.purl],
+    };
+
+    let dependents = graph
+        .reverse_dependents(&purls, params.max_depth.unwrap_or(MAX_TRAVERSAL_DEPTH))
+        .await?;
+
+    let names: HashMap<i32, String> = project::Entity::find()
+        .filter(project::Column::Id.is_in(dependents.iter().map(|d| d.project_id)))
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p.name))
+        .collect();
+
+    let items = dependents
+        .into_iter()
+        .filter_map(|d| {
+            names.get(&d.project_id).map(|name| PackageDependent {
+                project_id: d.project_id,
+                project_name: name.clone(),
+                scan_id: d.scan_id,
+                depth: d.depth,
+                direct: d.depth == 1,
+            })
+        })
+        .collect();
+
+    Ok(ApiResponse::ok(
+        "package dependents",
+        Some(PackageDependents {
+            package_id,
+            purls,
+            items,
+        }),
+    ))
+}
@@ -0,0 +1,87 @@
+use axum::extract::State;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    DatabaseBackend, DatabaseConnection, EntityTrait, FromQueryResult, JsonValue, Statement,
+};
+use serde::Serialize;
+
+use crate::{
+    app::AppState,
+    common::{ApiError, ApiResponse, ApiResult},
+    entity::package,
+    params::path::Path,
+};
+
+#[derive(Debug, Serialize)]
+pub struct PackageDetail {
+    pub id: i32,
+    pub purl: String,
+    pub purl_type: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    pub qualifiers: Option<JsonValue>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Every version seen in any scan, most recently seen first.
+    pub versions: Vec<PackageVersionSummary>,
+}
+
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct PackageVersionSummary {
+    pub id: i32,
+    pub purl: String,
+    pub version: Option<String>,
+    /// SPDX expression of the declared licenses.
+    pub license_expression: Option<String>,
+    pub licenses: JsonValue,
+    pub first_seen_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    /// Projects that recorded this version as a component in any scan.
+    pub project_count: i64,
+}
+
+pub async fn get_package(
+    State(AppState { db, .. }): State<AppState>,
+    Path(package_id): Path<i32>,
+) -> ApiResult<ApiResponse<PackageDetail>> {
+    let package = find_package(&db, package_id).await?;
+
+    let versions = PackageVersionSummary::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT v.id, v.purl, v.version, v.license_expression, v.licenses, \
+                v.first_seen_at, v.last_seen_at, \
+                (SELECT COUNT(DISTINCT d.project_id) FROM direct_dependencies d \
+                 WHERE d.package_version_id = v.id) AS project_count \
+         FROM package_versions v \
+         WHERE v.package_id = $1 \
+         ORDER BY v.last_seen_at DESC, v.id DESC",
+        [package_id.into()],
+    ))
+    .all(&db)
+    .await?;
+
+    Ok(ApiResponse::ok(
+        "package detail",
+        Some(PackageDetail {
+            id: package.id,
+            purl: package.purl,
+            purl_type: package.purl_type,
+            namespace: package.namespace,
+            name: package.name,
+            qualifiers: package.qualifiers,
+            created_at: package.created_at,
+            updated_at: package.updated_at,
+            versions,
+        }),
+    ))
+}
+
+pub(crate) async fn find_package(
+    db: &DatabaseConnection,
+    package_id: i32,
+) -> ApiResult<package::Model> {
+    package::Entity::find_by_id(package_id)
+        .one(db)
+        .await?
+        .ok_or(ApiError::NotFound)
+}
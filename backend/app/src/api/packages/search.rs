@@ -0,0 +1,187 @@
+use axum::extract::State;
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseBackend, FromQueryResult, JsonValue, Statement, Value};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    api::projects::list::escape_like,
+    app::AppState,
+    common::{ApiResponse, ApiResult},
+    params::{
+        pagination::{Page, PaginationParams},
+        query::Query,
+        valid::Valid,
+    },
+};
+
+/// How `name` and `namespace` are compared against the catalog.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchMode {
+    Exact,
+    #[default]
+    Prefix,
+    /// Trigram similarity (pg_trgm) or substring match.
+    Fuzzy,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ListPackagesQuery {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub pagination: PaginationParams,
+    /// Purl type such as npm, maven or cargo.
+    pub purl_type: Option<String>,
+    #[validate(length(max = 255))]
+    pub namespace: Option<String>,
+    #[validate(length(max = 255))]
+    pub name: Option<String>,
+    #[serde(default, rename = "match")]
+    pub match_mode: MatchMode,
+    /// Only packages with a version under this license, see [`license_condition`].
+    #[validate(length(max = 255))]
+    pub license: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct PackageSummary {
+    pub id: i32,
+    /// Versionless purl.
+    pub purl: String,
+    pub purl_type: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    pub version_count: i64,
+    /// License ids declared by any version, sorted.
+    pub licenses: JsonValue,
+    /// Most recent time any version of the package showed up in a scan.
+    pub last_seen_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CountRow {
+    total: i64,
+}
+
+/// Search the package catalog; with `match=fuzzy` results are ranked by similarity.
+pub async fn list_packages(
+    State(AppState { db, .. }): State<AppState>,
+    Valid(Query(params)): Valid<Query<ListPackagesQuery>>,
+) -> ApiResult<ApiResponse<Page<PackageSummary>>> {
+    let mut conditions = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+    let mut rank = Vec::new();
+
+    if let Some(purl_type) = params.purl_type.as_deref().filter(|t| !t.is_empty()) {
+        values.push(purl_type.to_lowercase().into());
+        conditions.push(format!("p.purl_type = ${}", values.len()));
+    }
+
+    for (column, term) in [("p.namespace", &params.namespace), ("p.name", &params.name)] {
+        let Some(term) = term.as_deref().filter(|t| !t.is_empty()) else {
+            continue;
+        };
+
+        match params.match_mode {
+            MatchMode::Exact => {
+                values.push(term.to_string().into());
+                conditions.push(format!("{column} = ${}", values.len()));
+            }
+            MatchMode::Prefix => {
+                values.push(format!("{}%", escape_like(term)).into());
+                conditions.push(format!("{column} ILIKE ${} ESCAPE '\\'", values.len()));
+            }
+            MatchMode::Fuzzy => {
+                values.push(term.to_string().into());
+                let raw = values.len();
+                values.push(format!("%{}%", escape_like(term)).into());
+                let like = values.len();
+                conditions.push(format!(
+                    "({column} % ${raw} OR {column} ILIKE ${like} ESCAPE '\\')"
+                ));
+                rank.push(format!("similarity({column}, ${raw}) DESC"));
+            }
+        }
+    }
+
+    if let Some(license) = params.license.as_deref().filter(|l| !l.is_empty()) {
+        conditions.push(format!(
+            "EXISTS (SELECT 1 FROM package_versions lv WHERE lv.package_id = p.id AND {})",
+            license_condition("lv.licenses", license, &mut values)
+        ));
+    }
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+
+    let total = CountRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!("SELECT COUNT(*) AS total FROM packages p {where_clause}"),
+        values.clone(),
+    ))
+    .one(&db)
+    .await?
+    .map(|row| row.total as u64)
+    .unwrap_or(0);
+
+    if total == 0 {
+        return Ok(ApiResponse::ok(
+            "package list",
+            Some(Page::from_pagination(params.pagination, 0, Vec::new())),
+        ));
+    }
+
+    rank.push("p.purl".to_string());
+    let order_clause = rank.join(", ");
+
+    let pagination = params.pagination;
+    values.push((pagination.per_page as i64).into());
+    let limit = values.len();
+    values.push(pagination.offset().into());
+    let offset = values.len();
+
+    let items = PackageSummary::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT p.id, p.purl, p.purl_type, p.namespace, p.name, \
+                    COUNT(v.id) AS version_count, \
+                    (SELECT COALESCE(jsonb_agg(DISTINCT l.id ORDER BY l.id), '[]'::jsonb) \
+                     FROM package_versions lv, jsonb_array_elements_text(lv.licenses) AS l(id) \
+                     WHERE lv.package_id = p.id) AS licenses, \
+                    MAX(v.last_seen_at) AS last_seen_at \
+             FROM packages p \
+             LEFT JOIN package_versions v ON v.package_id = p.id \
+             {where_clause} \
+             GROUP BY p.id \
+             ORDER BY {order_clause} \
+             LIMIT ${limit} OFFSET ${offset}"
+        ),
+        values,
+    ))
+    .all(&db)
+    .await?;
+
+    Ok(ApiResponse::ok(
+        "package list",
+        Some(Page::from_pagination(pagination, total, items)),
+    ))
+}
+
+/// Condition on a jsonb array of license ids. `license` matches an id case-insensitively
+/// or as a prefix up to a dash, so `AGPL` covers `AGPL-3.0-only` and `AGPL-3.0-or-later`;
+/// `none` matches components without declared licenses.
+pub(crate) fn license_condition(licenses: &str, license: &str, values: &mut Vec<Value>) -> String {
+    if license.eq_ignore_ascii_case("none") {
+        return format!("{licenses} = '[]'::jsonb");
+    }
+    values.push(escape_like(license).into());
+    let idx = values.len();
+    format!(
+        "EXISTS (SELECT 1 FROM jsonb_array_elements_text({licenses}) AS l(id) \
+         WHERE l.id ILIKE ${idx} ESCAPE '\\' OR l.id ILIKE (${idx} || '-%') ESCAPE '\\')"
+    )
+}
@@ -0,0 +1,152 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use axum::extract::State;
+use sea_orm::{DatabaseBackend, FromQueryResult, JsonValue, Statement};
+use serde::Serialize;
+
+use super::detail::find_package;
+use crate::{
+    api::projects::vulnerabilities::SEVERITY_ORDER,
+    app::AppState,
+    common::{ApiResponse, ApiResult},
+    params::path::Path,
+    version::{compare, Ecosystem},
+};
+
+#[derive(Debug, Serialize)]
+pub struct PackageVulnerabilities {
+    pub package_id: i32,
+    pub purl: String,
+    pub items: Vec<PackageAdvisory>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PackageAdvisory {
+    pub vulnerability_id: i32,
+    pub osv_id: String,
+    pub summary: Option<String>,
+    pub severity: Option<String>,
+    pub cvss_score: Option<f64>,
+    pub aliases: JsonValue,
+    pub ranges: Vec<AdvisoryRange>,
+    /// Versions of the package in projects' latest scans that the advisory affects.
+    pub affected_versions: Vec<String>,
+    /// Projects whose latest scan has a finding for this advisory and package.
+    pub affected_projects: usize,
+}
+
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct AdvisoryRange {
+    #[serde(skip)]
+    pub vulnerability_id: i32,
+    pub range_type: String,
+    pub introduced: Option<String>,
+    pub fixed: Option<String>,
+    pub last_affected: Option<String>,
+    pub versions: Option<JsonValue>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct AdvisoryRow {
+    id: i32,
+    osv_id: String,
+    summary: Option<String>,
+    severity: Option<String>,
+    cvss_score: Option<f64>,
+    aliases: JsonValue,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct FindingRow {
+    vulnerability_id: i32,
+    project_id: i32,
+    version: Option<String>,
+}
+
+/// Advisories that affect any version of the package, with their exposure in the org.
+pub async fn list_package_vulnerabilities(
+    State(AppState { db, .. }): State<AppState>,
+    Path(package_id): Path<i32>,
+) -> ApiResult<ApiResponse<PackageVulnerabilities>> {
+    let package = find_package(&db, package_id).await?;
+    let ecosystem = Ecosystem::from_purl_type(&package.purl_type);
+
+    let advisories = AdvisoryRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT vu.id, vu.osv_id, vu.summary, vu.severity, vu.cvss_score, vu.aliases \
+             FROM vulnerabilities vu \
+             WHERE vu.withdrawn_at IS NULL \
+               AND EXISTS (SELECT 1 FROM affected_ranges r \
+                           WHERE r.vulnerability_id = vu.id AND r.package_purl = $1) \
+             ORDER BY {SEVERITY_ORDER}, vu.osv_id"
+        ),
+        [package.purl.clone().into()],
+    ))
+    .all(&db)
+    .await?;
+
+    let mut ranges: BTreeMap<i32, Vec<AdvisoryRange>> = BTreeMap::new();
+    for range in AdvisoryRange::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT r.vulnerability_id, r.range_type, r.introduced, r.fixed, r.last_affected, \
+                r.versions \
+         FROM affected_ranges r WHERE r.package_purl = $1 ORDER BY r.id",
+        [package.purl.clone().into()],
+    ))
+    .all(&db)
+    .await?
+    {
+        ranges
+            .entry(range.vulnerability_id)
+            .or_default()
+            .push(range);
+    }
+
+    let mut exposure: BTreeMap<i32, (BTreeSet<String>, BTreeSet<i32>)> = BTreeMap::new();
+    for finding in FindingRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT f.vulnerability_id, f.project_id, f.version \
+         FROM vulnerability_findings f \
+         WHERE f.package_id = $1 \
+           AND f.scan_id IN (SELECT MAX(id) FROM scans WHERE status = 'success' \
+                             GROUP BY project_id)",
+        [package.id.into()],
+    ))
+    .all(&db)
+    .await?
+    {
+        let (versions, projects) = exposure.entry(finding.vulnerability_id).or_default();
+        versions.extend(finding.version);
+        projects.insert(finding.project_id);
+    }
+
+    let items = advisories
+        .into_iter()
+        .map(|advisory| {
+            let (versions, projects) = exposure.remove(&advisory.id).unwrap_or_default();
+            let mut affected_versions: Vec<String> = versions.into_iter().collect();
+            affected_versions.sort_by(|a, b| compare(ecosystem, a, b));
+            PackageAdvisory {
+                ranges: ranges.remove(&advisory.id).unwrap_or_default(),
+                vulnerability_id: advisory.id,
+                osv_id: advisory.osv_id,
+                summary: advisory.summary,
+                severity: advisory.severity,
+                cvss_score: advisory.cvss_score,
+                aliases: advisory.aliases,
+                affected_versions,
+                affected_projects: projects.len(),
+            }
+        })
+        .collect();
+
+    Ok(ApiResponse::ok(
+        "package vulnerabilities",
+        Some(PackageVulnerabilities {
+            package_id: package.id,
+            purl: package.purl,
+            items,
+        }),
+    ))
+}
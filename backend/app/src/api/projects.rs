@@ -1,15 +1,63 @@
-use axum::{routing::{get, post}, Router};
+use axum::{handler::Handler, routing::{delete, get, post}, Router};
+pub mod analyses;
+pub mod detail;
+pub mod diff;
+pub mod freshness;
+pub mod licenses;
+pub mod list;
 pub mod register;
+pub mod rescan;
+pub mod scans;
+pub mod vex;
+pub mod vulnerabilities;
+pub mod waivers;
+pub mod why;
 
-use crate::{app::AppState, common::{error::ApiError, response::ApiResponse}};
+use crate::{app::AppState, auth::get_jwt_auth_layer};
+use analyses::{analysis_history, list_analyses, record_project_analysis};
+use detail::{delete_project, get_project, update_project};
+use diff::diff_project_scans;
+use freshness::project_freshness;
+use licenses::list_license_violations;
+use list::list_projects;
 use register::register_project;
+use rescan::rescan_project;
+use scans::list_scans;
+use vex::{export_vex, import_vex};
+use vulnerabilities::list_vulnerabilities;
+use waivers::{create_waiver, list_waivers, revoke_waiver};
+use why::why_dependency;
 
 pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/", get(list_projects))
         .route("/register", post(register_project))
-}
-
-async fn list_projects() -> Result<ApiResponse<&'static str>, ApiError> {
-    Ok(ApiResponse::ok("project list", Some("stub")))
+        .route(
+            "/{id}",
+            get(get_project).patch(update_project).delete(delete_project),
+        )
+        .route("/{id}/scan", post(rescan_project))
+        .route("/{id}/scans", get(list_scans))
+        .route("/{id}/diff", get(diff_project_scans))
+        .route("/{id}/why", get(why_dependency))
+        .route("/{id}/vulnerabilities", get(list_vulnerabilities))
+        .route(
+            "/{id}/analyses",
+            get(list_analyses).post(record_project_analysis.layer(get_jwt_auth_layer())),
+        )
+        .route("/{id}/analyses/{analysis_id}/history", get(analysis_history))
+        .route(
+            "/{id}/vex",
+            get(export_vex).post(import_vex.layer(get_jwt_auth_layer())),
+        )
+        .route("/{id}/license-violations", get(list_license_violations))
+        .route("/{id}/freshness", get(project_freshness))
+        .route(
+            "/{id}/waivers",
+            get(list_waivers).post(create_waiver.layer(get_jwt_auth_layer())),
+        )
+        .route(
+            "/{id}/waivers/{waiver_id}",
+            delete(revoke_waiver.layer(get_jwt_auth_layer())),
+        )
 }
@@ -0,0 +1,218 @@
+use axum::{extract::State, Extension, Json};
+use axum_valid::Valid;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ColumnTrait, DatabaseBackend, EntityTrait, FromQueryResult, QueryFilter, QueryOrder, Statement,
+    Value,
+};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::detail::find_project;
+use crate::{
+    app::AppState,
+    auth::Principal,
+    common::{ApiError, ApiResponse, ApiResult},
+    entity::{vulnerability, vulnerability_analysis, vulnerability_analysis_event},
+    params::{
+        pagination::{Page, PaginationParams},
+        path::Path,
+        query::Query,
+    },
+    triage::{record_analysis, AnalysisChange, AnalysisState},
+};
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ListAnalysesQuery {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub pagination: PaginationParams,
+    /// Only analyses in this state.
+    pub state: Option<AnalysisState>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct RecordAnalysis {
+    pub vulnerability_id: i32,
+    /// Versioned purl of the component, as reported in the finding.
+    #[validate(length(min = 1, max = 2048))]
+    pub purl: String,
+    pub state: AnalysisState,
+    /// Required for `not_affected`, in CycloneDX or OpenVEX vocabulary.
+    pub justification: Option<String>,
+    #[validate(length(max = 4096))]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct Analysis {
+    pub id: i32,
+    pub vulnerability_id: i32,
+    pub osv_id: String,
+    pub purl: String,
+    pub state: String,
+    pub justification: Option<String>,
+    pub detail: Option<String>,
+    pub author_id: String,
+    pub author: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AnalysisEvent {
+    pub id: i32,
+    pub state: String,
+    pub justification: Option<String>,
+    pub detail: Option<String>,
+    pub author_id: String,
+    pub author: String,
+    pub source: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CountRow {
+    total: i64,
+}
+
+const ANALYSIS_COLUMNS: &str = "a.id, a.vulnerability_id, vu.osv_id, a.purl, a.state, \
+     a.justification, a.detail, a.author_id, a.author, a.created_at, a.updated_at";
+
+/// Current analyses of a project, most recently changed first.
+pub async fn list_analyses(
+    State(AppState { db, .. }): State<AppState>,
+    Path(project_id): Path<i32>,
+    Valid(Query(params)): Valid<Query<ListAnalysesQuery>>,
+) -> ApiResult<ApiResponse<Page<Analysis>>> {
+    find_project(&db, project_id).await?;
+
+    let mut values: Vec<Value> = vec![project_id.into()];
+    let mut where_clause = "WHERE a.project_id = $1".to_string();
+    if let Some(state) = params.state {
+        values.push(state.as_str().into());
+        where_clause.push_str(&format!(" AND a.state = ${}", values.len()));
+    }
+
+    let total = CountRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!("SELECT COUNT(*) AS total FROM vulnerability_analyses a {where_clause}"),
+        values.clone(),
+    ))
+    .one(&db)
+    .await?
+    .map(|row| row.total as u64)
+    .unwrap_or(0);
+
+    if total == 0 {
+        return Ok(ApiResponse::ok(
+            "analysis list",
+            Some(Page::from_pagination(params.pagination, 0, Vec::new())),
+        ));
+    }
+
+    let pagination = params.pagination;
+    values.push((pagination.per_page as i64).into());
+    let limit = values.len();
+    values.push(pagination.offset().into());
+    let offset = values.len();
+
+    let items = Analysis::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT {ANALYSIS_COLUMNS} FROM vulnerability_analyses a \
+             JOIN vulnerabilities vu ON vu.id = a.vulnerability_id {where_clause} \
+             ORDER BY a.updated_at DESC, a.id DESC \
+             LIMIT ${limit} OFFSET ${offset}"
+        ),
+        values,
+    ))
+    .all(&db)
+    .await?;
+
+    Ok(ApiResponse::ok(
+        "analysis list",
+        Some(Page::from_pagination(pagination, total, items)),
+    ))
+}
+
+/// Set the analysis of an advisory on one of the project's components. The caller
+/// becomes the author and the change is appended to the analysis history.
+pub async fn record_project_analysis(
+    State(AppState { db, .. }): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    Path(project_id): Path<i32>,
+    Valid(Json(payload)): Valid<Json<RecordAnalysis>>,
+) -> ApiResult<ApiResponse<Analysis>> {
+    find_project(&db, project_id).await?;
+    vulnerability::Entity::find_by_id(payload.vulnerability_id)
+        .one(&db)
+        .await?
+        .ok_or_else(|| ApiError::Biz("unknown vulnerability".into()))?;
+
+    let change = AnalysisChange {
+        vulnerability_id: payload.vulnerability_id,
+        purl: payload.purl,
+        state: payload.state,
+        justification: payload.justification.filter(|j| !j.is_empty()),
+        detail: payload.detail.filter(|d| !d.is_empty()),
+    };
+    change.validate().map_err(ApiError::Validation)?;
+
+    let recorded = record_analysis(&db, project_id, &change, &principal, "api").await?;
+    let message = match recorded {
+        Some(_) => "analysis recorded",
+        None => "analysis unchanged",
+    };
+
+    let analysis = Analysis::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT {ANALYSIS_COLUMNS} FROM vulnerability_analyses a \
+             JOIN vulnerabilities vu ON vu.id = a.vulnerability_id \
+             WHERE a.project_id = $1 AND a.vulnerability_id = $2 AND a.purl = $3"
+        ),
+        [
+            project_id.into(),
+            change.vulnerability_id.into(),
+            change.purl.into(),
+        ],
+    ))
+    .one(&db)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    Ok(ApiResponse::ok(message, Some(analysis)))
+}
+
+/// Every change of an analysis, oldest first.
+pub async fn analysis_history(
+    State(AppState { db, .. }): State<AppState>,
+    Path((project_id, analysis_id)): Path<(i32, i32)>,
+) -> ApiResult<ApiResponse<Vec<AnalysisEvent>>> {
+    vulnerability_analysis::Entity::find_by_id(analysis_id)
+        .filter(vulnerability_analysis::Column::ProjectId.eq(project_id))
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let events = vulnerability_analysis_event::Entity::find()
+        .filter(vulnerability_analysis_event::Column::AnalysisId.eq(analysis_id))
+        .order_by_asc(vulnerability_analysis_event::Column::Id)
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(|event| AnalysisEvent {
+            id: event.id,
+            state: event.state,
+            justification: event.justification,
+            detail: event.detail,
+            author_id: event.author_id,
+            author: event.author,
+            source: event.source,
+            created_at: event.created_at,
+        })
+        .collect();
+
+    Ok(ApiResponse::ok("analysis history", Some(events)))
+}
@@ -0,0 +1,300 @@
+use std::collections::BTreeSet;
+
+use axum::{extract::State, Json};
+use axum_valid::Valid;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseBackend, DatabaseConnection,
+    EntityTrait, JsonValue, PaginatorTrait, QueryFilter, QueryOrder, Set, Statement,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use validator::Validate;
+
+use crate::{
+    app::AppState,
+    common::{ApiError, ApiResponse, ApiResult},
+    entity::{
+        dependency_edge, direct_dependency, kev_alert, license_violation, package_health_finding,
+        policy_evaluation, policy_waiver, project, scan, vulnerability_analysis,
+        vulnerability_finding,
+    },
+    params::path::Path,
+    storage,
+};
+
+#[derive(Debug, Serialize)]
+pub struct ProjectDetail {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub team: Option<String>,
+    pub tags: Vec<String>,
+    pub repo_url: Option<String>,
+    pub homepage: Option<String>,
+    pub purl: Option<String>,
+    pub default_branch: Option<String>,
+    pub revision: Option<String>,
+    pub package_manager: Option<String>,
+    pub manifest_path: Option<String>,
+    pub lockfile_path: Option<String>,
+    pub sbom_path: Option<String>,
+    pub source_path: Option<String>,
+    pub sbom_format: Option<String>,
+    pub last_scanned_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub scan_count: u64,
+    pub last_scan_id: Option<i32>,
+    pub last_scan_status: Option<String>,
+    /// Dependencies recorded by the most recent scan.
+    pub dependency_count: u64,
+}
+
+/// Editable project fields; omitted fields are left unchanged.
+/// An empty `description` or `team` clears it.
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct UpdateProject {
+    #[validate(length(max = 2048))]
+    pub description: Option<String>,
+    #[validate(length(max = 255))]
+    pub team: Option<String>,
+    /// Replaces every tag; an empty list clears them.
+    #[validate(length(max = 32))]
+    pub tags: Option<Vec<String>>,
+    #[validate(url)]
+    pub homepage: Option<String>,
+    #[validate(length(min = 1, max = 255))]
+    pub default_branch: Option<String>,
+    #[validate(url)]
+    pub repo_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeletedProject {
+    pub project_id: i32,
+    pub scans_deleted: u64,
+    pub artifacts_deleted: usize,
+}
+
+pub async fn get_project(
+    State(AppState { db, .. }): State<AppState>,
+    Path(project_id): Path<i32>,
+) -> ApiResult<ApiResponse<ProjectDetail>> {
+    let project = find_project(&db, project_id).await?;
+    let detail = build_detail(&db, project).await?;
+
+    Ok(ApiResponse::ok("project detail", Some(detail)))
+}
+
+pub async fn update_project(
+    State(AppState { db, .. }): State<AppState>,
+    Path(project_id): Path<i32>,
+    Valid(Json(payload)): Valid<Json<UpdateProject>>,
+) -> ApiResult<ApiResponse<ProjectDetail>> {
+    let project = find_project(&db, project_id).await?;
+    let mut active: project::ActiveModel = project.into();
+
+    if let Some(description) = payload.description {
+        active.description = Set(Some(description).filter(|d| !d.is_empty()));
+    }
+    if let Some(team) = payload.team {
+        active.team = Set(Some(team).filter(|t| !t.is_empty()));
+    }
+    if let Some(tags) = payload.tags {
+        active.tags = Set(project_tags(tags));
+    }
+    if let Some(homepage) = payload.homepage {
+        active.homepage = Set(Some(homepage));
+    }
+    if let Some(default_branch) = payload.default_branch {
+        active.default_branch = Set(Some(default_branch));
+    }
+    if let Some(repo_url) = payload.repo_url {
+        active.repo_url = Set(Some(repo_url));
+    }
+    active.updated_at = Set(Utc::now());
+
+    let project = active.update(&db).await?;
+    let detail = build_detail(&db, project).await?;
+
+    Ok(ApiResponse::ok("project updated", Some(detail)))
+}
+
+/// Delete the project with its scans, dependencies, graph data and stored artifacts.
+pub async fn delete_project(
+    State(AppState { db, graph }): State<AppState>,
+    Path(project_id): Path<i32>,
+) -> ApiResult<ApiResponse<DeletedProject>> {
+    let project = find_project(&db, project_id).await?;
+
+    let scans = scan::Entity::find()
+        .filter(scan::Column::ProjectId.eq(project_id))
+        .all(&db)
+        .await?;
+
+    let artifacts: BTreeSet<String> = scans
+        .iter()
+        .flat_map(|s| [s.sbom_path.clone(), s.source_path.clone()])
+        .chain([project.sbom_path.clone(), project.source_path.clone()])
+        .flatten()
+        .collect();
+
+    let txn = db.begin().await?;
+
+    dependency_edge::Entity::delete_many()
+        .filter(dependency_edge::Column::ProjectId.eq(project_id))
+        .exec(&txn)
+        .await?;
+
+    txn.execute_raw(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "DELETE FROM dependency_edges_archive WHERE project_id = $1",
+        [project_id.into()],
+    ))
+    .await?;
+
+    vulnerability_finding::Entity::delete_many()
+        .filter(vulnerability_finding::Column::ProjectId.eq(project_id))
+        .exec(&txn)
+        .await?;
+
+    vulnerability_analysis::Entity::delete_many()
+        .filter(vulnerability_analysis::Column::ProjectId.eq(project_id))
+        .exec(&txn)
+        .await?;
+
+    kev_alert::Entity::delete_many()
+        .filter(kev_alert::Column::ProjectId.eq(project_id))
+        .exec(&txn)
+        .await?;
+
+    license_violation::Entity::delete_many()
+        .filter(license_violation::Column::ProjectId.eq(project_id))
+        .exec(&txn)
+        .await?;
+
+    package_health_finding::Entity::delete_many()
+        .filter(package_health_finding::Column::ProjectId.eq(project_id))
+        .exec(&txn)
+        .await?;
+
+    policy_evaluation::Entity::delete_many()
+        .filter(policy_evaluation::Column::ProjectId.eq(project_id))
+        .exec(&txn)
+        .await?;
+
+    policy_waiver::Entity::delete_many()
+        .filter(policy_waiver::Column::ProjectId.eq(project_id))
+        .exec(&txn)
+        .await?;
+
+    direct_dependency::Entity::delete_many()
+        .filter(direct_dependency::Column::ProjectId.eq(project_id))
+        .exec(&txn)
+        .await?;
+
+    let scans_deleted = scan::Entity::delete_many()
+        .filter(scan::Column::ProjectId.eq(project_id))
+        .exec(&txn)
+        .await?
+        .rows_affected;
+
+    project::Entity::delete_by_id(project_id).exec(&txn).await?;
+
+    txn.commit().await?;
+
+    if let Err(err) = graph.delete_project(project_id).await {
+        warn!(error = ?err, backend = graph.backend(), project_id, "failed to delete project graph");
+    }
+
+    let mut artifacts_deleted = 0;
+    for path in &artifacts {
+        match storage::delete_artifact(path).await {
+            Ok(()) => artifacts_deleted += 1,
+            Err(err) => warn!(error = ?err, path = %path, "failed to delete project artifact"),
+        }
+    }
+
+    Ok(ApiResponse::ok(
+        "project deleted",
+        Some(DeletedProject {
+            project_id,
+            scans_deleted,
+            artifacts_deleted,
+        }),
+    ))
+}
+
+pub(crate) async fn find_project(
+    db: &DatabaseConnection,
+    project_id: i32,
+) -> ApiResult<project::Model> {
+    project::Entity::find_by_id(project_id)
+        .one(db)
+        .await?
+        .ok_or(ApiError::NotFound)
+}
+
+/// Tags as stored: trimmed, without empty ones, sorted and distinct.
+pub(super) fn project_tags(tags: Vec<String>) -> JsonValue {
+    let tags: BTreeSet<String> = tags
+        .into_iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    serde_json::json!(tags)
+}
+
+async fn build_detail(
+    db: &DatabaseConnection,
+    project: project::Model,
+) -> ApiResult<ProjectDetail> {
+    let scan_count = scan::Entity::find()
+        .filter(scan::Column::ProjectId.eq(project.id))
+        .count(db)
+        .await?;
+
+    let last_scan = scan::Entity::find()
+        .filter(scan::Column::ProjectId.eq(project.id))
+        .order_by_desc(scan::Column::Id)
+        .one(db)
+        .await?;
+
+    let dependency_count = match &last_scan {
+        Some(last) => {
+            direct_dependency::Entity::find()
+                .filter(direct_dependency::Column::ScanId.eq(last.id))
+                .count(db)
+                .await?
+        }
+        None => 0,
+    };
+
+    Ok(ProjectDetail {
+        id: project.id,
+        name: project.name,
+        description: project.description,
+        team: project.team,
+        tags: serde_json::from_value(project.tags).unwrap_or_default(),
+        repo_url: project.repo_url,
+        homepage: project.homepage,
+        purl: project.purl,
+        default_branch: project.default_branch,
+        revision: project.revision,
+        package_manager: project.package_manager,
+        manifest_path: project.manifest_path,
+        lockfile_path: project.lockfile_path,
+        sbom_path: project.sbom_path,
+        source_path: project.source_path,
+        sbom_format: project.sbom_format,
+        last_scanned_at: project.last_scanned_at,
+        created_at: project.created_at,
+        updated_at: project.updated_at,
+        scan_count,
+        last_scan_id: last_scan.as_ref().map(|s| s.id),
+        last_scan_status: last_scan.and_then(|s| s.status),
+        dependency_count,
+    })
+}
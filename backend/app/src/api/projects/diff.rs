@@ -0,0 +1,61 @@
+use axum::extract::State;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde::Deserialize;
+use validator::Validate;
+
+use super::detail::find_project;
+use crate::{
+    app::AppState,
+    common::{ApiError, ApiResponse, ApiResult},
+    diff::{diff_scans, latest_successful_scan, ScanDiff},
+    entity::scan,
+    params::{path::Path, query::Query, valid::Valid},
+};
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct DiffQuery {
+    /// Older scan; defaults to the successful scan before `to`.
+    pub from: Option<i32>,
+    /// Newer scan; defaults to the latest successful scan.
+    pub to: Option<i32>,
+}
+
+pub async fn diff_project_scans(
+    State(AppState { db, graph, .. }): State<AppState>,
+    Path(project_id): Path<i32>,
+    Valid(Query(params)): Valid<Query<DiffQuery>>,
+) -> ApiResult<ApiResponse<ScanDiff>> {
+    find_project(&db, project_id).await?;
+
+    let to = match params.to {
+        Some(id) => id,
+        None => previous_scan(&db, project_id, None).await?,
+    };
+    let from = match params.from {
+        Some(id) => id,
+        None => previous_scan(&db, project_id, Some(to)).await?,
+    };
+
+    for scan_id in [from, to] {
+        scan::Entity::find_by_id(scan_id)
+            .filter(scan::Column::ProjectId.eq(project_id))
+            .one(&db)
+            .await?
+            .ok_or(ApiError::NotFound)?;
+    }
+
+    let diff = diff_scans(&db, graph.as_ref(), project_id, from, to).await?;
+
+    Ok(ApiResponse::ok("scan diff", Some(diff)))
+}
+
+/// [`latest_successful_scan`], failing when the project has too few scans to diff.
+async fn previous_scan(
+    db: &DatabaseConnection,
+    project_id: i32,
+    before: Option<i32>,
+) -> ApiResult<i32> {
+    latest_successful_scan(db, project_id, before)
+        .await?
+        .ok_or_else(|| ApiError::Biz("project needs two successful scans to diff".into()))
+}
@@ -0,0 +1,54 @@
+use axum::extract::State;
+use serde::{Deserialize, Serialize};
+
+use super::{detail::find_project, vulnerabilities::project_scan};
+use crate::{
+    app::AppState,
+    common::{ApiResponse, ApiResult},
+    freshness::{scan_freshness, summarize, ComponentFreshness, FreshnessSummary},
+    params::{path::Path, query::Query},
+};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FreshnessQuery {
+    /// Scan to report on; defaults to the latest successful scan.
+    pub scan_id: Option<i32>,
+    /// Only components with a newer stable release.
+    #[serde(default)]
+    pub outdated: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FreshnessReport {
+    pub project_id: i32,
+    pub scan_id: i32,
+    pub summary: FreshnessSummary,
+    /// Most libyears behind first.
+    pub components: Vec<ComponentFreshness>,
+}
+
+/// How far the components of a scan are behind their registries.
+pub async fn project_freshness(
+    State(AppState { db, .. }): State<AppState>,
+    Path(project_id): Path<i32>,
+    Query(params): Query<FreshnessQuery>,
+) -> ApiResult<ApiResponse<FreshnessReport>> {
+    find_project(&db, project_id).await?;
+    let scan_id = project_scan(&db, project_id, params.scan_id).await?;
+
+    let mut components = scan_freshness(&db, scan_id).await?;
+    let summary = summarize(&components);
+    if params.outdated {
+        components.retain(|component| component.versions_behind.is_some_and(|behind| behind > 0));
+    }
+
+    Ok(ApiResponse::ok(
+        "freshness report",
+        Some(FreshnessReport {
+            project_id,
+            scan_id,
+            summary,
+            components,
+        }),
+    ))
+}
@@ -0,0 +1,123 @@
+use axum::extract::State;
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseBackend, FromQueryResult, JsonValue, Statement, Value};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::{detail::find_project, vulnerabilities::project_scan};
+use crate::{
+    app::AppState,
+    common::{ApiResponse, ApiResult},
+    params::{
+        pagination::{Page, PaginationParams},
+        path::Path,
+        query::Query,
+        valid::Valid,
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViolationOutcome {
+    Denied,
+    Review,
+}
+
+impl ViolationOutcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            ViolationOutcome::Denied => "denied",
+            ViolationOutcome::Review => "review",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct LicenseViolationsQuery {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub pagination: PaginationParams,
+    /// Scan to report on; defaults to the latest successful scan.
+    pub scan_id: Option<i32>,
+    pub outcome: Option<ViolationOutcome>,
+}
+
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct LicenseViolation {
+    pub id: i32,
+    pub scan_id: i32,
+    pub purl: String,
+    pub scope: Option<String>,
+    pub license_expression: Option<String>,
+    pub outcome: String,
+    /// License terms that kept the component from being allowed.
+    pub licenses: JsonValue,
+    pub reason: String,
+    /// Shortest paths from the project root; the first hop is a direct dependency.
+    pub paths: JsonValue,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CountRow {
+    total: i64,
+}
+
+/// License policy violations of a scan, denied ones first.
+pub async fn list_license_violations(
+    State(AppState { db, .. }): State<AppState>,
+    Path(project_id): Path<i32>,
+    Valid(Query(params)): Valid<Query<LicenseViolationsQuery>>,
+) -> ApiResult<ApiResponse<Page<LicenseViolation>>> {
+    find_project(&db, project_id).await?;
+    let scan_id = project_scan(&db, project_id, params.scan_id).await?;
+
+    let mut values: Vec<Value> = vec![scan_id.into()];
+    let mut where_clause = "WHERE l.scan_id = $1".to_string();
+    if let Some(outcome) = params.outcome {
+        values.push(outcome.as_str().into());
+        where_clause.push_str(&format!(" AND l.outcome = ${}", values.len()));
+    }
+
+    let total = CountRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!("SELECT COUNT(*) AS total FROM license_violations l {where_clause}"),
+        values.clone(),
+    ))
+    .one(&db)
+    .await?
+    .map(|row| row.total as u64)
+    .unwrap_or(0);
+
+    if total == 0 {
+        return Ok(ApiResponse::ok(
+            "license violations",
+            Some(Page::from_pagination(params.pagination, 0, Vec::new())),
+        ));
+    }
+
+    let pagination = params.pagination;
+    values.push((pagination.per_page as i64).into());
+    let limit = values.len();
+    values.push(pagination.offset().into());
+    let offset = values.len();
+
+    let items = LicenseViolation::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT l.id, l.scan_id, l.purl, l.scope, l.license_expression, l.outcome, \
+                    l.licenses, l.reason, l.paths, l.created_at \
+             FROM license_violations l {where_clause} \
+             ORDER BY CASE l.outcome WHEN 'denied' THEN 0 ELSE 1 END, l.purl \
+             LIMIT ${limit} OFFSET ${offset}"
+        ),
+        values,
+    ))
+    .all(&db)
+    .await?;
+
+    Ok(ApiResponse::ok(
+        "license violations",
+        Some(Page::from_pagination(pagination, total, items)),
+    ))
+}
@@ -0,0 +1,175 @@
+use axum::extract::State;
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseBackend, FromQueryResult, Statement, Value};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    app::AppState,
+    common::{ApiResponse, ApiResult},
+    params::{
+        pagination::{Page, PaginationParams},
+        query::Query,
+        valid::Valid,
+    },
+};
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectSort {
+    #[default]
+    Name,
+    LastScanned,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ListProjectsQuery {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub pagination: PaginationParams,
+    /// Case-insensitive substring of the project name.
+    #[validate(length(max = 255))]
+    pub name: Option<String>,
+    pub team: Option<String>,
+    pub package_manager: Option<String>,
+    /// Status of the most recent scan (pending/running/success/failed).
+    pub status: Option<String>,
+    #[serde(default)]
+    pub sort: ProjectSort,
+    #[serde(default)]
+    pub order: SortOrder,
+}
+
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct ProjectSummary {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub team: Option<String>,
+    pub repo_url: Option<String>,
+    pub package_manager: Option<String>,
+    pub last_scanned_at: Option<DateTime<Utc>>,
+    pub last_scan_id: Option<i32>,
+    pub last_scan_status: Option<String>,
+    /// Dependencies recorded by the most recent scan.
+    pub dependency_count: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CountRow {
+    total: i64,
+}
+
+pub async fn list_projects(
+    State(AppState { db, .. }): State<AppState>,
+    Valid(Query(params)): Valid<Query<ListProjectsQuery>>,
+) -> ApiResult<ApiResponse<Page<ProjectSummary>>> {
+    let mut conditions = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+
+    if let Some(name) = params.name.as_deref().filter(|n| !n.is_empty()) {
+        values.push(format!("%{}%", escape_like(name)).into());
+        conditions.push(format!("p.name ILIKE ${} ESCAPE '\\'", values.len()));
+    }
+
+    if let Some(manager) = params.package_manager.as_deref().filter(|m| !m.is_empty()) {
+        values.push(manager.to_string().into());
+        conditions.push(format!("p.package_manager = ${}", values.len()));
+    }
+
+    if let Some(team) = params.team.as_deref().filter(|t| !t.is_empty()) {
+        values.push(team.to_string().into());
+        conditions.push(format!("p.team = ${}", values.len()));
+    }
+
+    if let Some(status) = params.status.as_deref().filter(|s| !s.is_empty()) {
+        values.push(status.to_string().into());
+        conditions.push(format!("s.status = ${}", values.len()));
+    }
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+
+    let from_clause = "FROM projects p \
+         LEFT JOIN LATERAL ( \
+             SELECT id, status FROM scans WHERE project_id = p.id ORDER BY id DESC LIMIT 1 \
+         ) s ON TRUE";
+
+    let total = CountRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!("SELECT COUNT(*) AS total {from_clause} {where_clause}"),
+        values.clone(),
+    ))
+    .one(&db)
+    .await?
+    .map(|row| row.total as u64)
+    .unwrap_or(0);
+
+    if total == 0 {
+        return Ok(ApiResponse::ok(
+            "project list",
+            Some(Page::from_pagination(params.pagination, 0, Vec::new())),
+        ));
+    }
+
+    let order = params.order.as_sql();
+    let order_clause = match params.sort {
+        ProjectSort::Name => format!("p.name {order}, p.id"),
+        ProjectSort::LastScanned => format!("p.last_scanned_at {order} NULLS LAST, p.id"),
+    };
+
+    let pagination = params.pagination;
+    values.push((pagination.per_page as i64).into());
+    let limit = values.len();
+    values.push(pagination.offset().into());
+    let offset = values.len();
+
+    let items = ProjectSummary::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT p.id, p.name, p.description, p.team, p.repo_url, p.package_manager, \
+                    p.last_scanned_at, s.id AS last_scan_id, s.status AS last_scan_status, \
+                    (SELECT COUNT(*) FROM direct_dependencies d WHERE d.scan_id = s.id) \
+                        AS dependency_count \
+             {from_clause} {where_clause} \
+             ORDER BY {order_clause} \
+             LIMIT ${limit} OFFSET ${offset}"
+        ),
+        values,
+    ))
+    .all(&db)
+    .await?;
+
+    Ok(ApiResponse::ok(
+        "project list",
+        Some(Page::from_pagination(pagination, total, items)),
+    ))
+}
+
+/// Escape LIKE wildcards so user input is matched literally.
+pub(crate) fn escape_like(input: &str) -> String {
+    input
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
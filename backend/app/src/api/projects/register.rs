@@ -1,4 +1,5 @@
 use std::{
+    collections::{HashMap, HashSet},
     fs,
     fs::File,
     path::{Path, PathBuf},
@@ -7,12 +8,6 @@ use std::{
 };
 
 use anyhow::{anyhow, Context};
-use aws_config::BehaviorVersion;
-use aws_sdk_s3::{
-    Client as S3Client,
-    config::{Credentials, Region},
-    primitives::ByteStream,
-};
 use axum::{extract::State, Json};
 use axum_valid::Valid;
 use chrono::Utc;
@@ -23,25 +18,37 @@ use reqwest::Client;
 use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use neo4rs::{query, Graph};
 use tar::Builder;
 use tracing::{debug, warn};
 use tokio::task;
 use validator::Validate;
 use walkdir::WalkDir;
 
+use super::detail::project_tags;
 use crate::{
     app::AppState,
     common::{ApiError, ApiResponse, ApiResult},
-    config::{LanguagesConfig, S3Config},
-    entity::{direct_dependency, package, project, scan},
+    config::LanguagesConfig,
+    entity::{direct_dependency, package, package_version, project, scan},
+    graph::{DependencyEdge, PackageNode, ProjectNode, ScanGraph, SharedGraphStore},
     id,
+    license::{self, LicenseChoice, Licenses},
+    policy,
+    purl,
+    registry,
+    storage::{build_s3_client, upload_bytes_to_s3, upload_file_to_s3},
+    vulnerability::{self, cpe::Cpe},
 };
 
 #[derive(Debug, Deserialize, Validate, Clone)]
 pub struct NewProject {
     pub name: String,
     pub description: Option<String>,
+    #[validate(length(min = 1, max = 255))]
+    pub team: Option<String>,
+    #[serde(default)]
+    #[validate(length(max = 32))]
+    pub tags: Vec<String>,
     #[validate(url)]
     pub repo_url: String,
     pub store_sbom: Option<bool>,
@@ -58,8 +65,24 @@ pub struct RegisterResponse {
 
 #[derive(Debug, Deserialize, Serialize)]
 struct CycloneDxBom {
+    metadata: Option<CycloneDxMetadata>,
     #[serde(default)]
     components: Vec<CycloneDxComponent>,
+    #[serde(default)]
+    dependencies: Vec<CycloneDxDependency>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct CycloneDxMetadata {
+    component: Option<CycloneDxComponent>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct CycloneDxDependency {
+    #[serde(rename = "ref")]
+    reference: String,
+    #[serde(rename = "dependsOn", default)]
+    depends_on: Vec<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -69,21 +92,115 @@ struct CycloneDxComponent {
     purl: Option<String>,
     version: Option<String>,
     scope: Option<String>,
+    cpe: Option<String>,
+    #[serde(default)]
+    licenses: Vec<CycloneDxLicenseChoice>,
     #[serde(default)]
     properties: Vec<CycloneDxProperty>,
 }
 
+/// One entry of a component's `licenses`: a license or an SPDX expression.
+#[derive(Debug, Deserialize, Serialize)]
+struct CycloneDxLicenseChoice {
+    license: Option<CycloneDxLicense>,
+    expression: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct CycloneDxLicense {
+    id: Option<String>,
+    name: Option<String>,
+}
+
+impl CycloneDxComponent {
+    /// Declared licenses normalized to an SPDX expression.
+    fn licenses(&self) -> Option<Licenses> {
+        license::resolve(self.licenses.iter().filter_map(|choice| {
+            match (&choice.expression, &choice.license) {
+                (Some(expression), _) => Some(LicenseChoice::Expression(expression)),
+                (None, Some(CycloneDxLicense { id: Some(id), .. })) => Some(LicenseChoice::Id(id)),
+                (None, Some(CycloneDxLicense { name: Some(name), .. })) => {
+                    Some(LicenseChoice::Name(name))
+                }
+                _ => None,
+            }
+        }))
+    }
+}
+
+/// Rows a component purl resolved to.
+#[derive(Debug, Clone, Copy)]
+struct PackageRef {
+    package_id: i32,
+    version_id: i32,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct CycloneDxProperty {
     name: String,
     value: Value,
 }
 
+/// Artifacts of one SBOM run, before anything is written to the database.
+pub(super) struct ScanArtifacts {
+    tmp_dir: PathBuf,
+    pub(super) package_manager: Option<String>,
+    bom: CycloneDxBom,
+    pub(super) sbom_path: Option<String>,
+    pub(super) source_path: Option<String>,
+}
+
+impl ScanArtifacts {
+    /// Remove the temp dir unless a stored artifact path points into it.
+    pub(super) fn cleanup(&self) {
+        cleanup_tmp_dir_if_unused(&self.tmp_dir, &self.sbom_path, &self.source_path);
+    }
+}
+
 #[axum::debug_handler]
 pub async fn register_project(
-    State(AppState { db, neo4j }): State<AppState>,
+    State(AppState { db, graph }): State<AppState>,
     Valid(Json(payload)): Valid<Json<NewProject>>,
 ) -> ApiResult<ApiResponse<RegisterResponse>> {
+    // 1-4) Clone, render SBOM and store artifacts.
+    let artifacts = generate_artifacts(&payload).await?;
+
+    // 5) Persist project + scan.
+    let now = Utc::now();
+    let register_response = persist_project_and_scan(
+        &db,
+        &payload,
+        &artifacts.package_manager,
+        &artifacts.sbom_path,
+        &artifacts.source_path,
+        now,
+    )
+    .await?;
+
+    record_dependencies(
+        &db,
+        &graph,
+        ProjectNode {
+            id: register_response.project_id,
+            name: payload.name.clone(),
+            repo_url: Some(payload.repo_url.clone()),
+            package_manager: artifacts.package_manager.clone(),
+        },
+        register_response.scan_id,
+        &artifacts,
+    )
+    .await?;
+
+    // 6) Cleanup temp dir if unused.
+    artifacts.cleanup();
+
+    Ok(ApiResponse::ok(
+        "project registered",
+        Some(register_response),
+    ))
+}
+
+pub(super) async fn generate_artifacts(payload: &NewProject) -> ApiResult<ScanArtifacts> {
     // 1) Prepare workspace and repo context.
     let tmp_dir = create_tmp_dir()?;
     let package_type = clone_and_detect(&payload.repo_url, &tmp_dir).await?;
@@ -91,67 +208,96 @@ pub async fn register_project(
     // 2) Render SBOM and persist locally.
     let sbom_local_path = tmp_dir.join("bom.json");
     let sbom = render_and_store_sbom(&payload.repo_url, package_type, &sbom_local_path).await?;
-    let components = parse_cyclonedx_components(&sbom)?;
+    let bom = parse_cyclonedx_bom(&sbom)?;
 
     // 3) Optionally archive source.
-    let source_archive_path = maybe_archive_source(&payload, &tmp_dir).await?;
+    let source_archive_path = maybe_archive_source(payload, &tmp_dir).await?;
 
     // 4) Optionally upload artifacts.
     let (sbom_path, source_path) =
-        maybe_upload_to_s3(&payload, sbom.as_bytes(), source_archive_path.as_deref()).await?;
+        maybe_upload_to_s3(payload, sbom.as_bytes(), source_archive_path.as_deref()).await?;
 
-    // 5) Persist project + scan.
-    let now = Utc::now();
-    let pm_string = package_type.as_str().map(str::to_string);
     let (sbom_path_to_store, source_path_to_store) = resolve_artifact_paths(
-        &payload,
+        payload,
         &sbom_path,
         &source_path,
         &sbom_local_path,
         source_archive_path.as_deref(),
     );
 
-    let register_response = persist_project_and_scan(
-        &db,
-        &payload,
-        &pm_string,
-        &sbom_path_to_store,
-        &source_path_to_store,
-        now,
-    )
-    .await?;
+    Ok(ScanArtifacts {
+        tmp_dir,
+        package_manager: package_type.as_str().map(str::to_string),
+        bom,
+        sbom_path: sbom_path_to_store,
+        source_path: source_path_to_store,
+    })
+}
 
-    insert_direct_dependencies(
-        &db,
-        register_response.project_id,
-        register_response.scan_id,
-        &pm_string,
-        &components,
+/// Store the scan's dependencies, sync its graph snapshot and prune superseded scans.
+pub(super) async fn record_dependencies(
+    db: &DatabaseConnection,
+    graph: &SharedGraphStore,
+    project: ProjectNode,
+    scan_id: i32,
+    artifacts: &ScanArtifacts,
+) -> ApiResult<()> {
+    let project_id = project.id;
+
+    let package_refs = insert_direct_dependencies(
+        db,
+        project_id,
+        scan_id,
+        &artifacts.package_manager,
+        &artifacts.bom.components,
     )
     .await?;
 
-    if let Some(graph) = neo4j.as_ref() {
-        if let Err(err) = sync_dependencies_to_neo4j(
-            graph,
-            register_response.project_id,
-            register_response.scan_id,
-            &payload,
-            pm_string.as_deref(),
-            &components,
-        )
-        .await
-        {
-            warn!(error = ?err, "failed to sync dependencies to Neo4j");
+    if let Err(err) = vulnerability::match_scan(db, scan_id).await {
+        warn!(error = ?err, scan_id, "failed to match scan against vulnerabilities");
+    }
+    if let Err(err) = registry::health::check_scan(db, project_id, scan_id).await {
+        warn!(error = ?err, scan_id, "failed to check package health");
+    }
+
+    let snapshot = build_scan_graph(project, scan_id, &artifacts.bom, &package_refs);
+
+    let synced = match graph.sync_scan(&snapshot).await {
+        Ok(()) => true,
+        Err(err) => {
+            warn!(error = ?err, backend = graph.backend(), "failed to sync dependency graph");
+            false
         }
+    };
+
+    // Violations carry dependency paths, so evaluate once the graph is in place. Without
+    // it they are still stored, just without paths.
+    if let Err(err) = license::policy::evaluate_scan(db, graph, project_id, scan_id).await {
+        warn!(error = ?err, scan_id, "failed to evaluate license policy");
+    }
+    match scan::Entity::find_by_id(scan_id).one(db).await {
+        Ok(Some(scan)) => {
+            if let Err(err) = policy::evaluate_scan(db, graph, &scan).await {
+                warn!(error = ?err, scan_id, "failed to evaluate policies");
+            }
+        }
+        Ok(None) => {}
+        Err(err) => warn!(error = ?err, scan_id, "failed to load scan for policy evaluation"),
     }
 
-    // 6) Cleanup temp dir if unused.
-    cleanup_tmp_dir_if_unused(&tmp_dir, &sbom_path_to_store, &source_path_to_store);
+    // Retention must not prune older snapshots while this one is missing from the graph.
+    if !synced {
+        return Ok(());
+    }
+    let retention = crate::config::get().graph();
+    if let Err(err) = graph
+        .apply_retention(project_id, retention.keep_superseded_scans(), retention.retention())
+        .await
+    {
+        warn!(error = ?err, backend = graph.backend(), "failed to apply graph retention");
+    }
 
-    Ok(ApiResponse::ok(
-        "project registered",
-        Some(register_response),
-    ))
+    Ok(())
 }
 
 fn create_tmp_dir() -> anyhow::Result<PathBuf> {
@@ -234,10 +380,111 @@ fn cleanup_tmp_dir_if_unused(
     }
 }
 
-fn parse_cyclonedx_components(sbom_json: &str) -> ApiResult<Vec<CycloneDxComponent>> {
+fn parse_cyclonedx_bom(sbom_json: &str) -> ApiResult<CycloneDxBom> {
     let bom: CycloneDxBom = serde_json::from_str(sbom_json)
         .with_context(|| "failed to parse CycloneDX SBOM")?;
-    Ok(bom.components)
+    Ok(bom)
+}
+
+/// Turn the CycloneDX `dependencies` section into purl edges.
+/// Components nobody depends on are attached to the project root so they stay reachable.
+fn build_scan_graph(
+    project: ProjectNode,
+    scan_id: i32,
+    bom: &CycloneDxBom,
+    package_refs: &HashMap<String, PackageRef>,
+) -> ScanGraph {
+    let mut packages = Vec::new();
+    let mut seen = HashSet::new();
+    let mut purl_by_ref = HashMap::new();
+
+    for component in &bom.components {
+        let Some(purl) = component.purl.as_deref() else {
+            continue;
+        };
+        let Some(package_ref) = package_refs.get(purl) else {
+            continue;
+        };
+
+        if let Some(bom_ref) = component.bom_ref.as_deref() {
+            purl_by_ref.insert(bom_ref, purl);
+        }
+
+        if !seen.insert(purl) {
+            continue;
+        }
+
+        let Ok(parsed) = PackageUrl::from_str(purl) else {
+            continue;
+        };
+
+        packages.push(PackageNode {
+            package_id: package_ref.package_id,
+            version_id: package_ref.version_id,
+            purl: purl.to_string(),
+            package_purl: purl::base_purl(&parsed),
+            purl_type: parsed.ty().to_string(),
+            namespace: parsed.namespace().map(|s| s.to_string()),
+            name: parsed.name().to_string(),
+            version: component
+                .version
+                .clone()
+                .or_else(|| parsed.version().map(|v| v.to_string())),
+            scope: component.scope.clone(),
+            bom_ref: component.bom_ref.clone(),
+        });
+    }
+
+    let root_ref = bom
+        .metadata
+        .as_ref()
+        .and_then(|m| m.component.as_ref())
+        .and_then(|c| c.bom_ref.as_deref());
+
+    let mut edges = HashSet::new();
+    for dependency in &bom.dependencies {
+        let parent = if Some(dependency.reference.as_str()) == root_ref {
+            None
+        } else {
+            match purl_by_ref.get(dependency.reference.as_str()) {
+                Some(purl) => Some(purl.to_string()),
+                None => continue,
+            }
+        };
+
+        for child_ref in &dependency.depends_on {
+            let Some(child) = purl_by_ref.get(child_ref.as_str()) else {
+                continue;
+            };
+
+            if parent.as_deref() == Some(*child) {
+                continue;
+            }
+
+            edges.insert(DependencyEdge {
+                parent: parent.clone(),
+                child: child.to_string(),
+            });
+        }
+    }
+
+    let children: HashSet<&str> = edges.iter().map(|e| e.child.as_str()).collect();
+    let orphans: Vec<DependencyEdge> = packages
+        .iter()
+        .filter(|p| !children.contains(p.purl.as_str()))
+        .map(|p| DependencyEdge {
+            parent: None,
+            child: p.purl.clone(),
+        })
+        .collect();
+    edges.extend(orphans);
+
+    ScanGraph {
+        project,
+        scan_id,
+        packages,
+        edges: edges.into_iter().collect(),
+    }
 }
 
 async fn insert_direct_dependencies(
@@ -246,20 +493,36 @@ async fn insert_direct_dependencies(
     scan_id: i32,
     pm_string: &Option<String>,
     components: &[CycloneDxComponent],
-) -> ApiResult<()> {
+) -> ApiResult<HashMap<String, PackageRef>> {
     let now_tz = Utc::now();
+    let mut package_refs = HashMap::new();
 
     for component in components {
         let Some(purl) = component.purl.as_deref() else {
             continue;
         };
 
-        let package_id = ensure_package(db, purl).await?;
+        let package_ref = match package_refs.get(purl) {
+            Some(existing) => *existing,
+            None => {
+                let resolved = ensure_package_version(
+                    db,
+                    purl,
+                    component.version.as_deref(),
+                    component.cpe.as_deref(),
+                    component.licenses().as_ref(),
+                )
+                .await?;
+                package_refs.insert(purl.to_string(), resolved);
+                resolved
+            }
+        };
 
         let dep_model = direct_dependency::ActiveModel {
             scan_id: Set(scan_id),
             project_id: Set(project_id),
-            package_id: Set(package_id),
+            package_id: Set(package_ref.package_id),
+            package_version_id: Set(Some(package_ref.version_id)),
             declared_constraint: Set(None),
             resolved_version: Set(component.version.clone()),
             scope: Set(component.scope.clone()),
@@ -275,116 +538,109 @@ async fn insert_direct_dependencies(
         let _ = dep_model.insert(db).await?;
     }
 
-    Ok(())
+    Ok(package_refs)
 }
 
-async fn sync_dependencies_to_neo4j(
-    graph: &Graph,
-    project_id: i32,
-    scan_id: i32,
-    payload: &NewProject,
-    pm: Option<&str>,
-    components: &[CycloneDxComponent],
-) -> anyhow::Result<()> {
-    let mut tx = graph.start_txn().await?;
-
-    tx.run(
-        query(
-            "MERGE (p:Project {id: $project_id}) \
-             SET p.name = $name, \
-                 p.repo_url = $repo_url, \
-                 p.package_manager = $package_manager, \
-                 p.updated_at = datetime()",
-        )
-        .param("project_id", project_id as i64)
-        .param("name", payload.name.as_str())
-        .param("repo_url", payload.repo_url.as_str())
-        .param("package_manager", pm.unwrap_or("")),
-    )
-    .await?;
+/// Find or create the versionless package and the concrete version behind `purl`,
+/// bumping the version's last-seen time. Declared licenses replace stored ones.
+async fn ensure_package_version(
+    db: &DatabaseConnection,
+    purl: &str,
+    component_version: Option<&str>,
+    cpe: Option<&str>,
+    licenses: Option<&Licenses>,
+) -> ApiResult<PackageRef> {
+    let parsed = PackageUrl::from_str(purl)
+        .map_err(|e| ApiError::Biz(format!("invalid purl: {e}")))?;
 
-    for component in components {
-        let Some(purl) = component.purl.as_deref() else {
-            continue;
-        };
+    let now_tz = Utc::now();
+    let package_id = ensure_package(db, &parsed, cpe, now_tz).await?;
 
-        let parsed = match PackageUrl::from_str(purl) {
-            Ok(p) => p,
-            Err(err) => {
-                warn!(error = ?err, "skip invalid purl during Neo4j sync, purl = {}", purl);
-                continue;
-            }
-        };
+    if let Some(existing) = package_version::Entity::find()
+        .filter(package_version::Column::Purl.eq(purl))
+        .one(db)
+        .await?
+    {
+        let version_id = existing.id;
+        let changed_licenses = licenses.filter(|licenses| {
+            existing.license_expression.as_deref() != Some(licenses.expression.as_str())
+        });
+        let mut active: package_version::ActiveModel = existing.into();
+        if let Some(licenses) = changed_licenses {
+            active.license_expression = Set(Some(licenses.expression.clone()));
+            active.licenses = Set(serde_json::json!(licenses.ids));
+        }
+        active.last_seen_at = Set(now_tz);
+        active.updated_at = Set(now_tz);
+        active.update(db).await?;
+
+        return Ok(PackageRef {
+            package_id,
+            version_id,
+        });
+    }
 
-        let pkg_name = parsed.name().to_string();
-        let pkg_type = parsed.ty().to_string();
-        let pkg_namespace = parsed
-            .namespace()
-            .map(|s| s.to_string())
-            .unwrap_or_default();
-
-        tx.run(
-            query(
-                "MERGE (pkg:Package {purl: $purl}) \
-                 SET pkg.name = $name, \
-                     pkg.type = $type, \
-                     pkg.namespace = $namespace, \
-                     pkg.updated_at = datetime()",
-            )
-            .param("purl", purl)
-            .param("name", pkg_name)
-            .param("type", pkg_type)
-            .param("namespace", pkg_namespace),
-        )
-        .await?;
+    let version = component_version
+        .map(str::to_string)
+        .or_else(|| parsed.version().map(|v| v.to_string()));
 
-        tx.run(
-            query(
-                "MATCH (p:Project {id: $project_id}), (pkg:Package {purl: $purl}) \
-                 MERGE (p)-[r:DEPENDS_ON {scan_id: $scan_id, purl: $purl}]->(pkg) \
-                 SET r.scope = $scope, \
-                     r.resolved_version = $resolved_version, \
-                     r.manager = $manager, \
-                     r.bom_ref = $bom_ref, \
-                     r.updated_at = datetime()",
-            )
-            .param("project_id", project_id as i64)
-            .param("scan_id", scan_id as i64)
-            .param("purl", purl)
-            .param("scope", component.scope.clone().unwrap_or_default())
-            .param("resolved_version", component.version.clone().unwrap_or_default())
-            .param("manager", pm.unwrap_or(""))
-            .param("bom_ref", component.bom_ref.clone().unwrap_or_default()),
-        )
-        .await?;
+    let inserted = package_version::ActiveModel {
+        package_id: Set(package_id),
+        purl: Set(purl.to_string()),
+        version: Set(version),
+        license_expression: Set(licenses.map(|licenses| licenses.expression.clone())),
+        licenses: Set(serde_json::json!(licenses
+            .map(|licenses| licenses.ids.as_slice())
+            .unwrap_or_default())),
+        first_seen_at: Set(now_tz),
+        last_seen_at: Set(now_tz),
+        created_at: Set(now_tz),
+        updated_at: Set(now_tz),
+        ..Default::default()
     }
+    .insert(db)
+    .await?;
 
-    tx.commit().await?;
-
-    Ok(())
+    Ok(PackageRef {
+        package_id,
+        version_id: inserted.id,
+    })
 }
 
-async fn ensure_package(db: &DatabaseConnection, purl: &str) -> ApiResult<i32> {
+/// Find or create the versionless package behind `parsed`. A reported CPE is stored
+/// without its version so NVD criteria can be matched against every version.
+async fn ensure_package(
+    db: &DatabaseConnection,
+    parsed: &PackageUrl<'_>,
+    cpe: Option<&str>,
+    now_tz: chrono::DateTime<Utc>,
+) -> ApiResult<i32> {
+    let base = purl::base_purl(parsed);
+    let cpe = cpe.and_then(Cpe::parse).map(|cpe| cpe.versionless());
+
     if let Some(existing) = package::Entity::find()
-        .filter(package::Column::Purl.eq(purl))
+        .filter(package::Column::Purl.eq(base.as_str()))
         .one(db)
         .await? {
-        return Ok(existing.id);
+        let id = existing.id;
+        if cpe.is_some() && existing.cpe != cpe {
+            let mut active: package::ActiveModel = existing.into();
+            active.cpe = Set(cpe);
+            active.updated_at = Set(now_tz);
+            active.update(db).await?;
+        }
+        return Ok(id);
     }
 
-    let parsed = PackageUrl::from_str(purl)
-        .map_err(|e| ApiError::Biz(format!("invalid purl: {e}")))?;
-
     let qualifiers = serde_json::to_value(parsed.qualifiers()).ok();
 
-    let now_tz = Utc::now();
-
     let pkg = package::ActiveModel {
-        purl: Set(purl.to_string()),
+        purl: Set(base),
         purl_type: Set(parsed.ty().to_string()),
         namespace: Set(parsed.namespace().map(|s| s.to_string())),
         name: Set(parsed.name().to_string()),
         qualifiers: Set(qualifiers),
+        cpe: Set(cpe),
         created_at: Set(now_tz),
         updated_at: Set(now_tz),
         ..Default::default()
@@ -414,6 +670,8 @@ async fn persist_project_and_scan(
     let project_model = project::ActiveModel {
         name: Set(payload.name.clone()),
         description: Set(payload.description.clone()),
+        team: Set(payload.team.clone()),
+        tags: Set(project_tags(payload.tags.clone())),
         repo_url: Set(Some(payload.repo_url.clone())),
         homepage: Set(None),
         purl: Set(None),
@@ -433,8 +691,27 @@ async fn persist_project_and_scan(
 
     let project = project_model.insert(db).await?;
 
+    let scan = insert_scan(db, project.id, pm_string, sbom_path_to_store, source_path_to_store, now)
+        .await?;
+
+    Ok(RegisterResponse {
+        project_id: project.id,
+        scan_id: scan.id,
+        sbom_path: sbom_path_to_store.clone(),
+        source_path: source_path_to_store.clone(),
+    })
+}
+
+pub(super) async fn insert_scan(
+    db: &DatabaseConnection,
+    project_id: i32,
+    pm_string: &Option<String>,
+    sbom_path_to_store: &Option<String>,
+    source_path_to_store: &Option<String>,
+    now: chrono::DateTime<Utc>,
+) -> ApiResult<scan::Model> {
     let scan_model = scan::ActiveModel {
-        project_id: Set(project.id),
+        project_id: Set(project_id),
         package_manager: Set(pm_string.clone()),
         manifest_path: Set(None),
         lockfile_path: Set(None),
@@ -453,14 +730,7 @@ async fn persist_project_and_scan(
         ..Default::default()
     };
 
-    let scan = scan_model.insert(db).await?;
-
-    Ok(RegisterResponse {
-        project_id: project.id,
-        scan_id: scan.id,
-        sbom_path: sbom_path_to_store.clone(),
-        source_path: source_path_to_store.clone(),
-    })
+    Ok(scan_model.insert(db).await?)
 }
 
 async fn clone_repo(repo_url: &str, dest: &Path) -> anyhow::Result<Repository> {
@@ -582,67 +852,6 @@ async fn maybe_upload_to_s3(
     Ok((sbom_path, source_path))
 }
 
-async fn build_s3_client(cfg: &S3Config, region: &str) -> anyhow::Result<S3Client> {
-    let mut loader =
-        aws_config::defaults(BehaviorVersion::latest()).region(Region::new(region.to_string()));
-
-    if let Some(endpoint) = cfg.endpoint() {
-        loader = loader.endpoint_url(endpoint);
-    }
-
-    let shared = loader.load().await;
-    let mut builder = aws_sdk_s3::config::Builder::from(&shared);
-
-    if let (Some(access_key), Some(secret)) = (cfg.access_key_id(), cfg.secret_access_key()) {
-        builder = builder
-            .credentials_provider(Credentials::new(access_key, secret, None, None, "static"));
-    }
-
-    if let Some(endpoint) = cfg.endpoint() {
-        builder = builder.endpoint_url(endpoint);
-    }
-
-    Ok(S3Client::from_conf(builder.build()))
-}
-
-async fn upload_bytes_to_s3(
-    client: &S3Client,
-    bucket: &str,
-    key: &str,
-    body: Vec<u8>,
-) -> anyhow::Result<String> {
-    client
-        .put_object()
-        .bucket(bucket)
-        .key(key)
-        .body(ByteStream::from(body))
-        .send()
-        .await?;
-
-    Ok(format!("s3://{}/{}", bucket, key))
-}
-
-async fn upload_file_to_s3(
-    client: &S3Client,
-    bucket: &str,
-    key: &str,
-    path: &Path,
-) -> anyhow::Result<String> {
-    let body = ByteStream::from_path(path)
-        .await
-        .with_context(|| format!("failed to open path {:?} for upload", path))?;
-
-    client
-        .put_object()
-        .bucket(bucket)
-        .key(key)
-        .body(body)
-        .send()
-        .await?;
-
-    Ok(format!("s3://{}/{}", bucket, key))
-}
-
 #[derive(Debug, Clone, Copy)]
 enum PackageManager {
     Npm,
@@ -0,0 +1,97 @@
+use axum::{extract::State, Json};
+use axum_valid::Valid;
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+use serde::Deserialize;
+use validator::Validate;
+
+use super::register::{
+    generate_artifacts, insert_scan, record_dependencies, NewProject, RegisterResponse,
+};
+use crate::{
+    app::AppState,
+    common::{ApiError, ApiResponse, ApiResult},
+    entity::project,
+    graph::ProjectNode,
+    params::path::Path,
+};
+
+#[derive(Debug, Default, Deserialize, Validate, Clone)]
+pub struct RescanRequest {
+    pub store_sbom: Option<bool>,
+    pub store_source: Option<bool>,
+}
+
+/// Run a fresh SBOM scan for an existing project; the new scan becomes the latest.
+pub async fn rescan_project(
+    State(AppState { db, graph }): State<AppState>,
+    Path(project_id): Path<i32>,
+    Valid(Json(payload)): Valid<Json<RescanRequest>>,
+) -> ApiResult<ApiResponse<RegisterResponse>> {
+    let project = project::Entity::find_by_id(project_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let repo_url = project
+        .repo_url
+        .clone()
+        .ok_or_else(|| ApiError::Biz("project has no repo_url to scan".into()))?;
+
+    let request = NewProject {
+        name: project.name.clone(),
+        description: project.description.clone(),
+        team: project.team.clone(),
+        tags: serde_json::from_value(project.tags.clone()).unwrap_or_default(),
+        repo_url,
+        store_sbom: payload.store_sbom,
+        store_source: payload.store_source,
+    };
+
+    let artifacts = generate_artifacts(&request).await?;
+
+    let now = Utc::now();
+    let scan = insert_scan(
+        &db,
+        project.id,
+        &artifacts.package_manager,
+        &artifacts.sbom_path,
+        &artifacts.source_path,
+        now,
+    )
+    .await?;
+
+    let mut active: project::ActiveModel = project.into();
+    active.package_manager = Set(artifacts.package_manager.clone());
+    active.sbom_path = Set(artifacts.sbom_path.clone());
+    active.source_path = Set(artifacts.source_path.clone());
+    active.last_scanned_at = Set(Some(now));
+    active.updated_at = Set(now);
+    let project = active.update(&db).await?;
+
+    record_dependencies(
+        &db,
+        &graph,
+        ProjectNode {
+            id: project.id,
+            name: project.name.clone(),
+            repo_url: project.repo_url.clone(),
+            package_manager: project.package_manager.clone(),
+        },
+        scan.id,
+        &artifacts,
+    )
+    .await?;
+
+    artifacts.cleanup();
+
+    Ok(ApiResponse::ok(
+        "project rescanned",
+        Some(RegisterResponse {
+            project_id: project.id,
+            scan_id: scan.id,
+            sbom_path: artifacts.sbom_path.clone(),
+            source_path: artifacts.source_path.clone(),
+        }),
+    ))
+}
@@ -0,0 +1,114 @@
+use axum::extract::State;
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseBackend, FromQueryResult, Statement, Value};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::detail::find_project;
+use crate::{
+    app::AppState,
+    common::{ApiResponse, ApiResult},
+    params::{
+        pagination::{Page, PaginationParams},
+        path::Path,
+        query::Query,
+        valid::Valid,
+    },
+};
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ListScansQuery {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub pagination: PaginationParams,
+    /// Only scans with this status (pending/running/success/failed).
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct ScanSummary {
+    pub id: i32,
+    pub project_id: i32,
+    pub status: Option<String>,
+    pub package_manager: Option<String>,
+    pub branch: Option<String>,
+    pub revision: Option<String>,
+    pub scanner: Option<String>,
+    pub sbom_format: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Wall-clock time between start and completion, when both are known.
+    pub duration_ms: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    /// Components recorded in `direct_dependencies` for this scan.
+    pub component_count: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CountRow {
+    total: i64,
+}
+
+/// Column list shared with the scan detail endpoint.
+pub(crate) const SCAN_SUMMARY_COLUMNS: &str = "s.id, s.project_id, s.status, s.package_manager, \
+     s.branch, s.revision, s.scanner, s.sbom_format, s.started_at, s.completed_at, \
+     (EXTRACT(EPOCH FROM (s.completed_at - s.started_at)) * 1000)::BIGINT AS duration_ms, \
+     s.created_at, \
+     (SELECT COUNT(*) FROM direct_dependencies d WHERE d.scan_id = s.id) AS component_count";
+
+/// Scan history of a project, newest first.
+pub async fn list_scans(
+    State(AppState { db, .. }): State<AppState>,
+    Path(project_id): Path<i32>,
+    Valid(Query(params)): Valid<Query<ListScansQuery>>,
+) -> ApiResult<ApiResponse<Page<ScanSummary>>> {
+    find_project(&db, project_id).await?;
+
+    let mut values: Vec<Value> = vec![project_id.into()];
+    let mut where_clause = "WHERE s.project_id = $1".to_string();
+
+    if let Some(status) = params.status.as_deref().filter(|s| !s.is_empty()) {
+        values.push(status.to_string().into());
+        where_clause.push_str(&format!(" AND s.status = ${}", values.len()));
+    }
+
+    let total = CountRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!("SELECT COUNT(*) AS total FROM scans s {where_clause}"),
+        values.clone(),
+    ))
+    .one(&db)
+    .await?
+    .map(|row| row.total as u64)
+    .unwrap_or(0);
+
+    if total == 0 {
+        return Ok(ApiResponse::ok(
+            "scan list",
+            Some(Page::from_pagination(params.pagination, 0, Vec::new())),
+        ));
+    }
+
+    let pagination = params.pagination;
+    values.push((pagination.per_page as i64).into());
+    let limit = values.len();
+    values.push(pagination.offset().into());
+    let offset = values.len();
+
+    let items = ScanSummary::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT {SCAN_SUMMARY_COLUMNS} FROM scans s {where_clause} \
+             ORDER BY s.id DESC \
+             LIMIT ${limit} OFFSET ${offset}"
+        ),
+        values,
+    ))
+    .all(&db)
+    .await?;
+
+    Ok(ApiResponse::ok(
+        "scan list",
+        Some(Page::from_pagination(pagination, total, items)),
+    ))
+}
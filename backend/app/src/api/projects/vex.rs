@@ -0,0 +1,322 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    str::FromStr,
+};
+
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use packageurl::PackageUrl;
+use sea_orm::{DatabaseBackend, FromQueryResult, JsonValue, Statement, TransactionTrait};
+use serde::{Deserialize, Serialize};
+
+use super::{detail::find_project, vulnerabilities::project_scan};
+use crate::{
+    app::AppState,
+    auth::Principal,
+    common::{ApiError, ApiResponse, ApiResult},
+    export::{
+        sbom::spdx_slug,
+        vex::{render, VexAnalysis, VexEntry, VexFormat, VexSubject},
+    },
+    params::{path::Path, query::Query},
+    purl::base_purl,
+    triage::{record_analysis, vex::parse, AnalysisChange, AnalysisState},
+};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct VexQuery {
+    #[serde(default)]
+    pub format: VexFormat,
+    /// Scan to report on; defaults to the latest successful scan.
+    pub scan_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportedVex {
+    pub format: VexFormat,
+    pub statements: usize,
+    /// Analyses created or changed.
+    pub recorded: usize,
+    /// Analyses that already had the imported state.
+    pub unchanged: usize,
+    pub skipped: Vec<SkippedStatement>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SkippedStatement {
+    pub vulnerability: String,
+    pub reason: String,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct FindingRow {
+    vulnerability_id: i32,
+    osv_id: String,
+    aliases: JsonValue,
+    purl: String,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct BomRefRow {
+    bom_ref: String,
+    purl: String,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct EntryRow {
+    osv_id: String,
+    source: String,
+    aliases: JsonValue,
+    severity: Option<String>,
+    cvss_score: Option<f64>,
+    cvss_vector: Option<String>,
+    purl: String,
+    state: Option<String>,
+    justification: Option<String>,
+    detail: Option<String>,
+    analysis_created_at: Option<DateTime<Utc>>,
+    analysis_updated_at: Option<DateTime<Utc>>,
+}
+
+/// Apply the assessments of a CycloneDX VEX or OpenVEX document to the findings of
+/// the project's latest scan. Statements are matched by advisory id or alias and by
+/// component purl; a versionless purl covers every version of the package.
+pub async fn import_vex(
+    State(AppState { db, .. }): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    Path(project_id): Path<i32>,
+    Json(document): Json<serde_json::Value>,
+) -> ApiResult<ApiResponse<ImportedVex>> {
+    find_project(&db, project_id).await?;
+    let (format, statements) =
+        parse(&document).map_err(|err| ApiError::Biz(format!("invalid VEX document: {err}")))?;
+    let scan_id = project_scan(&db, project_id, None).await?;
+
+    let findings = FindingRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT f.vulnerability_id, vu.osv_id, vu.aliases, f.purl \
+         FROM vulnerability_findings f \
+         JOIN vulnerabilities vu ON vu.id = f.vulnerability_id \
+         WHERE f.scan_id = $1",
+        [scan_id.into()],
+    ))
+    .all(&db)
+    .await?;
+    let package_purls: Vec<Option<String>> = findings
+        .iter()
+        .map(|finding| {
+            PackageUrl::from_str(&finding.purl)
+                .ok()
+                .map(|parsed| base_purl(&parsed))
+        })
+        .collect();
+
+    let bom_refs: HashMap<String, String> =
+        BomRefRow::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "SELECT d.bom_ref, COALESCE(v.purl, p.purl) AS purl \
+             FROM direct_dependencies d \
+             JOIN packages p ON p.id = d.package_id \
+             LEFT JOIN package_versions v ON v.id = d.package_version_id \
+             WHERE d.scan_id = $1 AND d.bom_ref IS NOT NULL",
+            [scan_id.into()],
+        ))
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(|row| (row.bom_ref, row.purl))
+        .collect();
+
+    let mut changes = Vec::new();
+    let mut skipped = Vec::new();
+    for statement in &statements {
+        let skip = |reason: &str| SkippedStatement {
+            vulnerability: statement.vulnerability.clone(),
+            reason: reason.to_string(),
+        };
+
+        let ids: HashSet<&str> = std::iter::once(statement.vulnerability.as_str())
+            .chain(statement.aliases.iter().map(String::as_str))
+            .collect();
+        let components: HashSet<&str> = statement
+            .components
+            .iter()
+            .map(|component| {
+                bom_refs
+                    .get(component)
+                    .map_or(component.as_str(), String::as_str)
+            })
+            .collect();
+        if components.is_empty() {
+            skipped.push(skip("no affected components"));
+            continue;
+        }
+
+        let targets: BTreeSet<(i32, &str)> = findings
+            .iter()
+            .zip(&package_purls)
+            .filter(|(finding, _)| {
+                ids.contains(finding.osv_id.as_str())
+                    || finding.aliases.as_array().is_some_and(|aliases| {
+                        aliases
+                            .iter()
+                            .filter_map(JsonValue::as_str)
+                            .any(|alias| ids.contains(alias))
+                    })
+            })
+            .filter(|(finding, package_purl)| {
+                components.contains(finding.purl.as_str())
+                    || package_purl
+                        .as_deref()
+                        .is_some_and(|purl| components.contains(purl))
+            })
+            .map(|(finding, _)| (finding.vulnerability_id, finding.purl.as_str()))
+            .collect();
+        if targets.is_empty() {
+            skipped.push(skip("no matching finding in the latest scan"));
+            continue;
+        }
+
+        let justification = statement
+            .justification
+            .clone()
+            .filter(|_| statement.state == AnalysisState::NotAffected);
+        let statement_changes: Vec<AnalysisChange> = targets
+            .into_iter()
+            .map(|(vulnerability_id, purl)| AnalysisChange {
+                vulnerability_id,
+                purl: purl.to_string(),
+                state: statement.state,
+                justification: justification.clone(),
+                detail: statement.detail.clone(),
+            })
+            .collect();
+        if let Err(reason) = statement_changes[0].validate() {
+            skipped.push(skip(&reason));
+            continue;
+        }
+        changes.extend(statement_changes);
+    }
+
+    let mut recorded = 0;
+    let txn = db.begin().await?;
+    for change in &changes {
+        if record_analysis(&txn, project_id, change, &principal, format.event_source())
+            .await?
+            .is_some()
+        {
+            recorded += 1;
+        }
+    }
+    txn.commit().await?;
+
+    Ok(ApiResponse::ok(
+        "vex imported",
+        Some(ImportedVex {
+            format,
+            statements: statements.len(),
+            recorded,
+            unchanged: changes.len() - recorded,
+            skipped,
+        }),
+    ))
+}
+
+/// The project's findings with their analyses as CycloneDX VEX or OpenVEX. The caller, when
+/// authenticated, is named as the author.
+pub async fn export_vex(
+    State(AppState { db, .. }): State<AppState>,
+    principal: Option<Extension<Principal>>,
+    Path(project_id): Path<i32>,
+    Query(params): Query<VexQuery>,
+) -> ApiResult<Response> {
+    let project = find_project(&db, project_id).await?;
+    let scan_id = project_scan(&db, project_id, params.scan_id).await?;
+
+    let rows = EntryRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT vu.osv_id, vu.source, vu.aliases, vu.severity, vu.cvss_score, vu.cvss_vector, \
+                f.purl, a.state, a.justification, a.detail, \
+                a.created_at AS analysis_created_at, a.updated_at AS analysis_updated_at \
+         FROM vulnerability_findings f \
+         JOIN vulnerabilities vu ON vu.id = f.vulnerability_id \
+         LEFT JOIN vulnerability_analyses a ON a.project_id = f.project_id \
+              AND a.vulnerability_id = f.vulnerability_id AND a.purl = f.purl \
+         WHERE f.scan_id = $1 \
+         ORDER BY vu.osv_id, f.purl",
+        [scan_id.into()],
+    ))
+    .all(&db)
+    .await?;
+
+    let entries: Vec<VexEntry> = rows
+        .into_iter()
+        .map(|row| {
+            let analysis = match (
+                row.state.as_deref().and_then(AnalysisState::from_name),
+                row.analysis_created_at,
+                row.analysis_updated_at,
+            ) {
+                (Some(state), Some(first_issued), Some(last_updated)) => Some(VexAnalysis {
+                    state,
+                    justification: row.justification,
+                    detail: row.detail,
+                    first_issued,
+                    last_updated,
+                }),
+                _ => None,
+            };
+            VexEntry {
+                vulnerability: row.osv_id,
+                source: row.source,
+                aliases: serde_json::from_value(row.aliases).unwrap_or_default(),
+                severity: row.severity,
+                cvss_score: row.cvss_score,
+                cvss_vector: row.cvss_vector,
+                purl: row.purl,
+                analysis,
+            }
+        })
+        .collect();
+
+    let subject = VexSubject {
+        bom_ref: project
+            .purl
+            .clone()
+            .unwrap_or_else(|| format!("project:{}", project.name)),
+        name: project.name.clone(),
+        purl: project.purl.clone(),
+        author: principal
+            .map(|Extension(principal)| principal.name)
+            .unwrap_or_else(|| env!("CARGO_PKG_NAME").to_string()),
+    };
+    let body = serde_json::to_string_pretty(&render(params.format, &subject, &entries))
+        .map_err(anyhow::Error::from)?;
+
+    let filename = format!(
+        "{}-scan-{}.{}",
+        spdx_slug(&project.name),
+        scan_id,
+        params.format.extension()
+    );
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                params.format.content_type().to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
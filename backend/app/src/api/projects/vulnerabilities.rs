@@ -0,0 +1,237 @@
+use axum::extract::State;
+use chrono::{DateTime, NaiveDate, Utc};
+use sea_orm::{
+    ColumnTrait, DatabaseBackend, DatabaseConnection, EntityTrait, FromQueryResult, JsonValue,
+    QueryFilter, QueryOrder, QuerySelect, Statement, Value,
+};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::detail::find_project;
+use crate::{
+    app::AppState,
+    common::{ApiError, ApiResponse, ApiResult},
+    entity::scan,
+    params::{
+        pagination::{Page, PaginationParams},
+        path::Path,
+        query::Query,
+        valid::Valid,
+    },
+    vulnerability::risk::EXPLOIT_JOIN,
+};
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ListVulnerabilitiesQuery {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub pagination: PaginationParams,
+    /// Scan to report on; defaults to the latest successful scan.
+    pub scan_id: Option<i32>,
+    /// Only findings of this severity (critical/high/medium/low).
+    pub severity: Option<String>,
+    /// Only findings with this analysis state, or `none` for findings not yet triaged.
+    pub analysis: Option<String>,
+    /// Only findings whose advisory is (`true`) or is not (`false`) in the KEV catalog.
+    pub kev: Option<bool>,
+    #[serde(default)]
+    pub sort: FindingSort,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingSort {
+    /// Most severe first.
+    #[default]
+    Severity,
+    /// Highest EPSS probability first.
+    Epss,
+    /// Known exploited first, ransomware use and then risk breaking ties.
+    Kev,
+    /// Highest combined risk score first.
+    Risk,
+}
+
+impl FindingSort {
+    pub fn order_clause(self) -> String {
+        match self {
+            FindingSort::Severity => format!("{SEVERITY_ORDER}, vu.osv_id, f.purl"),
+            FindingSort::Epss => {
+                format!("ex.epss DESC NULLS LAST, {SEVERITY_ORDER}, vu.osv_id, f.purl")
+            }
+            FindingSort::Kev => "ex.kev_date_added IS NULL, ex.kev_ransomware DESC, \
+                 ex.risk_score DESC, vu.osv_id, f.purl"
+                .to_string(),
+            FindingSort::Risk => {
+                format!("ex.risk_score DESC, {SEVERITY_ORDER}, vu.osv_id, f.purl")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct VulnerabilityFinding {
+    pub id: i32,
+    pub scan_id: i32,
+    pub vulnerability_id: i32,
+    pub osv_id: String,
+    /// `osv` or `nvd`.
+    pub source: String,
+    pub summary: Option<String>,
+    pub severity: Option<String>,
+    pub cvss_score: Option<f64>,
+    /// CVE/GHSA ids of the same advisory.
+    pub aliases: JsonValue,
+    pub project_id: i32,
+    pub project_name: String,
+    pub package_id: i32,
+    pub purl: String,
+    pub version: Option<String>,
+    /// Versions that are no longer affected, lowest first.
+    pub fixed_versions: JsonValue,
+    pub published_at: Option<DateTime<Utc>>,
+    /// Triage state recorded for the project, advisory and component.
+    pub analysis_state: Option<String>,
+    pub analysis_justification: Option<String>,
+    /// EPSS probability of exploitation in the next 30 days.
+    pub epss: Option<f64>,
+    pub epss_percentile: Option<f64>,
+    /// Listed in the CISA Known Exploited Vulnerabilities catalog.
+    pub in_kev: bool,
+    pub kev_date_added: Option<NaiveDate>,
+    pub kev_due_date: Option<NaiveDate>,
+    pub kev_ransomware: bool,
+    /// Severity weighted by exploit likelihood, 0 to 100.
+    pub risk_score: f64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CountRow {
+    total: i64,
+}
+
+/// Columns of [`VulnerabilityFinding`] over findings `f`, projects `pr`, advisories `vu`,
+/// analyses `a` and exploit data `ex`.
+pub(crate) const FINDING_COLUMNS: &str = "f.id, f.scan_id, f.vulnerability_id, vu.osv_id, \
+     vu.source, vu.summary, vu.severity, vu.cvss_score, vu.aliases, f.project_id, \
+     pr.name AS project_name, f.package_id, f.purl, f.version, f.fixed_versions, \
+     vu.published_at, a.state AS analysis_state, a.justification AS analysis_justification, \
+     ex.epss, ex.epss_percentile, ex.kev_date_added IS NOT NULL AS in_kev, ex.kev_date_added, \
+     ex.kev_due_date, ex.kev_ransomware, ex.risk_score";
+
+/// Joins [`FINDING_COLUMNS`] relies on, from findings `f`, apart from `ex` which
+/// [`EXPLOIT_JOIN`] adds after them.
+pub(crate) const FINDING_JOINS: &str = "JOIN projects pr ON pr.id = f.project_id \
+     JOIN vulnerabilities vu ON vu.id = f.vulnerability_id \
+     LEFT JOIN vulnerability_analyses a ON a.project_id = f.project_id \
+     AND a.vulnerability_id = f.vulnerability_id AND a.purl = f.purl";
+
+/// Most severe first; unrated advisories last.
+pub(crate) const SEVERITY_ORDER: &str = "CASE vu.severity WHEN 'critical' THEN 0 \
+     WHEN 'high' THEN 1 WHEN 'medium' THEN 2 WHEN 'low' THEN 3 ELSE 4 END, \
+     vu.cvss_score DESC NULLS LAST";
+
+/// Known vulnerabilities in a project's components, most severe first unless another
+/// order is requested.
+pub async fn list_vulnerabilities(
+    State(AppState { db, .. }): State<AppState>,
+    Path(project_id): Path<i32>,
+    Valid(Query(params)): Valid<Query<ListVulnerabilitiesQuery>>,
+) -> ApiResult<ApiResponse<Page<VulnerabilityFinding>>> {
+    find_project(&db, project_id).await?;
+    let scan_id = project_scan(&db, project_id, params.scan_id).await?;
+
+    let mut values: Vec<Value> = vec![scan_id.into()];
+    let mut where_clause = "WHERE f.scan_id = $1".to_string();
+
+    if let Some(severity) = params.severity.as_deref().filter(|s| !s.is_empty()) {
+        values.push(severity.to_ascii_lowercase().into());
+        where_clause.push_str(&format!(" AND vu.severity = ${}", values.len()));
+    }
+
+    match params.analysis.as_deref().filter(|s| !s.is_empty()) {
+        None => {}
+        Some("none") => where_clause.push_str(" AND a.id IS NULL"),
+        Some(state) => {
+            values.push(state.to_ascii_lowercase().into());
+            where_clause.push_str(&format!(" AND a.state = ${}", values.len()));
+        }
+    }
+
+    match params.kev {
+        Some(true) => where_clause.push_str(" AND ex.kev_date_added IS NOT NULL"),
+        Some(false) => where_clause.push_str(" AND ex.kev_date_added IS NULL"),
+        None => {}
+    }
+
+    let total = CountRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT COUNT(*) AS total FROM vulnerability_findings f \
+             {FINDING_JOINS} {EXPLOIT_JOIN} {where_clause}"
+        ),
+        values.clone(),
+    ))
+    .one(&db)
+    .await?
+    .map(|row| row.total as u64)
+    .unwrap_or(0);
+
+    if total == 0 {
+        return Ok(ApiResponse::ok(
+            "vulnerability list",
+            Some(Page::from_pagination(params.pagination, 0, Vec::new())),
+        ));
+    }
+
+    let pagination = params.pagination;
+    values.push((pagination.per_page as i64).into());
+    let limit = values.len();
+    values.push(pagination.offset().into());
+    let offset = values.len();
+
+    let order_clause = params.sort.order_clause();
+    let items = VulnerabilityFinding::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT {FINDING_COLUMNS} FROM vulnerability_findings f \
+             {FINDING_JOINS} {EXPLOIT_JOIN} {where_clause} \
+             ORDER BY {order_clause} \
+             LIMIT ${limit} OFFSET ${offset}"
+        ),
+        values,
+    ))
+    .all(&db)
+    .await?;
+
+    Ok(ApiResponse::ok(
+        "vulnerability list",
+        Some(Page::from_pagination(pagination, total, items)),
+    ))
+}
+
+/// The requested scan if it belongs to the project, else its latest successful scan.
+pub(crate) async fn project_scan(
+    db: &DatabaseConnection,
+    project_id: i32,
+    scan_id: Option<i32>,
+) -> ApiResult<i32> {
+    let mut query = scan::Entity::find().filter(scan::Column::ProjectId.eq(project_id));
+    query = match scan_id {
+        Some(scan_id) => query.filter(scan::Column::Id.eq(scan_id)),
+        None => query
+            .filter(scan::Column::Status.eq("success"))
+            .order_by_desc(scan::Column::Id),
+    };
+
+    query
+        .select_only()
+        .column(scan::Column::Id)
+        .into_tuple::<i32>()
+        .one(db)
+        .await?
+        .ok_or_else(|| match scan_id {
+            Some(_) => ApiError::NotFound,
+            None => ApiError::Biz("project has no successful scan".into()),
+        })
+}
@@ -0,0 +1,280 @@
+use axum::{extract::State, Extension, Json};
+use axum_valid::Valid;
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseBackend, EntityTrait, FromQueryResult, QueryFilter, Set,
+    Statement, Value,
+};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::detail::find_project;
+use crate::{
+    app::AppState,
+    auth::Principal,
+    common::{ApiError, ApiResponse, ApiResult},
+    entity::policy_waiver,
+    params::{
+        pagination::{Page, PaginationParams},
+        path::Path,
+        query::Query,
+    },
+    policy::load_policies,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WaiverStatus {
+    /// Neither expired nor revoked, including expiring ones.
+    Active,
+    /// Active and inside the reminder window.
+    Expiring,
+    Expired,
+    Revoked,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ListWaiversQuery {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub pagination: PaginationParams,
+    pub status: Option<WaiverStatus>,
+}
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct NewWaiver {
+    /// Rule id, `<policy>/<rule>`.
+    #[validate(length(min = 1, max = 255))]
+    pub rule: String,
+    /// Purl pattern of the waived components, e.g. `pkg:npm/lodash@4.17.*`.
+    #[validate(length(min = 1, max = 2048))]
+    pub purl: String,
+    #[validate(length(min = 1, max = 4096))]
+    pub justification: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Waiver {
+    pub id: i32,
+    pub project_id: i32,
+    pub rule: String,
+    pub purl: String,
+    pub justification: String,
+    pub approver_id: String,
+    pub approver: String,
+    pub expires_at: DateTime<Utc>,
+    /// `active`, `expiring`, `expired` or `revoked`.
+    pub status: &'static str,
+    pub reminded_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revoked_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CountRow {
+    total: i64,
+}
+
+impl Waiver {
+    fn from_model(model: policy_waiver::Model, now: DateTime<Utc>) -> Self {
+        let status = if model.revoked_at.is_some() {
+            "revoked"
+        } else if model.expires_at <= now {
+            "expired"
+        } else if model.expires_at <= reminder_horizon(now) {
+            "expiring"
+        } else {
+            "active"
+        };
+        Self {
+            id: model.id,
+            project_id: model.project_id,
+            rule: model.rule,
+            purl: model.purl,
+            justification: model.justification,
+            approver_id: model.approver_id,
+            approver: model.approver,
+            expires_at: model.expires_at,
+            status,
+            reminded_at: model.reminded_at,
+            revoked_at: model.revoked_at,
+            revoked_by: model.revoked_by,
+            created_at: model.created_at,
+        }
+    }
+}
+
+fn reminder_horizon(now: DateTime<Utc>) -> DateTime<Utc> {
+    now + Duration::days(
+        crate::config::get()
+            .policies()
+            .waiver_reminder_days()
+            .into(),
+    )
+}
+
+/// Waivers of a project, soonest expiry first.
+pub async fn list_waivers(
+    State(AppState { db, .. }): State<AppState>,
+    Path(project_id): Path<i32>,
+    Valid(Query(params)): Valid<Query<ListWaiversQuery>>,
+) -> ApiResult<ApiResponse<Page<Waiver>>> {
+    find_project(&db, project_id).await?;
+
+    let now = Utc::now();
+    let mut values: Vec<Value> = vec![project_id.into()];
+    let mut where_clause = "WHERE w.project_id = $1".to_string();
+    match params.status {
+        Some(WaiverStatus::Active) => {
+            values.push(now.into());
+            where_clause.push_str(&format!(
+                " AND w.revoked_at IS NULL AND w.expires_at > ${}",
+                values.len()
+            ));
+        }
+        Some(WaiverStatus::Expiring) => {
+            values.push(now.into());
+            values.push(reminder_horizon(now).into());
+            where_clause.push_str(&format!(
+                " AND w.revoked_at IS NULL AND w.expires_at > ${} AND w.expires_at <= ${}",
+                values.len() - 1,
+                values.len()
+            ));
+        }
+        Some(WaiverStatus::Expired) => {
+            values.push(now.into());
+            where_clause.push_str(&format!(
+                " AND w.revoked_at IS NULL AND w.expires_at <= ${}",
+                values.len()
+            ));
+        }
+        Some(WaiverStatus::Revoked) => where_clause.push_str(" AND w.revoked_at IS NOT NULL"),
+        None => {}
+    }
+
+    let total = CountRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!("SELECT COUNT(*) AS total FROM policy_waivers w {where_clause}"),
+        values.clone(),
+    ))
+    .one(&db)
+    .await?
+    .map(|row| row.total as u64)
+    .unwrap_or(0);
+
+    if total == 0 {
+        return Ok(ApiResponse::ok(
+            "waiver list",
+            Some(Page::from_pagination(params.pagination, 0, Vec::new())),
+        ));
+    }
+
+    let pagination = params.pagination;
+    values.push((pagination.per_page as i64).into());
+    let limit = values.len();
+    values.push(pagination.offset().into());
+    let offset = values.len();
+
+    let items: Vec<Waiver> =
+        policy_waiver::Model::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            format!(
+                "SELECT w.* FROM policy_waivers w {where_clause} \
+             ORDER BY w.revoked_at IS NOT NULL, w.expires_at, w.id \
+             LIMIT ${limit} OFFSET ${offset}"
+            ),
+            values,
+        ))
+        .all(&db)
+        .await?
+        .into_iter()
+        .map(|model| Waiver::from_model(model, now))
+        .collect();
+
+    Ok(ApiResponse::ok(
+        "waiver list",
+        Some(Page::from_pagination(pagination, total, items)),
+    ))
+}
+
+/// Waive the violations of a rule on matching components until `expires_at`. The caller
+/// is recorded as the approver.
+pub async fn create_waiver(
+    State(AppState { db, .. }): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    Path(project_id): Path<i32>,
+    Valid(Json(payload)): Valid<Json<NewWaiver>>,
+) -> ApiResult<ApiResponse<Waiver>> {
+    find_project(&db, project_id).await?;
+
+    let rule = payload.rule.trim();
+    let known = load_policies()?.iter().any(|policy| {
+        policy
+            .rules
+            .iter()
+            .any(|candidate| policy.rule_id(candidate) == rule)
+    });
+    if !known {
+        return Err(ApiError::Biz(format!("unknown policy rule {rule:?}")));
+    }
+
+    let now = Utc::now();
+    let max_days = crate::config::get().policies().max_waiver_days();
+    if payload.expires_at <= now {
+        return Err(ApiError::Biz("expires_at must be in the future".into()));
+    }
+    if payload.expires_at > now + Duration::days(max_days.into()) {
+        return Err(ApiError::Biz(format!(
+            "waivers may not run longer than {max_days} days"
+        )));
+    }
+
+    let waiver = policy_waiver::ActiveModel {
+        project_id: Set(project_id),
+        rule: Set(rule.to_string()),
+        purl: Set(payload.purl.trim().to_string()),
+        justification: Set(payload.justification),
+        approver_id: Set(principal.id),
+        approver: Set(principal.name),
+        expires_at: Set(payload.expires_at),
+        created_at: Set(now),
+        ..Default::default()
+    }
+    .insert(&db)
+    .await?;
+
+    Ok(ApiResponse::ok(
+        "waiver created",
+        Some(Waiver::from_model(waiver, now)),
+    ))
+}
+
+/// Revoke a waiver so its violations count again. Revoking twice keeps the first record.
+pub async fn revoke_waiver(
+    State(AppState { db, .. }): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    Path((project_id, waiver_id)): Path<(i32, i32)>,
+) -> ApiResult<ApiResponse<Waiver>> {
+    let waiver = policy_waiver::Entity::find_by_id(waiver_id)
+        .filter(policy_waiver::Column::ProjectId.eq(project_id))
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let now = Utc::now();
+    let (message, waiver) = if waiver.revoked_at.is_some() {
+        ("waiver already revoked", waiver)
+    } else {
+        let mut active: policy_waiver::ActiveModel = waiver.into();
+        active.revoked_at = Set(Some(now));
+        active.revoked_by = Set(Some(principal.name));
+        ("waiver revoked", active.update(&db).await?)
+    };
+
+    Ok(ApiResponse::ok(
+        message,
+        Some(Waiver::from_model(waiver, now)),
+    ))
+}
@@ -0,0 +1,88 @@
+use std::str::FromStr;
+
+use axum::extract::State;
+use packageurl::PackageUrl;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::detail::find_project;
+use crate::{
+    app::AppState,
+    common::{ApiError, ApiResponse, ApiResult},
+    entity::scan,
+    graph::{self, DependencyPath, MAX_TRAVERSAL_DEPTH},
+    params::{path::Path, query::Query, valid::Valid},
+    purl::base_purl,
+};
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct WhyQuery {
+    /// Package to explain; without a version every version of it matches.
+    #[validate(length(min = 1, max = 2048))]
+    pub purl: String,
+    /// Scan to inspect; defaults to the latest successful scan.
+    pub scan_id: Option<i32>,
+    #[validate(range(min = 1, max = 16))]
+    pub max_depth: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WhyResponse {
+    pub project_id: i32,
+    pub scan_id: i32,
+    pub purl: String,
+    /// True when at least one path is a single hop, i.e. the project declares it directly.
+    pub direct: bool,
+    /// Every shortest path from the project root; the first hop is a direct dependency.
+    pub paths: Vec<DependencyPath>,
+}
+
+/// Explain which direct dependencies pull a package into the project.
+pub async fn why_dependency(
+    State(AppState { db, graph }): State<AppState>,
+    Path(project_id): Path<i32>,
+    Valid(Query(params)): Valid<Query<WhyQuery>>,
+) -> ApiResult<ApiResponse<WhyResponse>> {
+    find_project(&db, project_id).await?;
+
+    let parsed = PackageUrl::from_str(&params.purl)
+        .map_err(|err| ApiError::Biz(format!("invalid purl {}: {err}", params.purl)))?;
+    // Versionless lookups match on the package row, which carries no qualifiers.
+    let purl = match parsed.version() {
+        Some(_) => params.purl.clone(),
+        None => base_purl(&parsed),
+    };
+
+    if let Some(scan_id) = params.scan_id {
+        scan::Entity::find_by_id(scan_id)
+            .filter(scan::Column::ProjectId.eq(project_id))
+            .one(&db)
+            .await?
+            .ok_or(ApiError::NotFound)?;
+    }
+
+    let scan_id = graph::resolve_scan(graph.as_ref(), project_id, params.scan_id)
+        .await?
+        .ok_or_else(|| ApiError::Biz("project has no successful scan yet".into()))?;
+
+    let paths = graph
+        .dependency_paths(
+            project_id,
+            Some(scan_id),
+            &purl,
+            params.max_depth.unwrap_or(MAX_TRAVERSAL_DEPTH),
+        )
+        .await?;
+
+    Ok(ApiResponse::ok(
+        "dependency paths",
+        Some(WhyResponse {
+            project_id,
+            scan_id,
+            purl,
+            direct: paths.iter().any(|path| path.len() == 1),
+            paths,
+        }),
+    ))
+}
@@ -0,0 +1,28 @@
+use axum::{routing::get, Router};
+pub mod dependencies;
+pub mod detail;
+pub mod graph;
+pub mod notice;
+pub mod package_health;
+pub mod sbom;
+pub mod verdict;
+
+use crate::app::AppState;
+use dependencies::list_dependencies;
+use detail::get_scan;
+use graph::export_graph;
+use notice::export_notice;
+use package_health::list_package_health;
+use sbom::export_sbom;
+use verdict::get_verdict;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/{id}", get(get_scan))
+        .route("/{id}/dependencies", get(list_dependencies))
+        .route("/{id}/graph", get(export_graph))
+        .route("/{id}/sbom", get(export_sbom))
+        .route("/{id}/notice", get(export_notice))
+        .route("/{id}/verdict", get(get_verdict))
+        .route("/{id}/package-health", get(list_package_health))
+}
@@ -0,0 +1,155 @@
+use axum::extract::State;
+use sea_orm::{DatabaseBackend, FromQueryResult, JsonValue, Statement, Value};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::detail::find_scan;
+use crate::{
+    api::{packages::search::license_condition, projects::list::escape_like},
+    app::AppState,
+    common::{ApiResponse, ApiResult},
+    params::{
+        pagination::{Page, PaginationParams},
+        path::Path,
+        query::Query,
+        valid::Valid,
+    },
+};
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ListDependenciesQuery {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub pagination: PaginationParams,
+    /// CycloneDX scope (required/optional/excluded).
+    pub scope: Option<String>,
+    /// Purl type such as npm, maven or cargo.
+    pub purl_type: Option<String>,
+    /// Case-insensitive substring of the package name or namespace.
+    #[validate(length(max = 255))]
+    pub name: Option<String>,
+    /// License id, id prefix such as `AGPL`, or `none` for undeclared licenses.
+    #[validate(length(max = 255))]
+    pub license: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct ScanDependency {
+    pub id: i32,
+    pub package_id: i32,
+    pub package_version_id: Option<i32>,
+    /// Versioned purl when the version is known, otherwise the package purl.
+    pub purl: String,
+    pub package_purl: String,
+    pub purl_type: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    pub declared_constraint: Option<String>,
+    pub resolved_version: Option<String>,
+    pub scope: Option<String>,
+    pub manager: Option<String>,
+    pub registry: Option<String>,
+    pub bom_ref: Option<String>,
+    pub is_optional: bool,
+    /// SPDX expression of the declared licenses.
+    pub license_expression: Option<String>,
+    /// License ids of `license_expression`.
+    pub licenses: JsonValue,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CountRow {
+    total: i64,
+}
+
+/// Components recorded by one scan, joined with their package rows.
+pub async fn list_dependencies(
+    State(AppState { db, .. }): State<AppState>,
+    Path(scan_id): Path<i32>,
+    Valid(Query(params)): Valid<Query<ListDependenciesQuery>>,
+) -> ApiResult<ApiResponse<Page<ScanDependency>>> {
+    find_scan(&db, scan_id).await?;
+
+    let mut conditions = vec!["d.scan_id = $1".to_string()];
+    let mut values: Vec<Value> = vec![scan_id.into()];
+
+    if let Some(scope) = params.scope.as_deref().filter(|s| !s.is_empty()) {
+        values.push(scope.to_string().into());
+        conditions.push(format!("d.scope = ${}", values.len()));
+    }
+
+    if let Some(purl_type) = params.purl_type.as_deref().filter(|t| !t.is_empty()) {
+        values.push(purl_type.to_lowercase().into());
+        conditions.push(format!("p.purl_type = ${}", values.len()));
+    }
+
+    if let Some(name) = params.name.as_deref().filter(|n| !n.is_empty()) {
+        values.push(format!("%{}%", escape_like(name)).into());
+        let idx = values.len();
+        conditions.push(format!(
+            "(p.name ILIKE ${idx} ESCAPE '\\' OR p.namespace ILIKE ${idx} ESCAPE '\\')"
+        ));
+    }
+
+    if let Some(license) = params.license.as_deref().filter(|l| !l.is_empty()) {
+        conditions.push(license_condition(
+            "COALESCE(v.licenses, '[]'::jsonb)",
+            license,
+            &mut values,
+        ));
+    }
+
+    let from_clause = format!(
+        "FROM direct_dependencies d \
+         JOIN packages p ON p.id = d.package_id \
+         LEFT JOIN package_versions v ON v.id = d.package_version_id \
+         WHERE {}",
+        conditions.join(" AND ")
+    );
+
+    let total = CountRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!("SELECT COUNT(*) AS total {from_clause}"),
+        values.clone(),
+    ))
+    .one(&db)
+    .await?
+    .map(|row| row.total as u64)
+    .unwrap_or(0);
+
+    if total == 0 {
+        return Ok(ApiResponse::ok(
+            "scan dependencies",
+            Some(Page::from_pagination(params.pagination, 0, Vec::new())),
+        ));
+    }
+
+    let pagination = params.pagination;
+    values.push((pagination.per_page as i64).into());
+    let limit = values.len();
+    values.push(pagination.offset().into());
+    let offset = values.len();
+
+    let items = ScanDependency::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT d.id, d.package_id, d.package_version_id, \
+                    COALESCE(v.purl, p.purl) AS purl, p.purl AS package_purl, \
+                    p.purl_type, p.namespace, p.name, d.declared_constraint, \
+                    d.resolved_version, d.scope, d.manager, d.registry, d.bom_ref, \
+                    d.is_optional, v.license_expression, \
+                    COALESCE(v.licenses, '[]'::jsonb) AS licenses \
+             {from_clause} \
+             ORDER BY p.purl_type, p.namespace NULLS FIRST, p.name, d.id \
+             LIMIT ${limit} OFFSET ${offset}"
+        ),
+        values,
+    ))
+    .all(&db)
+    .await?;
+
+    Ok(ApiResponse::ok(
+        "scan dependencies",
+        Some(Page::from_pagination(pagination, total, items)),
+    ))
+}
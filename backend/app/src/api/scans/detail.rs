@@ -0,0 +1,66 @@
+use axum::extract::State;
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseBackend, DatabaseConnection, EntityTrait, FromQueryResult, Statement};
+use serde::Serialize;
+
+use crate::{
+    api::projects::scans::SCAN_SUMMARY_COLUMNS,
+    app::AppState,
+    common::{ApiError, ApiResponse, ApiResult},
+    entity::scan,
+    params::path::Path,
+};
+
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct ScanDetail {
+    pub id: i32,
+    pub project_id: i32,
+    pub status: Option<String>,
+    pub package_manager: Option<String>,
+    pub branch: Option<String>,
+    pub revision: Option<String>,
+    pub scanner: Option<String>,
+    pub sbom_format: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub duration_ms: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub component_count: i64,
+    pub manifest_path: Option<String>,
+    pub lockfile_path: Option<String>,
+    pub sbom_path: Option<String>,
+    pub source_path: Option<String>,
+    pub sbom_hash: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+pub async fn get_scan(
+    State(AppState { db, .. }): State<AppState>,
+    Path(scan_id): Path<i32>,
+) -> ApiResult<ApiResponse<ScanDetail>> {
+    let scan = find_scan_detail(&db, scan_id).await?;
+
+    Ok(ApiResponse::ok("scan detail", Some(scan)))
+}
+
+async fn find_scan_detail(db: &DatabaseConnection, scan_id: i32) -> ApiResult<ScanDetail> {
+    ScanDetail::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT {SCAN_SUMMARY_COLUMNS}, s.manifest_path, s.lockfile_path, s.sbom_path, \
+                    s.source_path, s.sbom_hash, s.updated_at \
+             FROM scans s WHERE s.id = $1"
+        ),
+        [scan_id.into()],
+    ))
+    .one(db)
+    .await?
+    .ok_or(ApiError::NotFound)
+}
+
+pub(crate) async fn find_scan(db: &DatabaseConnection, scan_id: i32) -> ApiResult<scan::Model> {
+    scan::Entity::find_by_id(scan_id)
+        .one(db)
+        .await?
+        .ok_or(ApiError::NotFound)
+}
@@ -0,0 +1,84 @@
+use std::collections::HashSet;
+
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use validator::Validate;
+
+use super::detail::find_scan;
+use crate::{
+    api::projects::detail::find_project,
+    app::AppState,
+    common::ApiResult,
+    export::graph::{GraphDocument, GraphFormat},
+    graph::MAX_TRAVERSAL_DEPTH,
+    params::{path::Path, query::Query, valid::Valid},
+};
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct GraphQuery {
+    #[serde(default)]
+    pub format: GraphFormat,
+    #[validate(range(min = 1, max = 16))]
+    pub max_depth: Option<u32>,
+    /// Comma-separated scopes to keep (required, optional, excluded); all by default.
+    pub scope: Option<String>,
+    /// Comma-separated purls to highlight; a versionless purl marks every version.
+    #[validate(length(max = 8192))]
+    pub highlight: Option<String>,
+}
+
+/// Render a scan's dependency graph for Graphviz, yEd, Mermaid or as plain JSON.
+pub async fn export_graph(
+    State(AppState { db, graph }): State<AppState>,
+    Path(scan_id): Path<i32>,
+    Valid(Query(params)): Valid<Query<GraphQuery>>,
+) -> ApiResult<Response> {
+    let scan = find_scan(&db, scan_id).await?;
+    let project = find_project(&db, scan.project_id).await?;
+
+    let edges = graph
+        .dependency_tree(
+            scan.project_id,
+            Some(scan.id),
+            params.max_depth.unwrap_or(MAX_TRAVERSAL_DEPTH),
+        )
+        .await?;
+
+    let scopes: Option<HashSet<String>> = params
+        .scope
+        .as_deref()
+        .map(|scope| split_list(scope).map(|s| s.to_ascii_lowercase()).collect());
+    let highlight: Vec<String> = params
+        .highlight
+        .as_deref()
+        .map(|list| split_list(list).map(String::from).collect())
+        .unwrap_or_default();
+
+    let document = GraphDocument::build(&project.name, &edges, scopes.as_ref(), &highlight);
+    let filename = format!("scan-{}.{}", scan.id, params.format.extension());
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                params.format.content_type().to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("inline; filename=\"{filename}\""),
+            ),
+        ],
+        document.render(params.format),
+    )
+        .into_response())
+}
+
+fn split_list(list: &str) -> impl Iterator<Item = &str> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+}
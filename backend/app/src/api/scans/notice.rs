@@ -0,0 +1,61 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+
+use super::detail::find_scan;
+use crate::{
+    api::projects::detail::find_project,
+    app::AppState,
+    common::ApiResult,
+    export::{
+        notice::{render, NoticeFormat},
+        sbom::spdx_slug,
+    },
+    notice::build_notice,
+    params::{path::Path, query::Query},
+};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NoticeQuery {
+    #[serde(default)]
+    pub format: NoticeFormat,
+    /// Also list `optional` components, which are usually development dependencies.
+    #[serde(default)]
+    pub include_optional: bool,
+}
+
+/// Third-party attribution notice for every shipped component of a scan.
+pub async fn export_notice(
+    State(AppState { db, .. }): State<AppState>,
+    Path(scan_id): Path<i32>,
+    Query(params): Query<NoticeQuery>,
+) -> ApiResult<Response> {
+    let scan = find_scan(&db, scan_id).await?;
+    let project = find_project(&db, scan.project_id).await?;
+
+    let notice = build_notice(&db, &project, &scan, params.include_optional).await?;
+    let filename = format!(
+        "{}-scan-{}-NOTICE.{}",
+        spdx_slug(&project.name),
+        scan.id,
+        params.format.extension()
+    );
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                params.format.content_type().to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        render(&notice, params.format),
+    )
+        .into_response())
+}
@@ -0,0 +1,103 @@
+use axum::extract::State;
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseBackend, FromQueryResult, Statement, Value};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::detail::find_scan;
+use crate::{
+    app::AppState,
+    common::{ApiResponse, ApiResult},
+    params::{
+        pagination::{Page, PaginationParams},
+        path::Path,
+        query::Query,
+        valid::Valid,
+    },
+    registry::health::HealthKind,
+};
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct PackageHealthQuery {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub pagination: PaginationParams,
+    pub kind: Option<HealthKind>,
+}
+
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct PackageHealthFinding {
+    pub id: i32,
+    pub package_id: i32,
+    pub purl: String,
+    pub version: Option<String>,
+    /// `yanked`, `deprecated` or `abandoned`.
+    pub kind: String,
+    pub detail: String,
+    pub last_release_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CountRow {
+    total: i64,
+}
+
+/// Components of a scan that are yanked, deprecated or abandoned upstream.
+pub async fn list_package_health(
+    State(AppState { db, .. }): State<AppState>,
+    Path(scan_id): Path<i32>,
+    Valid(Query(params)): Valid<Query<PackageHealthQuery>>,
+) -> ApiResult<ApiResponse<Page<PackageHealthFinding>>> {
+    find_scan(&db, scan_id).await?;
+
+    let mut values: Vec<Value> = vec![scan_id.into()];
+    let mut where_clause = "WHERE h.scan_id = $1".to_string();
+    if let Some(kind) = params.kind {
+        values.push(kind.as_str().into());
+        where_clause.push_str(&format!(" AND h.kind = ${}", values.len()));
+    }
+
+    let total = CountRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!("SELECT COUNT(*) AS total FROM package_health_findings h {where_clause}"),
+        values.clone(),
+    ))
+    .one(&db)
+    .await?
+    .map(|row| row.total as u64)
+    .unwrap_or(0);
+
+    if total == 0 {
+        return Ok(ApiResponse::ok(
+            "package health findings",
+            Some(Page::from_pagination(params.pagination, 0, Vec::new())),
+        ));
+    }
+
+    let pagination = params.pagination;
+    values.push((pagination.per_page as i64).into());
+    let limit = values.len();
+    values.push(pagination.offset().into());
+    let offset = values.len();
+
+    let items = PackageHealthFinding::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT h.id, h.package_id, h.purl, h.version, h.kind, h.detail, \
+                    h.last_release_at, h.created_at \
+             FROM package_health_findings h {where_clause} \
+             ORDER BY CASE h.kind WHEN 'yanked' THEN 0 WHEN 'deprecated' THEN 1 ELSE 2 END, \
+                      h.purl \
+             LIMIT ${limit} OFFSET ${offset}"
+        ),
+        values,
+    ))
+    .all(&db)
+    .await?;
+
+    Ok(ApiResponse::ok(
+        "package health findings",
+        Some(Page::from_pagination(pagination, total, items)),
+    ))
+}
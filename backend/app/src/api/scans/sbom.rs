@@ -0,0 +1,182 @@
+use std::io::{self, BufWriter, Write};
+
+use axum::{
+    body::{Body, Bytes},
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use sea_orm::{DatabaseBackend, DatabaseConnection, DbErr, FromQueryResult, Statement};
+use serde::Deserialize;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::debug;
+
+use super::detail::find_scan;
+use crate::{
+    api::projects::detail::find_project,
+    app::AppState,
+    common::ApiResult,
+    export::sbom::{spdx_slug, SbomComponent, SbomDocument, SbomFormat, SbomSubject},
+    graph::MAX_TRAVERSAL_DEPTH,
+    params::{path::Path, query::Query},
+};
+
+/// Bytes rendered before a chunk is sent to the client.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Chunks buffered ahead of a slow client before rendering waits.
+const CHUNKS_IN_FLIGHT: usize = 4;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SbomQuery {
+    #[serde(default)]
+    pub format: SbomFormat,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ComponentRow {
+    purl: String,
+    namespace: Option<String>,
+    name: String,
+    version: Option<String>,
+    scope: Option<String>,
+    license_expression: Option<String>,
+}
+
+/// Sends each chunk written to it down the response body.
+struct ChunkSender(mpsc::Sender<io::Result<Bytes>>);
+
+impl Write for ChunkSender {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .blocking_send(Ok(Bytes::copy_from_slice(buf)))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "client went away"))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Regenerate a scan's SBOM from the database rather than serving the stored artifact.
+/// The document is streamed in chunks while it is rendered.
+pub async fn export_sbom(
+    State(AppState { db, graph }): State<AppState>,
+    Path(scan_id): Path<i32>,
+    Query(params): Query<SbomQuery>,
+) -> ApiResult<Response> {
+    let scan = find_scan(&db, scan_id).await?;
+    let project = find_project(&db, scan.project_id).await?;
+
+    let mut properties = vec![("check-deps:scan_id".to_string(), scan.id.to_string())];
+    for (name, value) in [
+        ("check-deps:branch", &scan.branch),
+        ("check-deps:package_manager", &scan.package_manager),
+        ("check-deps:scanner", &scan.scanner),
+        ("check-deps:team", &project.team),
+    ] {
+        if let Some(value) = value {
+            properties.push((name.to_string(), value.clone()));
+        }
+    }
+
+    let subject = SbomSubject {
+        bom_ref: project
+            .purl
+            .clone()
+            .unwrap_or_else(|| format!("project:{}", project.name)),
+        name: project.name.clone(),
+        version: scan.revision.clone(),
+        description: project.description.clone(),
+        purl: project.purl.clone(),
+        repo_url: project.repo_url.clone(),
+        homepage: project.homepage.clone(),
+        properties,
+    };
+
+    let components = scan_components(&db, scan.id).await?;
+    let mut document = SbomDocument::new(subject, components);
+    if let Some(completed_at) = scan.completed_at {
+        document.timestamp = completed_at;
+    }
+
+    let edges = graph
+        .dependency_tree(scan.project_id, Some(scan.id), MAX_TRAVERSAL_DEPTH)
+        .await?;
+    document.add_dependencies(
+        edges
+            .iter()
+            .map(|edge| (edge.parent.as_deref(), edge.purl.as_str())),
+    );
+
+    let filename = format!(
+        "{}-scan-{}.{}",
+        spdx_slug(&project.name),
+        scan.id,
+        params.format.extension()
+    );
+
+    let (tx, rx) = mpsc::channel(CHUNKS_IN_FLIGHT);
+    let format = params.format;
+    tokio::task::spawn_blocking(move || {
+        let mut out = BufWriter::with_capacity(CHUNK_SIZE, ChunkSender(tx.clone()));
+        if let Err(err) = document.write(format, &mut out).and_then(|()| out.flush()) {
+            debug!(error = ?err, scan_id, "stopped streaming SBOM");
+            // Abort the body so the client does not take a truncated document as complete.
+            let _ = tx.blocking_send(Err(err));
+        }
+    });
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                params.format.content_type().to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        Body::from_stream(ReceiverStream::new(rx)),
+    )
+        .into_response())
+}
+
+/// One component per resolved purl recorded for the scan.
+async fn scan_components(
+    db: &DatabaseConnection,
+    scan_id: i32,
+) -> Result<Vec<SbomComponent>, DbErr> {
+    let rows = ComponentRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT DISTINCT ON (COALESCE(v.purl, p.purl)) \
+                COALESCE(v.purl, p.purl) AS purl, p.namespace, p.name, \
+                COALESCE(v.version, d.resolved_version) AS version, \
+                CASE WHEN d.is_optional AND d.scope IS NULL THEN 'optional' ELSE d.scope END AS scope, \
+                v.license_expression \
+         FROM direct_dependencies d \
+         JOIN packages p ON p.id = d.package_id \
+         LEFT JOIN package_versions v ON v.id = d.package_version_id \
+         WHERE d.scan_id = $1 \
+         ORDER BY COALESCE(v.purl, p.purl), d.id",
+        [scan_id.into()],
+    ))
+    .all(db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| SbomComponent {
+            purl: row.purl,
+            namespace: row.namespace,
+            name: row.name,
+            version: row.version,
+            scope: row.scope,
+            license: row.license_expression,
+            properties: Vec::new(),
+        })
+        .collect())
+}
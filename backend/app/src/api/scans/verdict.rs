@@ -0,0 +1,20 @@
+use axum::extract::State;
+
+use super::detail::find_scan;
+use crate::{
+    app::AppState,
+    common::{ApiResponse, ApiResult},
+    params::path::Path,
+    policy::{self, PolicyEvaluation},
+};
+
+/// Evaluate a scan against the configured policies. CI pipelines block merges on a
+/// `fail` verdict.
+pub async fn get_verdict(
+    State(AppState { db, graph, .. }): State<AppState>,
+    Path(scan_id): Path<i32>,
+) -> ApiResult<ApiResponse<PolicyEvaluation>> {
+    let scan = find_scan(&db, scan_id).await?;
+    let evaluation = policy::evaluate_scan(&db, &graph, &scan).await?;
+    Ok(ApiResponse::ok("policy verdict", Some(evaluation)))
+}
@@ -0,0 +1,21 @@
+use axum::{
+    handler::Handler,
+    routing::{get, post},
+    Router,
+};
+pub mod alerts;
+pub mod list;
+
+use crate::{app::AppState, auth::get_jwt_auth_layer};
+use alerts::{acknowledge_alert, list_alerts};
+use list::list_findings;
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_findings))
+        .route("/alerts", get(list_alerts))
+        .route(
+            "/alerts/{id}/acknowledge",
+            post(acknowledge_alert.layer(get_jwt_auth_layer())),
+        )
+}
@@ -0,0 +1,166 @@
+use axum::{extract::State, Extension};
+use chrono::{DateTime, NaiveDate, Utc};
+use sea_orm::{
+    ActiveModelTrait, DatabaseBackend, EntityTrait, FromQueryResult, Set, Statement, Value,
+};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    app::AppState,
+    auth::Principal,
+    common::{ApiError, ApiResponse, ApiResult},
+    entity::kev_alert,
+    params::{
+        pagination::{Page, PaginationParams},
+        path::Path,
+        query::Query,
+        valid::Valid,
+    },
+};
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ListAlertsQuery {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub pagination: PaginationParams,
+    /// Only acknowledged (`true`) or open (`false`) alerts.
+    pub acknowledged: Option<bool>,
+    pub project_id: Option<i32>,
+    /// Only projects owned by this team.
+    pub team: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromQueryResult)]
+pub struct KevAlert {
+    pub id: i32,
+    pub project_id: i32,
+    pub project_name: String,
+    pub scan_id: i32,
+    pub vulnerability_id: i32,
+    pub osv_id: String,
+    /// CVE id listed in the KEV catalog.
+    pub cve: String,
+    pub purl: String,
+    /// Remediation deadline set by CISA.
+    pub due_date: Option<NaiveDate>,
+    pub known_ransomware: bool,
+    pub created_at: DateTime<Utc>,
+    pub notified_at: Option<DateTime<Utc>>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    pub acknowledged_by: Option<String>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CountRow {
+    total: i64,
+}
+
+const ALERT_COLUMNS: &str = "k.id, k.project_id, pr.name AS project_name, k.scan_id, \
+     k.vulnerability_id, vu.osv_id, k.cve, k.purl, k.due_date, k.known_ransomware, \
+     k.created_at, k.notified_at, k.acknowledged_at, k.acknowledged_by";
+
+const ALERT_JOINS: &str = "JOIN projects pr ON pr.id = k.project_id \
+     JOIN vulnerabilities vu ON vu.id = k.vulnerability_id";
+
+/// Alerts on known exploited vulnerabilities, open ones first and newest first.
+pub async fn list_alerts(
+    State(AppState { db, .. }): State<AppState>,
+    Valid(Query(params)): Valid<Query<ListAlertsQuery>>,
+) -> ApiResult<ApiResponse<Page<KevAlert>>> {
+    let mut values: Vec<Value> = Vec::new();
+    let mut conditions: Vec<String> = Vec::new();
+
+    match params.acknowledged {
+        Some(true) => conditions.push("k.acknowledged_at IS NOT NULL".to_string()),
+        Some(false) => conditions.push("k.acknowledged_at IS NULL".to_string()),
+        None => {}
+    }
+    if let Some(project_id) = params.project_id {
+        values.push(project_id.into());
+        conditions.push(format!("k.project_id = ${}", values.len()));
+    }
+    if let Some(team) = params.team.as_deref().filter(|s| !s.is_empty()) {
+        values.push(team.into());
+        conditions.push(format!("pr.team = ${}", values.len()));
+    }
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+
+    let total = CountRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!("SELECT COUNT(*) AS total FROM kev_alerts k {ALERT_JOINS} {where_clause}"),
+        values.clone(),
+    ))
+    .one(&db)
+    .await?
+    .map(|row| row.total as u64)
+    .unwrap_or(0);
+
+    if total == 0 {
+        return Ok(ApiResponse::ok(
+            "alert list",
+            Some(Page::from_pagination(params.pagination, 0, Vec::new())),
+        ));
+    }
+
+    let pagination = params.pagination;
+    values.push((pagination.per_page as i64).into());
+    let limit = values.len();
+    values.push(pagination.offset().into());
+    let offset = values.len();
+
+    let items = KevAlert::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT {ALERT_COLUMNS} FROM kev_alerts k {ALERT_JOINS} {where_clause} \
+             ORDER BY k.acknowledged_at IS NOT NULL, k.created_at DESC, k.id DESC \
+             LIMIT ${limit} OFFSET ${offset}"
+        ),
+        values,
+    ))
+    .all(&db)
+    .await?;
+
+    Ok(ApiResponse::ok(
+        "alert list",
+        Some(Page::from_pagination(pagination, total, items)),
+    ))
+}
+
+/// Mark an alert as seen by the caller. Acknowledging twice keeps the first record.
+pub async fn acknowledge_alert(
+    State(AppState { db, .. }): State<AppState>,
+    Extension(principal): Extension<Principal>,
+    Path(alert_id): Path<i32>,
+) -> ApiResult<ApiResponse<KevAlert>> {
+    let alert = kev_alert::Entity::find_by_id(alert_id)
+        .one(&db)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+    let message = if alert.acknowledged_at.is_some() {
+        "alert already acknowledged"
+    } else {
+        let mut active: kev_alert::ActiveModel = alert.into();
+        active.acknowledged_at = Set(Some(Utc::now()));
+        active.acknowledged_by = Set(Some(principal.name));
+        active.update(&db).await?;
+        "alert acknowledged"
+    };
+
+    let alert = KevAlert::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!("SELECT {ALERT_COLUMNS} FROM kev_alerts k {ALERT_JOINS} WHERE k.id = $1"),
+        [alert_id.into()],
+    ))
+    .one(&db)
+    .await?
+    .ok_or(ApiError::NotFound)?;
+
+    Ok(ApiResponse::ok(message, Some(alert)))
+}
@@ -0,0 +1,122 @@
+use axum::extract::State;
+use sea_orm::{DatabaseBackend, FromQueryResult, Statement, Value};
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    api::projects::vulnerabilities::{
+        FindingSort, VulnerabilityFinding, FINDING_COLUMNS, FINDING_JOINS,
+    },
+    app::AppState,
+    common::{ApiResponse, ApiResult},
+    params::{
+        pagination::{Page, PaginationParams},
+        query::Query,
+        valid::Valid,
+    },
+    vulnerability::risk::EXPLOIT_JOIN,
+};
+
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct ListFindingsQuery {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub pagination: PaginationParams,
+    /// Only projects owned by this team.
+    pub team: Option<String>,
+    /// Only findings of this severity (critical/high/medium/low).
+    pub severity: Option<String>,
+    /// Only findings with this analysis state, or `none` for findings not yet triaged.
+    pub analysis: Option<String>,
+    /// Only findings whose advisory is (`true`) or is not (`false`) in the KEV catalog.
+    pub kev: Option<bool>,
+    #[serde(default)]
+    pub sort: FindingSort,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CountRow {
+    total: i64,
+}
+
+/// Findings of the latest successful scan of every project, most severe first unless
+/// another order is requested.
+pub async fn list_findings(
+    State(AppState { db, .. }): State<AppState>,
+    Valid(Query(params)): Valid<Query<ListFindingsQuery>>,
+) -> ApiResult<ApiResponse<Page<VulnerabilityFinding>>> {
+    let mut values: Vec<Value> = Vec::new();
+    let mut where_clause = "WHERE f.scan_id IN (SELECT MAX(id) FROM scans \
+         WHERE status = 'success' GROUP BY project_id)"
+        .to_string();
+
+    if let Some(team) = params.team.as_deref().filter(|s| !s.is_empty()) {
+        values.push(team.into());
+        where_clause.push_str(&format!(" AND pr.team = ${}", values.len()));
+    }
+
+    if let Some(severity) = params.severity.as_deref().filter(|s| !s.is_empty()) {
+        values.push(severity.to_ascii_lowercase().into());
+        where_clause.push_str(&format!(" AND vu.severity = ${}", values.len()));
+    }
+
+    match params.analysis.as_deref().filter(|s| !s.is_empty()) {
+        None => {}
+        Some("none") => where_clause.push_str(" AND a.id IS NULL"),
+        Some(state) => {
+            values.push(state.to_ascii_lowercase().into());
+            where_clause.push_str(&format!(" AND a.state = ${}", values.len()));
+        }
+    }
+
+    match params.kev {
+        Some(true) => where_clause.push_str(" AND ex.kev_date_added IS NOT NULL"),
+        Some(false) => where_clause.push_str(" AND ex.kev_date_added IS NULL"),
+        None => {}
+    }
+
+    let total = CountRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT COUNT(*) AS total FROM vulnerability_findings f \
+             {FINDING_JOINS} {EXPLOIT_JOIN} {where_clause}"
+        ),
+        values.clone(),
+    ))
+    .one(&db)
+    .await?
+    .map(|row| row.total as u64)
+    .unwrap_or(0);
+
+    if total == 0 {
+        return Ok(ApiResponse::ok(
+            "vulnerability list",
+            Some(Page::from_pagination(params.pagination, 0, Vec::new())),
+        ));
+    }
+
+    let pagination = params.pagination;
+    values.push((pagination.per_page as i64).into());
+    let limit = values.len();
+    values.push(pagination.offset().into());
+    let offset = values.len();
+
+    let order_clause = params.sort.order_clause();
+    let items = VulnerabilityFinding::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT {FINDING_COLUMNS} FROM vulnerability_findings f \
+             {FINDING_JOINS} {EXPLOIT_JOIN} {where_clause} \
+             ORDER BY {order_clause}, pr.name \
+             LIMIT ${limit} OFFSET ${offset}"
+        ),
+        values,
+    ))
+    .all(&db)
+    .await?;
+
+    Ok(ApiResponse::ok(
+        "vulnerability list",
+        Some(Page::from_pagination(pagination, total, items)),
+    ))
+}
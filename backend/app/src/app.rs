@@ -1,23 +1,24 @@
-use neo4rs::Graph;
 use sea_orm::DatabaseConnection;
 
-use crate::{api, database, id, logger, neo4j, server::Server};
+use clap::Parser;
+
+use crate::{api, cli::{self, Cli, Command}, database, graph::{self, SharedGraphStore}, id, logger, server::Server};
 use migration::{Migrator, MigratorTrait};
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: DatabaseConnection,
-    pub neo4j: Option<Graph>,
+    pub graph: SharedGraphStore,
 }
 
 impl AppState {
-    pub fn new(db: DatabaseConnection, neo4j: Option<Graph>) -> Self {
-        Self { db, neo4j }
+    pub fn new(db: DatabaseConnection, graph: SharedGraphStore) -> Self {
+        Self { db, graph }
     }
 }
 
 pub async fn run() -> anyhow::Result<()> {
-    let router = api::create_router();
+    let cli = Cli::parse();
 
     logger::init();
 
@@ -31,13 +32,28 @@ pub async fn run() -> anyhow::Result<()> {
 
     Migrator::up(&db, None).await?;
 
-    let neo4j = neo4j::init().await?;
-
-    let state = AppState::new(db, neo4j);
-
-    let server = Server::new(crate::config::get().server());
-
-    server.start(state, router).await?;
+    let graph = graph::init(db.clone()).await?;
+    tracing::info!("Graph store backend: {}", graph.backend());
+
+    let state = AppState::new(db, graph);
+
+    match cli.command.unwrap_or(Command::Serve) {
+        Command::Serve => {
+            let router = api::create_router();
+            let server = Server::new(crate::config::get().server());
+
+            server.start(state, router).await?;
+        }
+        Command::Inventory(args) => cli::inventory(&state, args).await?,
+        Command::ImportOsv(args) => cli::import_osv(&state, args).await?,
+        Command::ImportNvd(args) => cli::import_nvd(&state, args).await?,
+        Command::ImportEpss(args) => cli::import_epss(&state, args).await?,
+        Command::ImportKev(args) => cli::import_kev(&state, args).await?,
+        Command::EvaluateLicenses => cli::evaluate_licenses(&state).await?,
+        Command::EvaluatePolicies => cli::evaluate_policies(&state).await?,
+        Command::RemindWaivers => cli::remind_waivers(&state).await?,
+        Command::ImportRegistries(args) => cli::import_registries(&state, args).await?,
+    }
 
     Ok(())
 }
@@ -12,6 +12,7 @@ use crate::config::auth::AuthConfig;
 
 use super::service::{JwtService, jwt_service};
 
+#[derive(Clone)]
 pub struct JwtAuthLayer {
     jwt: &'static JwtService,
     auth_config: &'static AuthConfig,
@@ -0,0 +1,280 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::{Args, Parser, Subcommand};
+
+use crate::{
+    api::inventory::export::inventory_filter,
+    app::AppState,
+    export::inventory::{render, InventoryFormat},
+    inventory::build_inventory,
+    license, policy,
+    registry::{self, RegistrySources},
+    vulnerability,
+};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "check-deps",
+    version,
+    about = "Dependency inventory and scanning service"
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the HTTP API (the default).
+    Serve,
+    /// Write the merged component inventory of the latest scan of every project.
+    Inventory(InventoryArgs),
+    /// Import OSV dumps and re-match the latest scan of every project.
+    ImportOsv(ImportOsvArgs),
+    /// Import NVD CVE feeds and re-match the latest scan of every project.
+    ImportNvd(ImportNvdArgs),
+    /// Import an EPSS snapshot.
+    ImportEpss(ImportEpssArgs),
+    /// Import the CISA KEV catalog and alert on known exploited findings.
+    ImportKev(ImportKevArgs),
+    /// Re-evaluate the latest scan of every project against the license policy.
+    EvaluateLicenses,
+    /// Re-evaluate the latest scan of every project against the configured policies.
+    EvaluatePolicies,
+    /// Send reminders for policy waivers that are about to expire.
+    RemindWaivers,
+    /// Import release histories from local registry metadata.
+    ImportRegistries(ImportRegistriesArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct InventoryArgs {
+    /// cyclonedx-json, cyclonedx-xml, spdx-json, csv or xlsx.
+    #[arg(long, default_value = "cyclonedx-json", value_parser = parse_inventory_format)]
+    pub format: InventoryFormat,
+    /// Only projects owned by this team.
+    #[arg(long)]
+    pub team: Option<String>,
+    /// Comma-separated project ids or names.
+    #[arg(long)]
+    pub projects: Option<String>,
+    /// File to write.
+    #[arg(long, short)]
+    pub output: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct ImportOsvArgs {
+    /// Directory with the OSV dumps; defaults to `vulnerabilities.osv_dir`.
+    #[arg(long)]
+    pub dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct ImportNvdArgs {
+    /// Directory with the NVD feeds; defaults to `vulnerabilities.nvd_dir`.
+    #[arg(long)]
+    pub dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct ImportEpssArgs {
+    /// EPSS CSV (optionally gzipped); defaults to `vulnerabilities.epss_file`.
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct ImportKevArgs {
+    /// KEV catalog JSON; defaults to `vulnerabilities.kev_file`.
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct ImportRegistriesArgs {
+    /// Clone of the crates.io index; defaults to `registries.cargo_index`.
+    #[arg(long)]
+    pub cargo_index: Option<PathBuf>,
+    /// Directory of npm packuments; defaults to `registries.npm_dir`.
+    #[arg(long)]
+    pub npm_dir: Option<PathBuf>,
+    /// Directory of PyPI JSON metadata; defaults to `registries.pypi_dir`.
+    #[arg(long)]
+    pub pypi_dir: Option<PathBuf>,
+    /// Directory of `maven-metadata.xml` files; defaults to `registries.maven_dir`.
+    #[arg(long)]
+    pub maven_dir: Option<PathBuf>,
+}
+
+fn parse_inventory_format(name: &str) -> Result<InventoryFormat, String> {
+    InventoryFormat::from_name(name).ok_or_else(|| format!("unsupported format {name:?}"))
+}
+
+pub async fn inventory(state: &AppState, args: InventoryArgs) -> anyhow::Result<()> {
+    let filter = inventory_filter(args.team, args.projects.as_deref());
+    let name = match &filter.team {
+        Some(team) => format!("{team} inventory"),
+        None => "Organization inventory".to_string(),
+    };
+
+    let inventory = build_inventory(&state.db, &filter).await?;
+    let body = render(&inventory, &name, args.format)?;
+    tokio::fs::write(&args.output, body)
+        .await
+        .with_context(|| format!("failed to write {}", args.output.display()))?;
+
+    tracing::info!(
+        "Wrote {} components from {} projects to {}",
+        inventory.components.len(),
+        inventory.projects.len(),
+        args.output.display()
+    );
+    Ok(())
+}
+
+pub async fn import_osv(state: &AppState, args: ImportOsvArgs) -> anyhow::Result<()> {
+    let dir = args
+        .dir
+        .or_else(|| {
+            crate::config::get()
+                .vulnerabilities()
+                .osv_dir()
+                .map(PathBuf::from)
+        })
+        .context("no OSV directory given and vulnerabilities.osv_dir is not set")?;
+
+    let summary = vulnerability::import_dir(&state.db, &dir).await?;
+    tracing::info!(
+        "Imported {} of {} OSV records from {} files ({} unchanged, {} without a known ecosystem)",
+        summary.imported,
+        summary.records,
+        summary.files,
+        summary.unchanged,
+        summary.skipped
+    );
+
+    let findings = vulnerability::rematch_latest_scans(&state.db).await?;
+    tracing::info!("Re-matched latest scans: {findings} findings");
+    Ok(())
+}
+
+pub async fn import_nvd(state: &AppState, args: ImportNvdArgs) -> anyhow::Result<()> {
+    let dir = args
+        .dir
+        .or_else(|| {
+            crate::config::get()
+                .vulnerabilities()
+                .nvd_dir()
+                .map(PathBuf::from)
+        })
+        .context("no NVD directory given and vulnerabilities.nvd_dir is not set")?;
+
+    let summary = vulnerability::import_feeds(&state.db, &dir).await?;
+    tracing::info!(
+        "Imported {} of {} CVEs from {} NVD feeds ({} unchanged, {} without vulnerable CPEs)",
+        summary.imported,
+        summary.records,
+        summary.files,
+        summary.unchanged,
+        summary.skipped
+    );
+
+    let findings = vulnerability::rematch_latest_scans(&state.db).await?;
+    tracing::info!("Re-matched latest scans: {findings} findings");
+    Ok(())
+}
+
+pub async fn import_epss(state: &AppState, args: ImportEpssArgs) -> anyhow::Result<()> {
+    let file = args
+        .file
+        .or_else(|| {
+            crate::config::get()
+                .vulnerabilities()
+                .epss_file()
+                .map(PathBuf::from)
+        })
+        .context("no EPSS file given and vulnerabilities.epss_file is not set")?;
+
+    let summary = vulnerability::epss::import_file(&state.db, &file).await?;
+    tracing::info!(
+        "Imported {} EPSS scores (model {}, scored {})",
+        summary.scores,
+        summary.model_version.as_deref().unwrap_or("unknown"),
+        summary
+            .score_date
+            .map_or_else(|| "unknown".to_string(), |date| date.to_string())
+    );
+    Ok(())
+}
+
+pub async fn import_kev(state: &AppState, args: ImportKevArgs) -> anyhow::Result<()> {
+    let file = args
+        .file
+        .or_else(|| {
+            crate::config::get()
+                .vulnerabilities()
+                .kev_file()
+                .map(PathBuf::from)
+        })
+        .context("no KEV file given and vulnerabilities.kev_file is not set")?;
+
+    let summary = vulnerability::kev::import_file(&state.db, &file).await?;
+    tracing::info!(
+        "Imported {} KEV entries (catalog {})",
+        summary.entries,
+        summary.catalog_version.as_deref().unwrap_or("unknown")
+    );
+
+    let alerts = vulnerability::raise_for_latest_scans(&state.db).await?;
+    tracing::info!("Raised {alerts} KEV alerts on latest scans");
+    Ok(())
+}
+
+pub async fn evaluate_licenses(state: &AppState) -> anyhow::Result<()> {
+    let violations = license::policy::evaluate_latest_scans(&state.db, &state.graph).await?;
+    tracing::info!("Evaluated latest scans against the license policy: {violations} violations");
+    Ok(())
+}
+
+pub async fn evaluate_policies(state: &AppState) -> anyhow::Result<()> {
+    let failed = policy::evaluate_latest_scans(&state.db, &state.graph).await?;
+    tracing::info!("Evaluated latest scans against the configured policies: {failed} failed");
+    Ok(())
+}
+
+pub async fn remind_waivers(state: &AppState) -> anyhow::Result<()> {
+    let reminded = policy::waiver::remind_expiring(&state.db).await?;
+    tracing::info!("Sent {reminded} policy waiver reminders");
+    Ok(())
+}
+
+pub async fn import_registries(state: &AppState, args: ImportRegistriesArgs) -> anyhow::Result<()> {
+    let config = crate::config::get().registries();
+    let sources = RegistrySources {
+        cargo_index: args
+            .cargo_index
+            .or_else(|| config.cargo_index().map(PathBuf::from)),
+        npm_dir: args.npm_dir.or_else(|| config.npm_dir().map(PathBuf::from)),
+        pypi_dir: args.pypi_dir.or_else(|| config.pypi_dir().map(PathBuf::from)),
+        maven_dir: args
+            .maven_dir
+            .or_else(|| config.maven_dir().map(PathBuf::from)),
+    };
+
+    let summary = registry::import(&state.db, &sources).await?;
+    tracing::info!(
+        "Imported {} releases of {} packages from {} metadata files ({} packages not in any scan)",
+        summary.releases,
+        summary.packages,
+        summary.files,
+        summary.unknown
+    );
+
+    let findings = registry::health::check_latest_scans(&state.db).await?;
+    tracing::info!(
+        "Checked latest scans for yanked, deprecated and abandoned packages: {findings} findings"
+    );
+    Ok(())
+}
@@ -1,11 +1,16 @@
 pub mod auth;
 pub mod database;
+pub mod graph;
 pub mod jwt;
 pub mod logger;
 pub mod server;
 pub mod s3;
 pub mod neo4j;
 pub mod languages;
+pub mod license_policy;
+pub mod policies;
+pub mod registries;
+pub mod vulnerabilities;
 
 pub(crate) use std::sync::LazyLock;
 
@@ -14,11 +19,16 @@ use config::Config;
 use serde::Deserialize;
 
 pub use database::DatabaseConfig;
+pub use graph::GraphConfig;
 pub use jwt::JwtConfig;
 pub use s3::S3Config;
 pub use server::ServerConfig;
 pub use neo4j::Neo4jConfig;
 pub use languages::LanguagesConfig;
+pub use license_policy::LicensePolicyConfig;
+pub use policies::PoliciesConfig;
+pub use registries::RegistriesConfig;
+pub use vulnerabilities::VulnerabilitiesConfig;
 
 use crate::config::{auth::AuthConfig, logger::LoggerConfig};
 
@@ -37,9 +47,19 @@ pub struct AppConfig {
     s3: S3Config,
     neo4j: Neo4jConfig,
     #[serde(default)]
+    graph: GraphConfig,
+    #[serde(default)]
     languages: LanguagesConfig,
     #[serde(default)]
     logger: LoggerConfig,
+    #[serde(default)]
+    vulnerabilities: VulnerabilitiesConfig,
+    #[serde(default)]
+    license_policy: LicensePolicyConfig,
+    #[serde(default)]
+    policies: PoliciesConfig,
+    #[serde(default)]
+    registries: RegistriesConfig,
 }
 
 impl AppConfig {
@@ -87,6 +107,10 @@ impl AppConfig {
         &self.neo4j
     }
 
+    pub fn graph(&self) -> &GraphConfig {
+        &self.graph
+    }
+
     pub fn languages(&self) -> &LanguagesConfig {
         &self.languages
     }
@@ -94,6 +118,22 @@ impl AppConfig {
     pub fn logger(&self) -> &LoggerConfig {
         &self.logger
     }
+
+    pub fn vulnerabilities(&self) -> &VulnerabilitiesConfig {
+        &self.vulnerabilities
+    }
+
+    pub fn license_policy(&self) -> &LicensePolicyConfig {
+        &self.license_policy
+    }
+
+    pub fn policies(&self) -> &PoliciesConfig {
+        &self.policies
+    }
+
+    pub fn registries(&self) -> &RegistriesConfig {
+        &self.registries
+    }
 }
 
 pub fn get() -> &'static AppConfig {
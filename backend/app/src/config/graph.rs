@@ -0,0 +1,30 @@
+use serde::Deserialize;
+
+/// What happens to the edges of scans that fall out of the retention window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionMode {
+    /// Move the edges out of the live graph but keep them and the scan snapshot.
+    Archive,
+    /// Drop the edges and the scan snapshot entirely.
+    Prune,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct GraphConfig {
+    keep_superseded_scans: Option<u32>,
+    retention: Option<String>,
+}
+
+impl GraphConfig {
+    /// Superseded scans per project whose edges stay queryable next to the latest one.
+    pub fn keep_superseded_scans(&self) -> u32 {
+        self.keep_superseded_scans.unwrap_or(1)
+    }
+
+    pub fn retention(&self) -> RetentionMode {
+        match self.retention.as_deref().unwrap_or("archive") {
+            "prune" => RetentionMode::Prune,
+            _ => RetentionMode::Archive,
+        }
+    }
+}
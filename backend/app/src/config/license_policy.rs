@@ -0,0 +1,77 @@
+use serde::Deserialize;
+
+/// How the license policy treats a license.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseOutcome {
+    Allowed,
+    #[default]
+    Review,
+    Denied,
+}
+
+impl LicenseOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LicenseOutcome::Allowed => "allowed",
+            LicenseOutcome::Review => "review",
+            LicenseOutcome::Denied => "denied",
+        }
+    }
+}
+
+/// Licenses accepted for one package regardless of the lists.
+#[derive(Debug, Deserialize, Clone)]
+pub struct LicenseExceptionConfig {
+    /// Package purl to cover every version, or a versioned purl for one release.
+    pub purl: String,
+    /// License terms the exception covers; empty covers all of them.
+    #[serde(default)]
+    pub licenses: Vec<String>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct LicensePolicyConfig {
+    /// SPDX ids, or `<id> WITH <exception>` terms, that may be used freely.
+    #[serde(default)]
+    allowed: Vec<String>,
+    /// Terms that must not be used.
+    #[serde(default)]
+    denied: Vec<String>,
+    /// Terms that need legal review before use.
+    #[serde(default)]
+    review: Vec<String>,
+    /// Outcome for terms on no list and for components without a declared license.
+    #[serde(default)]
+    unknown: LicenseOutcome,
+    #[serde(default)]
+    exceptions: Vec<LicenseExceptionConfig>,
+}
+
+impl LicensePolicyConfig {
+    pub fn allowed(&self) -> &[String] {
+        &self.allowed
+    }
+
+    pub fn denied(&self) -> &[String] {
+        &self.denied
+    }
+
+    pub fn review(&self) -> &[String] {
+        &self.review
+    }
+
+    pub fn unknown(&self) -> LicenseOutcome {
+        self.unknown
+    }
+
+    pub fn exceptions(&self) -> &[LicenseExceptionConfig] {
+        &self.exceptions
+    }
+
+    /// No policy is enforced until at least one list has entries.
+    pub fn is_empty(&self) -> bool {
+        self.allowed.is_empty() && self.denied.is_empty() && self.review.is_empty()
+    }
+}
@@ -0,0 +1,33 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Default)]
+pub struct PoliciesConfig {
+    /// Directory of policy files (`*.yaml`, `*.yml` or `*.json`), one policy per file.
+    dir: Option<String>,
+    /// Longest a waiver may run, in days.
+    max_waiver_days: Option<u32>,
+    /// How many days before expiry a waiver reminder goes out.
+    waiver_reminder_days: Option<u32>,
+    /// Receives a JSON POST for every waiver reminder.
+    waiver_webhook_url: Option<String>,
+}
+
+impl PoliciesConfig {
+    pub fn dir(&self) -> Option<&str> {
+        self.dir.as_deref().filter(|dir| !dir.is_empty())
+    }
+
+    pub fn max_waiver_days(&self) -> u32 {
+        self.max_waiver_days.unwrap_or(90)
+    }
+
+    pub fn waiver_reminder_days(&self) -> u32 {
+        self.waiver_reminder_days.unwrap_or(14)
+    }
+
+    pub fn waiver_webhook_url(&self) -> Option<&str> {
+        self.waiver_webhook_url
+            .as_deref()
+            .filter(|url| !url.is_empty())
+    }
+}
@@ -0,0 +1,37 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Default)]
+pub struct RegistriesConfig {
+    /// Clone of the crates.io index (`https://github.com/rust-lang/crates.io-index`).
+    cargo_index: Option<String>,
+    /// Directory of npm packument JSON files, one per package.
+    npm_dir: Option<String>,
+    /// Directory of PyPI JSON API or simple (PEP 691) JSON files, one per project.
+    pypi_dir: Option<String>,
+    /// Maven repository mirror or directory holding `maven-metadata.xml` files.
+    maven_dir: Option<String>,
+    /// Years without a release after which a package counts as abandoned.
+    abandoned_after_years: Option<u32>,
+}
+
+impl RegistriesConfig {
+    pub fn cargo_index(&self) -> Option<&str> {
+        self.cargo_index.as_deref().filter(|dir| !dir.is_empty())
+    }
+
+    pub fn npm_dir(&self) -> Option<&str> {
+        self.npm_dir.as_deref().filter(|dir| !dir.is_empty())
+    }
+
+    pub fn pypi_dir(&self) -> Option<&str> {
+        self.pypi_dir.as_deref().filter(|dir| !dir.is_empty())
+    }
+
+    pub fn maven_dir(&self) -> Option<&str> {
+        self.maven_dir.as_deref().filter(|dir| !dir.is_empty())
+    }
+
+    pub fn abandoned_after_years(&self) -> u32 {
+        self.abandoned_after_years.unwrap_or(2)
+    }
+}
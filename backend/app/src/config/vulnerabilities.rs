@@ -0,0 +1,37 @@
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Default)]
+pub struct VulnerabilitiesConfig {
+    /// Directory holding OSV dumps (`<ecosystem>/all.zip` or loose `*.json` records).
+    osv_dir: Option<String>,
+    /// Directory holding NVD CVE 2.0 feeds (`nvdcve-2.0-<year>.json[.gz|.zip]`).
+    nvd_dir: Option<String>,
+    /// EPSS snapshot (`epss_scores-<date>.csv.gz`).
+    epss_file: Option<String>,
+    /// CISA KEV catalog (`known_exploited_vulnerabilities.json`).
+    kev_file: Option<String>,
+    /// Receives a JSON POST for every alert on a known exploited vulnerability.
+    kev_webhook_url: Option<String>,
+}
+
+impl VulnerabilitiesConfig {
+    pub fn osv_dir(&self) -> Option<&str> {
+        self.osv_dir.as_deref()
+    }
+
+    pub fn nvd_dir(&self) -> Option<&str> {
+        self.nvd_dir.as_deref()
+    }
+
+    pub fn epss_file(&self) -> Option<&str> {
+        self.epss_file.as_deref()
+    }
+
+    pub fn kev_file(&self) -> Option<&str> {
+        self.kev_file.as_deref()
+    }
+
+    pub fn kev_webhook_url(&self) -> Option<&str> {
+        self.kev_webhook_url.as_deref().filter(|url| !url.is_empty())
+    }
+}
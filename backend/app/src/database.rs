@@ -1,9 +1,15 @@
 use std::cmp::max;
 
 use crate::config;
-use sea_orm::{ConnectOptions, ConnectionTrait, Database, DatabaseConnection, Statement};
+use sea_orm::{
+    ActiveModelTrait, ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbErr,
+    EntityTrait, Iterable, Statement,
+};
 use tracing::info_span;
 
+/// Postgres accepts at most this many bind parameters per statement.
+const MAX_BIND_PARAMS: usize = 65_535;
+
 pub async fn init() -> anyhow::Result<DatabaseConnection> {
     let config = config::get().database();
 
@@ -39,6 +45,22 @@ pub async fn init() -> anyhow::Result<DatabaseConnection> {
     Ok(db)
 }
 
+/// Insert `models` with one statement per batch, sizing batches by the entity's column
+/// count so every statement stays under the bind parameter limit.
+pub async fn insert_in_batches<A, C>(db: &C, mut models: Vec<A>) -> Result<(), DbErr>
+where
+    A: ActiveModelTrait,
+    C: ConnectionTrait,
+{
+    let columns = <A::Entity as EntityTrait>::Column::iter().count();
+    let batch_size = (MAX_BIND_PARAMS / columns.max(1)).max(1);
+    while !models.is_empty() {
+        let rest = models.split_off(models.len().min(batch_size));
+        A::Entity::insert_many(models).exec(db).await?;
+        models = rest;
+    }
+    Ok(())
+}
 
 async fn log_db_version(db: &DatabaseConnection) -> anyhow::Result<()> {
     let version = db
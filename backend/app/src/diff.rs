@@ -0,0 +1,234 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet, HashSet},
+};
+
+use sea_orm::{
+    ColumnTrait, DatabaseBackend, DatabaseConnection, DbErr, EntityTrait, FromQueryResult,
+    QueryFilter, QueryOrder, QuerySelect, Statement,
+};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::{
+    entity::scan,
+    graph::GraphStore,
+    version::{compare_opt, Ecosystem},
+};
+
+/// What changed between two scans of the same project.
+///
+/// Packages are keyed by their versionless purl. A package that resolves to several
+/// versions in one scan is compared by its highest version.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScanDiff {
+    pub from_scan_id: i32,
+    pub to_scan_id: i32,
+    /// New direct dependencies.
+    pub added: Vec<DiffEntry>,
+    /// Dependencies, direct or transitive, that are gone.
+    pub removed: Vec<DiffEntry>,
+    pub upgraded: Vec<VersionChange>,
+    pub downgraded: Vec<VersionChange>,
+    pub scope_changed: Vec<ScopeChange>,
+    /// Packages newly pulled in through another dependency.
+    pub new_transitive: Vec<DiffEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffEntry {
+    pub package_id: i32,
+    pub purl: String,
+    pub package_purl: String,
+    pub name: String,
+    pub version: Option<String>,
+    pub scope: Option<String>,
+    pub direct: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionChange {
+    pub package_id: i32,
+    pub package_purl: String,
+    pub name: String,
+    pub from_version: Option<String>,
+    pub to_version: Option<String>,
+    pub scope: Option<String>,
+    pub direct: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScopeChange {
+    pub package_id: i32,
+    pub package_purl: String,
+    pub name: String,
+    pub version: Option<String>,
+    pub from_scope: Option<String>,
+    pub to_scope: Option<String>,
+}
+
+#[derive(Debug, Clone, FromQueryResult)]
+struct ComponentRow {
+    package_id: i32,
+    purl: String,
+    package_purl: String,
+    purl_type: String,
+    name: String,
+    version: Option<String>,
+    scope: Option<String>,
+    #[sea_orm(skip)]
+    direct: bool,
+}
+
+/// Latest successful scan of the project, older than `before` when given.
+pub async fn latest_successful_scan(
+    db: &DatabaseConnection,
+    project_id: i32,
+    before: Option<i32>,
+) -> Result<Option<i32>, DbErr> {
+    let mut query = scan::Entity::find()
+        .filter(scan::Column::ProjectId.eq(project_id))
+        .filter(scan::Column::Status.eq("success"));
+    if let Some(before) = before {
+        query = query.filter(scan::Column::Id.lt(before));
+    }
+
+    query
+        .order_by_desc(scan::Column::Id)
+        .select_only()
+        .column(scan::Column::Id)
+        .into_tuple::<i32>()
+        .one(db)
+        .await
+}
+
+/// Compare the components recorded by two scans of a project.
+pub async fn diff_scans(
+    db: &DatabaseConnection,
+    graph: &dyn GraphStore,
+    project_id: i32,
+    from_scan_id: i32,
+    to_scan_id: i32,
+) -> Result<ScanDiff, DbErr> {
+    let before = scan_components(db, graph, project_id, from_scan_id).await?;
+    let after = scan_components(db, graph, project_id, to_scan_id).await?;
+
+    let mut diff = ScanDiff {
+        from_scan_id,
+        to_scan_id,
+        ..Default::default()
+    };
+
+    let keys: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    for key in keys {
+        match (before.get(key), after.get(key)) {
+            (None, Some(new)) if new.direct => diff.added.push(new.into()),
+            (None, Some(new)) => diff.new_transitive.push(new.into()),
+            (Some(old), None) => diff.removed.push(old.into()),
+            (Some(old), Some(new)) => {
+                let change = || VersionChange {
+                    package_id: new.package_id,
+                    package_purl: new.package_purl.clone(),
+                    name: new.name.clone(),
+                    from_version: old.version.clone(),
+                    to_version: new.version.clone(),
+                    scope: new.scope.clone(),
+                    direct: new.direct,
+                };
+                let ecosystem = Ecosystem::from_purl_type(&new.purl_type);
+                match compare_opt(ecosystem, old.version.as_deref(), new.version.as_deref()) {
+                    Ordering::Less => diff.upgraded.push(change()),
+                    Ordering::Greater => diff.downgraded.push(change()),
+                    Ordering::Equal => {}
+                }
+
+                if old.scope != new.scope {
+                    diff.scope_changed.push(ScopeChange {
+                        package_id: new.package_id,
+                        package_purl: new.package_purl.clone(),
+                        name: new.name.clone(),
+                        version: new.version.clone(),
+                        from_scope: old.scope.clone(),
+                        to_scope: new.scope.clone(),
+                    });
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    Ok(diff)
+}
+
+impl From<&ComponentRow> for DiffEntry {
+    fn from(row: &ComponentRow) -> Self {
+        DiffEntry {
+            package_id: row.package_id,
+            purl: row.purl.clone(),
+            package_purl: row.package_purl.clone(),
+            name: row.name.clone(),
+            version: row.version.clone(),
+            scope: row.scope.clone(),
+            direct: row.direct,
+        }
+    }
+}
+
+/// Components of one scan keyed by package purl, keeping the highest version of each.
+///
+/// A component is direct when the scan's graph links it to the project root. Scans whose
+/// edges were archived or pruned by retention have no graph left, so all their components
+/// count as direct; so do those of any scan while the graph store is unavailable.
+async fn scan_components(
+    db: &DatabaseConnection,
+    graph: &dyn GraphStore,
+    project_id: i32,
+    scan_id: i32,
+) -> Result<BTreeMap<String, ComponentRow>, DbErr> {
+    let rows = ComponentRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT d.package_id, COALESCE(v.purl, p.purl) AS purl, p.purl AS package_purl, \
+                p.purl_type, p.name, d.resolved_version AS version, d.scope \
+         FROM direct_dependencies d \
+         JOIN packages p ON p.id = d.package_id \
+         LEFT JOIN package_versions v ON v.id = d.package_version_id \
+         WHERE d.scan_id = $1 \
+         ORDER BY d.id",
+        [scan_id.into()],
+    ))
+    .all(db)
+    .await?;
+
+    let roots: HashSet<String> = match graph.dependency_tree(project_id, Some(scan_id), 1).await {
+        Ok(edges) => edges.into_iter().map(|edge| edge.purl).collect(),
+        Err(err) => {
+            warn!(error = ?err, scan_id, "failed to load direct dependencies from the graph");
+            HashSet::new()
+        }
+    };
+
+    let mut components: BTreeMap<String, ComponentRow> = BTreeMap::new();
+    for mut row in rows {
+        row.direct = roots.is_empty() || roots.contains(&row.purl);
+        match components.get_mut(&row.package_purl) {
+            Some(existing) => {
+                let direct = existing.direct || row.direct;
+                let ecosystem = Ecosystem::from_purl_type(&row.purl_type);
+                if compare_opt(
+                    ecosystem,
+                    existing.version.as_deref(),
+                    row.version.as_deref(),
+                ) == Ordering::Less
+                {
+                    *existing = row;
+                }
+                existing.direct = direct;
+            }
+            None => {
+                components.insert(row.package_purl.clone(), row);
+            }
+        }
+    }
+
+    Ok(components)
+}
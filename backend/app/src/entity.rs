@@ -1,4 +1,20 @@
+pub mod affected_range;
+pub mod cpe_match;
+pub mod dependency_edge;
 pub mod direct_dependency;
+pub mod epss_score;
+pub mod kev_alert;
+pub mod kev_entry;
+pub mod license_violation;
 pub mod package;
+pub mod package_health_finding;
+pub mod package_version;
+pub mod policy_evaluation;
+pub mod policy_waiver;
 pub mod project;
+pub mod registry_version;
 pub mod scan;
+pub mod vulnerability;
+pub mod vulnerability_analysis;
+pub mod vulnerability_analysis_event;
+pub mod vulnerability_finding;
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+use sea_orm::JsonValue;
+
+/// One affected interval (or explicit version list) of a package in an advisory.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "affected_ranges")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub vulnerability_id: i32,
+    /// OSV ecosystem as written in the advisory (npm, PyPI, Maven, crates.io...).
+    pub ecosystem: String,
+    /// Package name in the ecosystem's own notation.
+    pub package_name: String,
+    /// Versionless purl, matched against `packages.purl`.
+    #[sea_orm(column_type = "Text")]
+    pub package_purl: String,
+    /// SEMVER, ECOSYSTEM, GIT or VERSIONS for an explicit version list.
+    pub range_type: String,
+    /// First affected version; `0` means every version before `fixed`.
+    pub introduced: Option<String>,
+    /// First version that is no longer affected.
+    pub fixed: Option<String>,
+    /// Last affected version, used instead of `fixed` when no fix exists.
+    pub last_affected: Option<String>,
+    /// Explicitly listed affected versions (JSON array) for VERSIONS rows.
+    pub versions: Option<JsonValue>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::vulnerability::Entity",
+        from = "Column::VulnerabilityId",
+        to = "super::vulnerability::Column::Id"
+    )]
+    Vulnerability,
+}
+
+impl Related<super::vulnerability::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Vulnerability.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+
+/// One vulnerable CPE match criterion of an NVD CVE configuration.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "cpe_matches")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub vulnerability_id: i32,
+    /// CPE 2.3 criteria as published by NVD.
+    #[sea_orm(column_type = "Text")]
+    pub criteria: String,
+    /// `criteria` with every field after the product wildcarded, matched against
+    /// `packages.cpe`.
+    #[sea_orm(column_type = "Text")]
+    pub package_cpe: String,
+    /// Exact version named in the criteria; `None` when it is `*` or `-`.
+    pub version: Option<String>,
+    pub version_start_including: Option<String>,
+    pub version_start_excluding: Option<String>,
+    pub version_end_including: Option<String>,
+    pub version_end_excluding: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::vulnerability::Entity",
+        from = "Column::VulnerabilityId",
+        to = "super::vulnerability::Column::Id"
+    )]
+    Vulnerability,
+}
+
+impl Related<super::vulnerability::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Vulnerability.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,52 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+
+/// One parent -> child edge of a scan's dependency graph.
+/// Backs the Postgres graph store so transitive queries work without Neo4j.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "dependency_edges")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub scan_id: i32,
+    pub project_id: i32,
+    /// Depending package; `None` means the edge starts at the project root.
+    pub parent_package_id: Option<i32>,
+    /// Package being depended on.
+    pub child_package_id: i32,
+    /// Version row of the parent; the graph is walked on versions, not packages.
+    pub parent_version_id: Option<i32>,
+    /// Version row of the child.
+    pub child_version_id: Option<i32>,
+    /// Resolved version of the child as reported in this scan.
+    pub resolved_version: Option<String>,
+    /// CycloneDX scope of the child (required/optional/excluded).
+    pub scope: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(belongs_to = "super::scan::Entity", from = "Column::ScanId", to = "super::scan::Column::Id")]
+    Scan,
+    #[sea_orm(belongs_to = "super::project::Entity", from = "Column::ProjectId", to = "super::project::Column::Id")]
+    Project,
+    #[sea_orm(belongs_to = "super::package::Entity", from = "Column::ParentPackageId", to = "super::package::Column::Id")]
+    Parent,
+    #[sea_orm(belongs_to = "super::package::Entity", from = "Column::ChildPackageId", to = "super::package::Column::Id")]
+    Child,
+}
+
+impl Related<super::scan::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Scan.def()
+    }
+}
+
+impl Related<super::project::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Project.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
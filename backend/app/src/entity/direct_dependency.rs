@@ -11,6 +11,8 @@ pub struct Model {
     pub scan_id: i32,
     pub project_id: i32,
     pub package_id: i32,
+    /// Concrete version row this dependency resolved to.
+    pub package_version_id: Option<i32>,
     /// Version constraint declared in manifest (e.g., ^1.2.3 or [1.0,2.0)).
     pub declared_constraint: Option<String>,
     /// Resolved version from lock/SBOM component entry.
@@ -38,6 +40,8 @@ pub enum Relation {
     Project,
     #[sea_orm(belongs_to = "super::package::Entity", from = "Column::PackageId", to = "super::package::Column::Id")]
     Package,
+    #[sea_orm(belongs_to = "super::package_version::Entity", from = "Column::PackageVersionId", to = "super::package_version::Column::Id")]
+    PackageVersion,
 }
 
 impl Related<super::scan::Entity> for Entity {
@@ -58,4 +62,10 @@ impl Related<super::package::Entity> for Entity {
     }
 }
 
+impl Related<super::package_version::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PackageVersion.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,22 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use sea_orm::entity::prelude::*;
+
+/// Exploit Prediction Scoring System score of a CVE from the latest imported snapshot.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "epss_scores")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub cve: String,
+    /// Probability of exploitation activity in the next 30 days, 0 to 1.
+    pub epss: f64,
+    /// Rank of the score among all scored CVEs, 0 to 1.
+    pub percentile: f64,
+    pub model_version: Option<String>,
+    pub score_date: Option<NaiveDate>,
+    pub imported_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,31 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use sea_orm::entity::prelude::*;
+
+/// A CVE in CISA's Known Exploited Vulnerabilities catalog.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "kev_entries")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub cve: String,
+    pub vendor_project: Option<String>,
+    pub product: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub vulnerability_name: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub short_description: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub required_action: Option<String>,
+    pub date_added: NaiveDate,
+    /// Remediation deadline for US federal agencies.
+    pub due_date: Option<NaiveDate>,
+    /// `knownRansomwareCampaignUse` is `Known`.
+    pub known_ransomware: bool,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub notes: Option<String>,
+    pub imported_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,59 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+use sea_orm::JsonValue;
+
+/// A component of a scan whose licenses the license policy does not allow.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "license_violations")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub project_id: i32,
+    pub scan_id: i32,
+    pub package_version_id: Option<i32>,
+    /// Versioned purl of the component, or the package purl when it had no version.
+    #[sea_orm(column_type = "Text")]
+    pub purl: String,
+    pub scope: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub license_expression: Option<String>,
+    /// `denied` or `review`.
+    pub outcome: String,
+    /// License terms of the expression that caused the outcome.
+    pub licenses: JsonValue,
+    #[sea_orm(column_type = "Text")]
+    pub reason: String,
+    /// Shortest dependency paths from the project root to the component.
+    pub paths: JsonValue,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::project::Entity",
+        from = "Column::ProjectId",
+        to = "super::project::Column::Id"
+    )]
+    Project,
+    #[sea_orm(
+        belongs_to = "super::scan::Entity",
+        from = "Column::ScanId",
+        to = "super::scan::Column::Id"
+    )]
+    Scan,
+}
+
+impl Related<super::project::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Project.def()
+    }
+}
+
+impl Related<super::scan::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Scan.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
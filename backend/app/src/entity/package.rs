@@ -7,7 +7,7 @@ use sea_orm::JsonValue;
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
-    /// Full Package URL. Keep unique so we can reuse the same record across scans.
+    /// Package URL without version, qualifiers or subpath. Unique so all versions share one record.
     #[sea_orm(unique, column_type = "Text")]
     pub purl: String,
     /// Extracted type from PURL (e.g. npm, maven, pypi) to speed lookups.
@@ -18,6 +18,9 @@ pub struct Model {
     pub name: String,
     /// Qualifiers from PURL stored as JSON for flexible filtering.
     pub qualifiers: Option<JsonValue>,
+    /// Versionless CPE 2.3 name reported by the SBOM, matched against `cpe_matches.package_cpe`.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub cpe: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -26,6 +29,8 @@ pub struct Model {
 pub enum Relation {
     #[sea_orm(has_many = "super::direct_dependency::Entity")]
     DirectDependency,
+    #[sea_orm(has_many = "super::package_version::Entity")]
+    PackageVersion,
 }
 
 impl Related<super::direct_dependency::Entity> for Entity {
@@ -34,4 +39,10 @@ impl Related<super::direct_dependency::Entity> for Entity {
     }
 }
 
+impl Related<super::package_version::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PackageVersion.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
\ No newline at end of file
@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+
+/// A scanned component that its registry marks as yanked or deprecated, or whose package
+/// has had no release for a long time.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "package_health_findings")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub project_id: i32,
+    pub scan_id: i32,
+    pub package_id: i32,
+    /// Versioned purl of the component.
+    #[sea_orm(column_type = "Text")]
+    pub purl: String,
+    pub version: Option<String>,
+    /// `yanked`, `deprecated` or `abandoned`.
+    pub kind: String,
+    /// Yank reason, deprecation message or the date of the last release.
+    #[sea_orm(column_type = "Text")]
+    pub detail: String,
+    /// Latest known release of the package.
+    pub last_release_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::project::Entity",
+        from = "Column::ProjectId",
+        to = "super::project::Column::Id"
+    )]
+    Project,
+    #[sea_orm(
+        belongs_to = "super::scan::Entity",
+        from = "Column::ScanId",
+        to = "super::scan::Column::Id"
+    )]
+    Scan,
+    #[sea_orm(
+        belongs_to = "super::package::Entity",
+        from = "Column::PackageId",
+        to = "super::package::Column::Id"
+    )]
+    Package,
+}
+
+impl Related<super::project::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Project.def()
+    }
+}
+
+impl Related<super::scan::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Scan.def()
+    }
+}
+
+impl Related<super::package::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Package.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+use sea_orm::JsonValue;
+
+/// A concrete version of a package as observed in scans across the org.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "package_versions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub package_id: i32,
+    /// Full Package URL including the version, as reported by the SBOM.
+    #[sea_orm(unique, column_type = "Text")]
+    pub purl: String,
+    /// Version string from the PURL or component; missing when the SBOM had none.
+    pub version: Option<String>,
+    /// SPDX expression of the declared licenses; ids outside the SPDX list are
+    /// `LicenseRef-` ids.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub license_expression: Option<String>,
+    /// Distinct license ids of `license_expression`.
+    pub licenses: JsonValue,
+    /// First time any scan reported this version.
+    pub first_seen_at: DateTime<Utc>,
+    /// Most recent time any scan reported this version.
+    pub last_seen_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(belongs_to = "super::package::Entity", from = "Column::PackageId", to = "super::package::Column::Id")]
+    Package,
+    #[sea_orm(has_many = "super::direct_dependency::Entity")]
+    DirectDependency,
+}
+
+impl Related<super::package::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Package.def()
+    }
+}
+
+impl Related<super::direct_dependency::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::DirectDependency.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
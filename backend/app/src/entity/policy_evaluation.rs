@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+use sea_orm::JsonValue;
+
+/// The latest policy evaluation of a scan.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "policy_evaluations")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub project_id: i32,
+    #[sea_orm(unique)]
+    pub scan_id: i32,
+    /// `pass`, `warn` or `fail`.
+    pub verdict: String,
+    /// Names of the policies that applied to the project.
+    pub policies: JsonValue,
+    pub violations: JsonValue,
+    pub evaluated_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::project::Entity",
+        from = "Column::ProjectId",
+        to = "super::project::Column::Id"
+    )]
+    Project,
+    #[sea_orm(
+        belongs_to = "super::scan::Entity",
+        from = "Column::ScanId",
+        to = "super::scan::Column::Id"
+    )]
+    Scan,
+}
+
+impl Related<super::project::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Project.def()
+    }
+}
+
+impl Related<super::scan::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Scan.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
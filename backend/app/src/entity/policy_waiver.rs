@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+
+/// A time-boxed acceptance of the violations of one policy rule on matching components
+/// of a project.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "policy_waivers")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub project_id: i32,
+    /// Rule id, `<policy>/<rule>`.
+    pub rule: String,
+    /// Purl pattern of the waived components; a version limits it to matching releases
+    /// and a trailing `*` matches a prefix.
+    #[sea_orm(column_type = "Text")]
+    pub purl: String,
+    #[sea_orm(column_type = "Text")]
+    pub justification: String,
+    /// Principal that approved the waiver.
+    pub approver_id: String,
+    pub approver: String,
+    pub expires_at: DateTime<Utc>,
+    /// When the expiry reminder went out.
+    pub reminded_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revoked_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::project::Entity",
+        from = "Column::ProjectId",
+        to = "super::project::Column::Id"
+    )]
+    Project,
+}
+
+impl Related<super::project::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Project.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
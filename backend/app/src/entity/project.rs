@@ -1,4 +1,5 @@
 use sea_orm::entity::prelude::*;
+use sea_orm::JsonValue;
 use chrono::{DateTime, Utc};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
@@ -10,6 +11,10 @@ pub struct Model {
     #[sea_orm(unique)]
     pub name: String,
     pub description: Option<String>,
+    /// Owning team, used to group org-wide reports.
+    pub team: Option<String>,
+    /// Free-form labels, e.g. to scope policies; sorted and distinct.
+    pub tags: JsonValue,
     /// Repository HTTP(S) URL for cloning. Was `url`, keep separate to avoid clashes with app URL.
     pub repo_url: Option<String>,
     /// Optional public/project homepage URL.
@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+
+/// A release of a package as listed by its registry, imported from a local mirror.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "registry_versions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub package_id: i32,
+    pub version: String,
+    /// Unknown for registries that do not record release dates per version.
+    pub published_at: Option<DateTime<Utc>>,
+    /// Yanked on crates.io or PyPI.
+    pub yanked: bool,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub yanked_reason: Option<String>,
+    /// Deprecation message on npm.
+    #[sea_orm(column_type = "Text", nullable)]
+    pub deprecated: Option<String>,
+    pub imported_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::package::Entity",
+        from = "Column::PackageId",
+        to = "super::package::Column::Id"
+    )]
+    Package,
+}
+
+impl Related<super::package::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Package.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+use sea_orm::JsonValue;
+
+/// An advisory imported from an OSV dump or an NVD CVE feed.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "vulnerabilities")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    /// OSV identifier (GHSA-..., PYSEC-..., RUSTSEC-...) or CVE id for NVD records.
+    /// Unique across databases.
+    #[sea_orm(unique)]
+    pub osv_id: String,
+    /// `osv` or `nvd`: which feed the advisory was imported from.
+    pub source: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub summary: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub details: Option<String>,
+    /// critical/high/medium/low, from the CVSS score or the database's own rating.
+    pub severity: Option<String>,
+    /// CVSS v3 base score when the advisory carries a v3 vector.
+    pub cvss_score: Option<f64>,
+    pub cvss_vector: Option<String>,
+    /// Other ids of the same issue (CVE-..., GHSA-...) as a JSON array.
+    pub aliases: JsonValue,
+    /// OSV `references` entries as published.
+    pub references: Option<JsonValue>,
+    pub published_at: Option<DateTime<Utc>>,
+    /// OSV `modified`; re-imports skip records that did not change.
+    pub modified_at: Option<DateTime<Utc>>,
+    pub withdrawn_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::affected_range::Entity")]
+    AffectedRange,
+    #[sea_orm(has_many = "super::cpe_match::Entity")]
+    CpeMatch,
+    #[sea_orm(has_many = "super::vulnerability_finding::Entity")]
+    VulnerabilityFinding,
+}
+
+impl Related<super::affected_range::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AffectedRange.def()
+    }
+}
+
+impl Related<super::cpe_match::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CpeMatch.def()
+    }
+}
+
+impl Related<super::vulnerability_finding::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::VulnerabilityFinding.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
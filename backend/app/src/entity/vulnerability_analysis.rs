@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+
+/// The current triage decision for one advisory on one component of a project.
+/// Findings are rebuilt on every scan, so analyses are keyed by project, advisory and
+/// versioned purl and carry over to later scans of the same component.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "vulnerability_analyses")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub project_id: i32,
+    pub vulnerability_id: i32,
+    /// Versioned purl of the analysed component.
+    #[sea_orm(column_type = "Text")]
+    pub purl: String,
+    /// not_affected, affected, fixed, under_investigation or false_positive.
+    pub state: String,
+    /// Why the component is not affected, in CycloneDX or OpenVEX vocabulary.
+    pub justification: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub detail: Option<String>,
+    /// Principal that made the latest change.
+    pub author_id: String,
+    pub author: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::vulnerability::Entity",
+        from = "Column::VulnerabilityId",
+        to = "super::vulnerability::Column::Id"
+    )]
+    Vulnerability,
+    #[sea_orm(
+        belongs_to = "super::project::Entity",
+        from = "Column::ProjectId",
+        to = "super::project::Column::Id"
+    )]
+    Project,
+    #[sea_orm(has_many = "super::vulnerability_analysis_event::Entity")]
+    Event,
+}
+
+impl Related<super::vulnerability::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Vulnerability.def()
+    }
+}
+
+impl Related<super::project::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Project.def()
+    }
+}
+
+impl Related<super::vulnerability_analysis_event::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Event.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
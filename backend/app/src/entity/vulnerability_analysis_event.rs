@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+
+/// One change of an analysis, kept as its history.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "vulnerability_analysis_events")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub analysis_id: i32,
+    /// State, justification and detail as set by this change.
+    pub state: String,
+    pub justification: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub detail: Option<String>,
+    pub author_id: String,
+    pub author: String,
+    /// `api`, `cyclonedx-vex` or `openvex`.
+    pub source: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::vulnerability_analysis::Entity",
+        from = "Column::AnalysisId",
+        to = "super::vulnerability_analysis::Column::Id"
+    )]
+    Analysis,
+}
+
+impl Related<super::vulnerability_analysis::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Analysis.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,7 @@
+//! Renderers that turn stored scan data into documents for other tools.
+
+pub mod graph;
+pub mod inventory;
+pub mod notice;
+pub mod sbom;
+pub mod vex;
@@ -0,0 +1,318 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Write,
+};
+
+use quick_xml::escape::escape;
+use serde::{Deserialize, Serialize};
+
+use crate::graph::TreeEdge;
+
+/// Id of the synthetic node that stands for the scanned project.
+pub const ROOT_ID: &str = "root";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormat {
+    #[serde(alias = "graphviz")]
+    Dot,
+    Graphml,
+    #[serde(alias = "mmd")]
+    Mermaid,
+    #[default]
+    Json,
+}
+
+impl GraphFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "text/vnd.graphviz; charset=utf-8",
+            GraphFormat::Graphml => "application/graphml+xml; charset=utf-8",
+            GraphFormat::Mermaid => "text/vnd.mermaid; charset=utf-8",
+            GraphFormat::Json => "application/json",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "dot",
+            GraphFormat::Graphml => "graphml",
+            GraphFormat::Mermaid => "mmd",
+            GraphFormat::Json => "json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphNode {
+    /// Versioned purl, or [`ROOT_ID`] for the project itself.
+    pub id: String,
+    pub label: String,
+    pub version: Option<String>,
+    pub scope: Option<String>,
+    /// Shortest distance from the project; 0 for the root.
+    pub depth: u32,
+    pub highlighted: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphLink {
+    pub source: String,
+    pub target: String,
+    pub scope: Option<String>,
+}
+
+/// A scan's dependency graph, rooted at the project.
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphDocument {
+    pub name: String,
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphLink>,
+}
+
+impl GraphDocument {
+    /// Build the document from tree edges.
+    ///
+    /// With `scopes` set, edges of other scopes are dropped together with whatever is
+    /// only reachable through them. A node is highlighted when its purl equals one of
+    /// `highlight`, or when a versionless entry matches it regardless of version.
+    pub fn build(
+        name: &str,
+        edges: &[TreeEdge],
+        scopes: Option<&HashSet<String>>,
+        highlight: &[String],
+    ) -> Self {
+        let kept: Vec<&TreeEdge> = edges
+            .iter()
+            .filter(|edge| {
+                scopes.is_none_or(|scopes| {
+                    scopes.contains(edge.scope.as_deref().unwrap_or("required"))
+                })
+            })
+            .collect();
+
+        // Breadth-first from the root so scope filtering prunes unreachable subtrees.
+        let mut children: HashMap<&str, Vec<&TreeEdge>> = HashMap::new();
+        for edge in &kept {
+            children
+                .entry(edge.parent.as_deref().unwrap_or(ROOT_ID))
+                .or_default()
+                .push(edge);
+        }
+
+        let mut depths: BTreeMap<String, (u32, &TreeEdge)> = BTreeMap::new();
+        let mut links = Vec::new();
+        let mut seen_links = HashSet::new();
+        let mut frontier = vec![(ROOT_ID, 0u32)];
+        while !frontier.is_empty() {
+            let mut next = Vec::new();
+            for (parent, depth) in frontier {
+                for edge in children.get(parent).into_iter().flatten() {
+                    if seen_links.insert((parent, edge.purl.as_str())) {
+                        links.push(GraphLink {
+                            source: parent.to_string(),
+                            target: edge.purl.clone(),
+                            scope: edge.scope.clone(),
+                        });
+                    }
+                    if !depths.contains_key(&edge.purl) {
+                        depths.insert(edge.purl.clone(), (depth + 1, edge));
+                        next.push((edge.purl.as_str(), depth + 1));
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        let mut nodes = vec![GraphNode {
+            id: ROOT_ID.to_string(),
+            label: name.to_string(),
+            version: None,
+            scope: None,
+            depth: 0,
+            highlighted: false,
+        }];
+        nodes.extend(depths.into_iter().map(|(purl, (depth, edge))| GraphNode {
+            label: label(&purl),
+            highlighted: is_highlighted(&purl, highlight),
+            version: edge.version.clone(),
+            scope: edge.scope.clone(),
+            depth,
+            id: purl,
+        }));
+
+        GraphDocument {
+            name: name.to_string(),
+            nodes,
+            edges: links,
+        }
+    }
+
+    pub fn render(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(),
+            GraphFormat::Graphml => self.to_graphml(),
+            GraphFormat::Mermaid => self.to_mermaid(),
+            GraphFormat::Json => serde_json::to_string_pretty(self).unwrap_or_default(),
+        }
+    }
+
+    /// Graphviz digraph, left to right, with highlighted nodes filled red.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "digraph {} {{", dot_quote(&self.name));
+        out.push_str("  rankdir=LR;\n");
+        out.push_str("  node [shape=box, style=rounded, fontname=\"Helvetica\"];\n");
+        for node in &self.nodes {
+            let mut attrs = vec![format!("label={}", dot_quote(&node.label))];
+            if node.id == ROOT_ID {
+                attrs.push("shape=folder".into());
+                attrs.push("style=bold".into());
+            } else if node.highlighted {
+                attrs.push("style=\"rounded,filled\"".into());
+                attrs.push("fillcolor=\"#f8d7da\"".into());
+                attrs.push("color=\"#c0392b\"".into());
+            } else if node.scope.as_deref().is_some_and(|s| s != "required") {
+                attrs.push("style=\"rounded,dashed\"".into());
+            }
+            let _ = writeln!(out, "  {} [{}];", dot_quote(&node.id), attrs.join(", "));
+        }
+        for edge in &self.edges {
+            let _ = writeln!(
+                out,
+                "  {} -> {};",
+                dot_quote(&edge.source),
+                dot_quote(&edge.target)
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// GraphML with label, version, scope, depth and highlight as node data keys.
+    pub fn to_graphml(&self) -> String {
+        let mut out = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\" \
+             xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+             xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns \
+             http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">\n\
+             \x20 <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n\
+             \x20 <key id=\"version\" for=\"node\" attr.name=\"version\" attr.type=\"string\"/>\n\
+             \x20 <key id=\"scope\" for=\"all\" attr.name=\"scope\" attr.type=\"string\"/>\n\
+             \x20 <key id=\"depth\" for=\"node\" attr.name=\"depth\" attr.type=\"int\"/>\n\
+             \x20 <key id=\"highlighted\" for=\"node\" attr.name=\"highlighted\" attr.type=\"boolean\">\
+             <default>false</default></key>\n",
+        );
+        let _ = writeln!(
+            out,
+            "  <graph id=\"{}\" edgedefault=\"directed\">",
+            escape(&self.name)
+        );
+        for node in &self.nodes {
+            let _ = writeln!(out, "    <node id=\"{}\">", escape(&node.id));
+            let _ = writeln!(
+                out,
+                "      <data key=\"label\">{}</data>",
+                escape(&node.label)
+            );
+            if let Some(version) = &node.version {
+                let _ = writeln!(
+                    out,
+                    "      <data key=\"version\">{}</data>",
+                    escape(version)
+                );
+            }
+            if let Some(scope) = &node.scope {
+                let _ = writeln!(out, "      <data key=\"scope\">{}</data>", escape(scope));
+            }
+            let _ = writeln!(out, "      <data key=\"depth\">{}</data>", node.depth);
+            if node.highlighted {
+                out.push_str("      <data key=\"highlighted\">true</data>\n");
+            }
+            out.push_str("    </node>\n");
+        }
+        for (index, edge) in self.edges.iter().enumerate() {
+            let _ = write!(
+                out,
+                "    <edge id=\"e{index}\" source=\"{}\" target=\"{}\"",
+                escape(&edge.source),
+                escape(&edge.target)
+            );
+            match &edge.scope {
+                Some(scope) => {
+                    let _ = writeln!(out, "><data key=\"scope\">{}</data></edge>", escape(scope));
+                }
+                None => out.push_str("/>\n"),
+            }
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    /// Mermaid flowchart. Purls are not valid Mermaid ids, so nodes are numbered.
+    pub fn to_mermaid(&self) -> String {
+        let ids: HashMap<&str, String> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (node.id.as_str(), format!("n{index}")))
+            .collect();
+
+        let mut out = String::from("graph LR\n");
+        for node in &self.nodes {
+            let id = &ids[node.id.as_str()];
+            let label = mermaid_escape(&node.label);
+            if node.id == ROOT_ID {
+                let _ = writeln!(out, "  {id}[[\"{label}\"]]");
+            } else {
+                let _ = writeln!(out, "  {id}[\"{label}\"]");
+            }
+        }
+        for edge in &self.edges {
+            let _ = writeln!(
+                out,
+                "  {} --> {}",
+                ids[edge.source.as_str()],
+                ids[edge.target.as_str()]
+            );
+        }
+
+        let highlighted: Vec<&str> = self
+            .nodes
+            .iter()
+            .filter(|node| node.highlighted)
+            .map(|node| ids[node.id.as_str()].as_str())
+            .collect();
+        if !highlighted.is_empty() {
+            out.push_str("  classDef highlight fill:#f8d7da,stroke:#c0392b,stroke-width:2px\n");
+            let _ = writeln!(out, "  class {} highlight", highlighted.join(","));
+        }
+        out
+    }
+}
+
+/// `pkg:npm/%40scope/name@1.0.0` reads better as `@scope/name@1.0.0`.
+fn label(purl: &str) -> String {
+    let body = purl.strip_prefix("pkg:").unwrap_or(purl);
+    let body = body.split(['?', '#']).next().unwrap_or(body);
+    let body = body.split_once('/').map_or(body, |(_, rest)| rest);
+    body.replace("%40", "@").replace("%2F", "/")
+}
+
+fn is_highlighted(purl: &str, highlight: &[String]) -> bool {
+    highlight.iter().any(|wanted| {
+        purl == wanted
+            || purl
+                .strip_prefix(wanted.as_str())
+                .is_some_and(|rest| rest.starts_with('@'))
+    })
+}
+
+fn dot_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn mermaid_escape(value: &str) -> String {
+    value.replace('"', "#quot;")
+}
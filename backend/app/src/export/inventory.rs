@@ -0,0 +1,215 @@
+use rust_xlsxwriter::{Format, Workbook};
+use serde::Deserialize;
+
+use super::sbom::{SbomComponent, SbomDocument, SbomFormat, SbomSubject};
+use crate::inventory::Inventory;
+
+const COLUMNS: [&str; 11] = [
+    "purl",
+    "package_purl",
+    "type",
+    "namespace",
+    "name",
+    "version",
+    "scope",
+    "license",
+    "project_count",
+    "projects",
+    "teams",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum InventoryFormat {
+    #[default]
+    #[serde(rename = "cyclonedx-json", alias = "cyclonedx")]
+    CycloneDxJson,
+    #[serde(rename = "cyclonedx-xml")]
+    CycloneDxXml,
+    #[serde(rename = "spdx-json", alias = "spdx")]
+    SpdxJson,
+    #[serde(rename = "csv")]
+    Csv,
+    #[serde(rename = "xlsx")]
+    Xlsx,
+}
+
+impl InventoryFormat {
+    fn sbom(self) -> Option<SbomFormat> {
+        match self {
+            InventoryFormat::CycloneDxJson => Some(SbomFormat::CycloneDxJson),
+            InventoryFormat::CycloneDxXml => Some(SbomFormat::CycloneDxXml),
+            InventoryFormat::SpdxJson => Some(SbomFormat::SpdxJson),
+            InventoryFormat::Csv | InventoryFormat::Xlsx => None,
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            InventoryFormat::Csv => "text/csv; charset=utf-8",
+            InventoryFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+            other => other.sbom().map_or("", SbomFormat::content_type),
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            InventoryFormat::Csv => "csv",
+            InventoryFormat::Xlsx => "xlsx",
+            other => other.sbom().map_or("", SbomFormat::extension),
+        }
+    }
+
+    /// Parse the names accepted by the `format` query parameter.
+    pub fn from_name(name: &str) -> Option<Self> {
+        serde_json::from_value(serde_json::Value::String(name.to_ascii_lowercase())).ok()
+    }
+}
+
+/// Render the inventory; `name` titles the BOM or document.
+pub fn render(
+    inventory: &Inventory,
+    name: &str,
+    format: InventoryFormat,
+) -> anyhow::Result<Vec<u8>> {
+    match format.sbom() {
+        Some(sbom) => Ok(to_sbom(inventory, name).render(sbom).into_bytes()),
+        None if format == InventoryFormat::Csv => to_csv(inventory),
+        None => to_xlsx(inventory),
+    }
+}
+
+/// One merged BOM whose root depends on every component. Each component lists the
+/// projects consuming it in the `check-deps:consuming_projects` property.
+pub fn to_sbom(inventory: &Inventory, name: &str) -> SbomDocument {
+    let mut properties = vec![(
+        "check-deps:project_count".to_string(),
+        inventory.projects.len().to_string(),
+    )];
+    properties.extend(inventory.projects.iter().map(|project| {
+        (
+            "check-deps:project".to_string(),
+            format!("{} (scan {})", project.name, project.scan_id),
+        )
+    }));
+
+    let subject = SbomSubject {
+        bom_ref: format!("inventory:{name}"),
+        name: name.to_string(),
+        version: None,
+        description: Some(format!(
+            "Third-party components of {} projects",
+            inventory.projects.len()
+        )),
+        purl: None,
+        repo_url: None,
+        homepage: None,
+        properties,
+    };
+
+    let components = inventory
+        .components
+        .iter()
+        .map(|component| {
+            let mut properties = vec![
+                (
+                    "check-deps:consuming_projects".to_string(),
+                    component.projects.join(", "),
+                ),
+                (
+                    "check-deps:project_count".to_string(),
+                    component.projects.len().to_string(),
+                ),
+            ];
+            if !component.teams.is_empty() {
+                properties.push(("check-deps:teams".to_string(), component.teams.join(", ")));
+            }
+            SbomComponent {
+                purl: component.purl.clone(),
+                namespace: component.namespace.clone(),
+                name: component.name.clone(),
+                version: component.version.clone(),
+                scope: component.scope.clone(),
+                license: component.license.clone(),
+                properties,
+            }
+        })
+        .collect();
+
+    let mut document = SbomDocument::new(subject, components);
+    let refs: Vec<String> = document.components.iter().map(|c| c.purl.clone()).collect();
+    document.add_dependencies(refs.iter().map(|r| (None, r.as_str())));
+    document
+}
+
+fn rows(inventory: &Inventory) -> impl Iterator<Item = [String; 11]> + '_ {
+    inventory.components.iter().map(|component| {
+        [
+            component.purl.clone(),
+            component.package_purl.clone(),
+            component.purl_type.clone(),
+            component.namespace.clone().unwrap_or_default(),
+            component.name.clone(),
+            component.version.clone().unwrap_or_default(),
+            component.scope.clone().unwrap_or_default(),
+            component.license.clone().unwrap_or_default(),
+            component.projects.len().to_string(),
+            component.projects.join("; "),
+            component.teams.join("; "),
+        ]
+    })
+}
+
+pub fn to_csv(inventory: &Inventory) -> anyhow::Result<Vec<u8>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(COLUMNS)?;
+    for row in rows(inventory) {
+        writer.write_record(&row)?;
+    }
+    Ok(writer.into_inner()?)
+}
+
+/// Workbook with a `Components` sheet shaped like the CSV and a `Projects` sheet.
+pub fn to_xlsx(inventory: &Inventory) -> anyhow::Result<Vec<u8>> {
+    let mut workbook = Workbook::new();
+    let header = Format::new().set_bold();
+
+    let sheet = workbook.add_worksheet().set_name("Components")?;
+    for (col, title) in COLUMNS.iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *title, &header)?;
+    }
+    for (index, row) in rows(inventory).enumerate() {
+        let line = index as u32 + 1;
+        for (col, value) in row.iter().enumerate() {
+            if COLUMNS[col] == "project_count" {
+                sheet.write_number(line, col as u16, value.parse::<f64>().unwrap_or(0.0))?;
+            } else {
+                sheet.write_string(line, col as u16, value)?;
+            }
+        }
+    }
+    sheet.set_freeze_panes(1, 0)?;
+    sheet.autofilter(
+        0,
+        0,
+        inventory.components.len() as u32,
+        COLUMNS.len() as u16 - 1,
+    )?;
+    sheet.autofit();
+
+    let sheet = workbook.add_worksheet().set_name("Projects")?;
+    for (col, title) in ["id", "name", "team", "scan_id"].iter().enumerate() {
+        sheet.write_string_with_format(0, col as u16, *title, &header)?;
+    }
+    for (index, project) in inventory.projects.iter().enumerate() {
+        let line = index as u32 + 1;
+        sheet.write_number(line, 0, project.id)?;
+        sheet.write_string(line, 1, &project.name)?;
+        sheet.write_string(line, 2, project.team.as_deref().unwrap_or_default())?;
+        sheet.write_number(line, 3, project.scan_id)?;
+    }
+    sheet.autofit();
+
+    Ok(workbook.save_to_buffer()?)
+}
@@ -0,0 +1,259 @@
+use std::fmt::Write;
+
+use quick_xml::escape::escape;
+use serde::Deserialize;
+
+use crate::notice::{Notice, NoticeComponent};
+
+const RULE: &str =
+    "================================================================================";
+const THIN_RULE: &str =
+    "--------------------------------------------------------------------------------";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoticeFormat {
+    #[default]
+    #[serde(alias = "text")]
+    Txt,
+    #[serde(alias = "markdown")]
+    Md,
+    Html,
+}
+
+impl NoticeFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            NoticeFormat::Txt => "text/plain; charset=utf-8",
+            NoticeFormat::Md => "text/markdown; charset=utf-8",
+            NoticeFormat::Html => "text/html; charset=utf-8",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            NoticeFormat::Txt => "txt",
+            NoticeFormat::Md => "md",
+            NoticeFormat::Html => "html",
+        }
+    }
+}
+
+pub fn render(notice: &Notice, format: NoticeFormat) -> String {
+    match format {
+        NoticeFormat::Txt => to_text(notice),
+        NoticeFormat::Md => to_markdown(notice),
+        NoticeFormat::Html => to_html(notice),
+    }
+}
+
+fn title(notice: &Notice) -> String {
+    match &notice.version {
+        Some(version) => format!("{} {version}", notice.project),
+        None => format!("{} (scan {})", notice.project, notice.scan_id),
+    }
+}
+
+fn label(component: &NoticeComponent) -> String {
+    match &component.version {
+        Some(version) => format!("{} {version}", component.name),
+        None => component.name.clone(),
+    }
+}
+
+fn license_label(component: &NoticeComponent) -> &str {
+    component.license.as_deref().unwrap_or("not declared")
+}
+
+pub fn to_text(notice: &Notice) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "THIRD-PARTY SOFTWARE NOTICES\n");
+    let _ = writeln!(out, "{}\n", title(notice));
+    let _ = writeln!(
+        out,
+        "This product includes the following {} third-party components.\n",
+        notice.components.len()
+    );
+
+    for component in &notice.components {
+        let _ = writeln!(out, "{RULE}");
+        let _ = writeln!(out, "{}", label(component));
+        let _ = writeln!(out, "Package URL: {}", component.purl);
+        let _ = writeln!(out, "License: {}", license_label(component));
+        for copyright in &component.copyright {
+            let _ = writeln!(out, "{copyright}");
+        }
+        for file in &component.license_files {
+            let _ = writeln!(out, "\n{THIN_RULE}\n{}\n{THIN_RULE}", file.path);
+            let _ = writeln!(out, "{}", file.text.trim_end());
+        }
+        out.push('\n');
+    }
+
+    if !notice.licenses.is_empty() {
+        let _ = writeln!(out, "{RULE}\nLICENSE TEXTS\n{RULE}\n");
+        for license in &notice.licenses {
+            let _ = writeln!(
+                out,
+                "{THIN_RULE}\n{} ({})\n{THIN_RULE}",
+                license.name, license.id
+            );
+            match &license.text {
+                Some(text) => {
+                    let _ = writeln!(out, "{}\n", text.trim_end());
+                }
+                None => {
+                    let _ = writeln!(
+                        out,
+                        "See the license file distributed with the component.\n"
+                    );
+                }
+            }
+        }
+    }
+    out
+}
+
+pub fn to_markdown(notice: &Notice) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# Third-party software notices\n");
+    let _ = writeln!(out, "{}\n", markdown_escape(&title(notice)));
+    let _ = writeln!(
+        out,
+        "This product includes the following {} third-party components.\n",
+        notice.components.len()
+    );
+
+    for component in &notice.components {
+        let _ = writeln!(out, "## {}\n", markdown_escape(&label(component)));
+        let _ = writeln!(out, "- Package URL: `{}`", component.purl);
+        let license = match &component.license {
+            Some(expression) => format!("`{expression}`"),
+            None => "not declared".to_string(),
+        };
+        let _ = writeln!(out, "- License: {license}");
+        for copyright in &component.copyright {
+            let _ = writeln!(out, "- {}", markdown_escape(copyright));
+        }
+        out.push('\n');
+        for file in &component.license_files {
+            let _ = writeln!(out, "### {}\n", markdown_escape(&file.path));
+            let _ = writeln!(out, "{}\n", fenced(&file.text));
+        }
+    }
+
+    if !notice.licenses.is_empty() {
+        let _ = writeln!(out, "# License texts\n");
+        for license in &notice.licenses {
+            let _ = writeln!(
+                out,
+                "## {} ({})\n",
+                markdown_escape(&license.name),
+                markdown_escape(&license.id)
+            );
+            match &license.text {
+                Some(text) => {
+                    let _ = writeln!(out, "{}\n", fenced(text));
+                }
+                None => {
+                    let _ = writeln!(
+                        out,
+                        "See the license file distributed with the component.\n"
+                    );
+                }
+            }
+        }
+    }
+    out
+}
+
+pub fn to_html(notice: &Notice) -> String {
+    let title = escape(title(notice)).into_owned();
+    let mut out = String::new();
+    let _ = writeln!(out, "<!DOCTYPE html>");
+    let _ = writeln!(out, "<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">");
+    let _ = writeln!(out, "<title>Third-party software notices - {title}</title>");
+    let _ = writeln!(out, "</head>\n<body>");
+    let _ = writeln!(out, "<h1>Third-party software notices</h1>");
+    let _ = writeln!(out, "<p>{title}</p>");
+    let _ = writeln!(
+        out,
+        "<p>This product includes the following {} third-party components.</p>",
+        notice.components.len()
+    );
+
+    for component in &notice.components {
+        let _ = writeln!(out, "<section>");
+        let _ = writeln!(out, "<h2>{}</h2>", escape(label(component)));
+        let _ = writeln!(out, "<ul>");
+        let _ = writeln!(
+            out,
+            "<li>Package URL: <code>{}</code></li>",
+            escape(component.purl.as_str())
+        );
+        let _ = writeln!(
+            out,
+            "<li>License: {}</li>",
+            escape(license_label(component))
+        );
+        for copyright in &component.copyright {
+            let _ = writeln!(out, "<li>{}</li>", escape(copyright.as_str()));
+        }
+        let _ = writeln!(out, "</ul>");
+        for file in &component.license_files {
+            let _ = writeln!(out, "<h3>{}</h3>", escape(file.path.as_str()));
+            let _ = writeln!(out, "<pre>{}</pre>", escape(file.text.trim_end()));
+        }
+        let _ = writeln!(out, "</section>");
+    }
+
+    if !notice.licenses.is_empty() {
+        let _ = writeln!(out, "<h1>License texts</h1>");
+        for license in &notice.licenses {
+            let _ = writeln!(
+                out,
+                "<section id=\"{}\">\n<h2>{} ({})</h2>",
+                escape(license.id.as_str()),
+                escape(license.name.as_str()),
+                escape(license.id.as_str())
+            );
+            match &license.text {
+                Some(text) => {
+                    let _ = writeln!(out, "<pre>{}</pre>", escape(text.trim_end()));
+                }
+                None => {
+                    let _ = writeln!(
+                        out,
+                        "<p>See the license file distributed with the component.</p>"
+                    );
+                }
+            }
+            let _ = writeln!(out, "</section>");
+        }
+    }
+    let _ = writeln!(out, "</body>\n</html>");
+    out
+}
+
+/// A fenced code block long enough not to be closed by backticks in the text.
+fn fenced(text: &str) -> String {
+    let mut fence = "```".to_string();
+    while text.contains(&fence) {
+        fence.push('`');
+    }
+    format!("{fence}text\n{}\n{fence}", text.trim_end())
+}
+
+fn markdown_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|'
+        ) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
@@ -0,0 +1,516 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    io,
+};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use quick_xml::escape::escape;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+use crate::license::license_ids;
+
+pub(super) const TOOL_NAME: &str = env!("CARGO_PKG_NAME");
+pub(super) const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub(super) const CYCLONEDX_SPEC_VERSION: &str = "1.6";
+const CYCLONEDX_XML_NAMESPACE: &str = "http://cyclonedx.org/schema/bom/1.6";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum SbomFormat {
+    #[default]
+    #[serde(rename = "cyclonedx-json", alias = "cyclonedx")]
+    CycloneDxJson,
+    #[serde(rename = "cyclonedx-xml")]
+    CycloneDxXml,
+    #[serde(rename = "spdx-json", alias = "spdx")]
+    SpdxJson,
+}
+
+impl SbomFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            SbomFormat::CycloneDxJson => "application/vnd.cyclonedx+json; version=1.6",
+            SbomFormat::CycloneDxXml => "application/vnd.cyclonedx+xml; version=1.6",
+            SbomFormat::SpdxJson => "application/spdx+json",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            SbomFormat::CycloneDxJson => "cdx.json",
+            SbomFormat::CycloneDxXml => "cdx.xml",
+            SbomFormat::SpdxJson => "spdx.json",
+        }
+    }
+}
+
+/// What the SBOM describes: a project, or the whole organisation for aggregated BOMs.
+#[derive(Debug, Clone)]
+pub struct SbomSubject {
+    pub bom_ref: String,
+    pub name: String,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub purl: Option<String>,
+    pub repo_url: Option<String>,
+    pub homepage: Option<String>,
+    pub properties: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SbomComponent {
+    /// Versioned purl; doubles as the bom-ref.
+    pub purl: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    pub version: Option<String>,
+    pub scope: Option<String>,
+    /// SPDX expression of the declared licenses.
+    pub license: Option<String>,
+    pub properties: Vec<(String, String)>,
+}
+
+/// A bill of materials ready to be rendered in any [`SbomFormat`].
+#[derive(Debug, Clone)]
+pub struct SbomDocument {
+    pub serial_number: Uuid,
+    pub timestamp: DateTime<Utc>,
+    pub subject: SbomSubject,
+    pub components: Vec<SbomComponent>,
+    /// `bom-ref -> bom-refs it depends on`; the subject's ref is the root.
+    pub dependencies: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl SbomDocument {
+    pub fn new(subject: SbomSubject, components: Vec<SbomComponent>) -> Self {
+        SbomDocument {
+            serial_number: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            subject,
+            components,
+            dependencies: BTreeMap::new(),
+        }
+    }
+
+    /// Record `parent -> child` edges; a `None` parent means the subject itself.
+    /// Edges to refs that are not components of this document are ignored.
+    pub fn add_dependencies<'a>(
+        &mut self,
+        edges: impl IntoIterator<Item = (Option<&'a str>, &'a str)>,
+    ) {
+        let known: HashSet<&str> = self.components.iter().map(|c| c.purl.as_str()).collect();
+        for (parent, child) in edges {
+            let parent = parent.unwrap_or(&self.subject.bom_ref);
+            if !known.contains(child) || (parent != self.subject.bom_ref && !known.contains(parent))
+            {
+                continue;
+            }
+            self.dependencies
+                .entry(parent.to_string())
+                .or_default()
+                .insert(child.to_string());
+        }
+    }
+
+    pub fn render(&self, format: SbomFormat) -> String {
+        let mut out = Vec::new();
+        // Writing into memory cannot fail.
+        let _ = self.write(format, &mut out);
+        String::from_utf8(out).unwrap_or_default()
+    }
+
+    /// Write the document to `w`; XML goes out component by component.
+    pub fn write(&self, format: SbomFormat, w: &mut impl io::Write) -> io::Result<()> {
+        match format {
+            SbomFormat::CycloneDxJson => {
+                serde_json::to_writer_pretty(&mut *w, &self.to_cyclonedx_json())?
+            }
+            SbomFormat::CycloneDxXml => self.write_cyclonedx_xml(w)?,
+            SbomFormat::SpdxJson => serde_json::to_writer_pretty(&mut *w, &self.to_spdx_json())?,
+        }
+        Ok(())
+    }
+
+    fn timestamp(&self) -> String {
+        self.timestamp.to_rfc3339_opts(SecondsFormat::Secs, true)
+    }
+
+    /// Every ref with an entry in `dependencies`, including leaves with an empty list.
+    fn dependency_graph(&self) -> BTreeMap<&str, Vec<&str>> {
+        let mut graph: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        graph.insert(&self.subject.bom_ref, Vec::new());
+        for component in &self.components {
+            graph.insert(&component.purl, Vec::new());
+        }
+        for (parent, children) in &self.dependencies {
+            graph
+                .entry(parent)
+                .or_default()
+                .extend(children.iter().map(String::as_str));
+        }
+        graph
+    }
+
+    pub fn to_cyclonedx_json(&self) -> Value {
+        let subject = &self.subject;
+        let mut root = Map::new();
+        root.insert("type".into(), json!("application"));
+        root.insert("bom-ref".into(), json!(subject.bom_ref));
+        root.insert("name".into(), json!(subject.name));
+        insert_opt(&mut root, "version", subject.version.as_deref());
+        insert_opt(&mut root, "description", subject.description.as_deref());
+        insert_opt(&mut root, "purl", subject.purl.as_deref());
+        let references: Vec<Value> = subject_references(subject)
+            .map(|(kind, url)| json!({ "type": kind, "url": url }))
+            .collect();
+        if !references.is_empty() {
+            root.insert("externalReferences".into(), Value::Array(references));
+        }
+
+        let mut metadata = json!({
+            "timestamp": self.timestamp(),
+            "tools": {
+                "components": [{
+                    "type": "application",
+                    "name": TOOL_NAME,
+                    "version": TOOL_VERSION,
+                }]
+            },
+            "component": root,
+        });
+        if !subject.properties.is_empty() {
+            metadata["properties"] = json_properties(&subject.properties);
+        }
+
+        let components: Vec<Value> = self
+            .components
+            .iter()
+            .map(|component| {
+                let mut value = Map::new();
+                value.insert("type".into(), json!("library"));
+                value.insert("bom-ref".into(), json!(component.purl));
+                insert_opt(&mut value, "group", component.namespace.as_deref());
+                value.insert("name".into(), json!(component.name));
+                insert_opt(&mut value, "version", component.version.as_deref());
+                insert_opt(&mut value, "scope", cyclonedx_scope(component));
+                if let Some(license) = &component.license {
+                    value.insert("licenses".into(), json!([{ "expression": license }]));
+                }
+                value.insert("purl".into(), json!(component.purl));
+                if !component.properties.is_empty() {
+                    value.insert("properties".into(), json_properties(&component.properties));
+                }
+                Value::Object(value)
+            })
+            .collect();
+
+        let dependencies: Vec<Value> = self
+            .dependency_graph()
+            .into_iter()
+            .map(|(reference, depends_on)| json!({ "ref": reference, "dependsOn": depends_on }))
+            .collect();
+
+        json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": CYCLONEDX_SPEC_VERSION,
+            "serialNumber": self.serial_number.urn().to_string(),
+            "version": 1,
+            "metadata": metadata,
+            "components": components,
+            "dependencies": dependencies,
+        })
+    }
+
+    /// Write CycloneDX XML, handing each component and dependency to `w` as soon as it is
+    /// rendered.
+    fn write_cyclonedx_xml(&self, w: &mut impl io::Write) -> io::Result<()> {
+        let subject = &self.subject;
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str(&format!(
+            "<bom xmlns=\"{CYCLONEDX_XML_NAMESPACE}\" serialNumber=\"{}\" version=\"1\">\n",
+            self.serial_number.urn()
+        ));
+
+        out.push_str("  <metadata>\n");
+        element(&mut out, 4, "timestamp", &self.timestamp());
+        out.push_str("    <tools>\n      <components>\n");
+        out.push_str("        <component type=\"application\">\n");
+        element(&mut out, 10, "name", TOOL_NAME);
+        element(&mut out, 10, "version", TOOL_VERSION);
+        out.push_str("        </component>\n      </components>\n    </tools>\n");
+        out.push_str(&format!(
+            "    <component type=\"application\" bom-ref=\"{}\">\n",
+            escape(subject.bom_ref.as_str())
+        ));
+        element(&mut out, 6, "name", &subject.name);
+        element_opt(&mut out, 6, "version", subject.version.as_deref());
+        element_opt(&mut out, 6, "description", subject.description.as_deref());
+        element_opt(&mut out, 6, "purl", subject.purl.as_deref());
+        let references: Vec<_> = subject_references(subject).collect();
+        if !references.is_empty() {
+            out.push_str("      <externalReferences>\n");
+            for (kind, url) in references {
+                out.push_str(&format!("        <reference type=\"{kind}\">\n"));
+                element(&mut out, 10, "url", url);
+                out.push_str("        </reference>\n");
+            }
+            out.push_str("      </externalReferences>\n");
+        }
+        out.push_str("    </component>\n");
+        xml_properties(&mut out, 4, &subject.properties);
+        out.push_str("  </metadata>\n");
+
+        out.push_str("  <components>\n");
+        flush(w, &mut out)?;
+        for component in &self.components {
+            out.push_str(&format!(
+                "    <component type=\"library\" bom-ref=\"{}\">\n",
+                escape(component.purl.as_str())
+            ));
+            element_opt(&mut out, 6, "group", component.namespace.as_deref());
+            element(&mut out, 6, "name", &component.name);
+            element_opt(&mut out, 6, "version", component.version.as_deref());
+            element_opt(&mut out, 6, "scope", cyclonedx_scope(component));
+            if let Some(license) = &component.license {
+                out.push_str("      <licenses>\n");
+                element(&mut out, 8, "expression", license);
+                out.push_str("      </licenses>\n");
+            }
+            element(&mut out, 6, "purl", &component.purl);
+            xml_properties(&mut out, 6, &component.properties);
+            out.push_str("    </component>\n");
+            flush(w, &mut out)?;
+        }
+        out.push_str("  </components>\n");
+
+        out.push_str("  <dependencies>\n");
+        for (reference, depends_on) in self.dependency_graph() {
+            if depends_on.is_empty() {
+                out.push_str(&format!(
+                    "    <dependency ref=\"{}\"/>\n",
+                    escape(reference)
+                ));
+                flush(w, &mut out)?;
+                continue;
+            }
+            out.push_str(&format!("    <dependency ref=\"{}\">\n", escape(reference)));
+            for child in depends_on {
+                out.push_str(&format!("      <dependency ref=\"{}\"/>\n", escape(child)));
+            }
+            out.push_str("    </dependency>\n");
+            flush(w, &mut out)?;
+        }
+        out.push_str("  </dependencies>\n</bom>\n");
+        flush(w, &mut out)
+    }
+
+    pub fn to_spdx_json(&self) -> Value {
+        let subject = &self.subject;
+        // SPDX ids only allow letters, digits, `.` and `-`, so purls cannot be used directly.
+        let mut ids: HashMap<&str, String> = HashMap::new();
+        ids.insert(&subject.bom_ref, "SPDXRef-Package-root".to_string());
+        for (index, component) in self.components.iter().enumerate() {
+            ids.insert(&component.purl, format!("SPDXRef-Package-{}", index + 1));
+        }
+
+        let mut root = json!({
+            "SPDXID": ids[subject.bom_ref.as_str()],
+            "name": subject.name,
+            "downloadLocation": subject.repo_url.as_deref().unwrap_or("NOASSERTION"),
+            "filesAnalyzed": false,
+            "licenseConcluded": "NOASSERTION",
+            "licenseDeclared": "NOASSERTION",
+            "copyrightText": "NOASSERTION",
+            "primaryPackagePurpose": "APPLICATION",
+        });
+        if let Some(version) = &subject.version {
+            root["versionInfo"] = json!(version);
+        }
+        if let Some(description) = &subject.description {
+            root["description"] = json!(description);
+        }
+        if let Some(homepage) = &subject.homepage {
+            root["homepage"] = json!(homepage);
+        }
+        if let Some(purl) = &subject.purl {
+            root["externalRefs"] = json!([spdx_purl_ref(purl)]);
+        }
+
+        let mut packages = vec![root];
+        packages.extend(self.components.iter().map(|component| {
+            let name = match &component.namespace {
+                Some(namespace) => format!("{namespace}/{}", component.name),
+                None => component.name.clone(),
+            };
+            let mut package = json!({
+                "SPDXID": ids[component.purl.as_str()],
+                "name": name,
+                "downloadLocation": "NOASSERTION",
+                "filesAnalyzed": false,
+                "licenseConcluded": "NOASSERTION",
+                "licenseDeclared": component.license.as_deref().unwrap_or("NOASSERTION"),
+                "copyrightText": "NOASSERTION",
+                "primaryPackagePurpose": "LIBRARY",
+                "externalRefs": [spdx_purl_ref(&component.purl)],
+            });
+            if let Some(version) = &component.version {
+                package["versionInfo"] = json!(version);
+            }
+            if !component.properties.is_empty() {
+                let comment: Vec<String> = component
+                    .properties
+                    .iter()
+                    .map(|(name, value)| format!("{name}: {value}"))
+                    .collect();
+                package["comment"] = json!(comment.join("\n"));
+            }
+            package
+        }));
+
+        // Every LicenseRef used in an expression has to be defined in the document.
+        let license_refs: BTreeSet<String> = self
+            .components
+            .iter()
+            .filter_map(|component| component.license.as_deref())
+            .flat_map(license_ids)
+            .filter(|id| id.starts_with("LicenseRef-"))
+            .collect();
+        let extracted_licenses: Vec<Value> = license_refs
+            .iter()
+            .map(|id| {
+                json!({
+                    "licenseId": id,
+                    "name": id.trim_start_matches("LicenseRef-").replace('-', " "),
+                    "extractedText": "NOASSERTION",
+                })
+            })
+            .collect();
+
+        let mut relationships = vec![json!({
+            "spdxElementId": "SPDXRef-DOCUMENT",
+            "relationshipType": "DESCRIBES",
+            "relatedSpdxElement": ids[subject.bom_ref.as_str()],
+        })];
+        for (parent, children) in &self.dependencies {
+            for child in children {
+                relationships.push(json!({
+                    "spdxElementId": ids[parent.as_str()],
+                    "relationshipType": "DEPENDS_ON",
+                    "relatedSpdxElement": ids[child.as_str()],
+                }));
+            }
+        }
+
+        let mut document = json!({
+            "spdxVersion": "SPDX-2.3",
+            "dataLicense": "CC0-1.0",
+            "SPDXID": "SPDXRef-DOCUMENT",
+            "name": subject.name,
+            "documentNamespace": format!(
+                "https://spdx.org/spdxdocs/{}-{}",
+                spdx_slug(&subject.name),
+                self.serial_number
+            ),
+            "creationInfo": {
+                "created": self.timestamp(),
+                "creators": [format!("Tool: {TOOL_NAME}-{TOOL_VERSION}")],
+            },
+            "documentDescribes": [ids[subject.bom_ref.as_str()]],
+            "packages": packages,
+            "relationships": relationships,
+        });
+        if !extracted_licenses.is_empty() {
+            document["hasExtractedLicensingInfos"] = json!(extracted_licenses);
+        }
+        document
+    }
+}
+
+/// Only the three scopes CycloneDX defines are emitted; anything else is dropped.
+fn cyclonedx_scope(component: &SbomComponent) -> Option<&str> {
+    component
+        .scope
+        .as_deref()
+        .filter(|scope| matches!(*scope, "required" | "optional" | "excluded"))
+}
+
+fn subject_references(subject: &SbomSubject) -> impl Iterator<Item = (&'static str, &str)> {
+    [
+        ("vcs", subject.repo_url.as_deref()),
+        ("website", subject.homepage.as_deref()),
+    ]
+    .into_iter()
+    .filter_map(|(kind, url)| url.map(|url| (kind, url)))
+}
+
+fn insert_opt(map: &mut Map<String, Value>, key: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        map.insert(key.into(), json!(value));
+    }
+}
+
+fn json_properties(properties: &[(String, String)]) -> Value {
+    properties
+        .iter()
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect()
+}
+
+/// Hand what has been rendered so far to `w`.
+fn flush(w: &mut impl io::Write, out: &mut String) -> io::Result<()> {
+    w.write_all(out.as_bytes())?;
+    out.clear();
+    Ok(())
+}
+
+fn element(out: &mut String, indent: usize, name: &str, value: &str) {
+    out.push_str(&format!(
+        "{:indent$}<{name}>{}</{name}>\n",
+        "",
+        escape(value)
+    ));
+}
+
+fn element_opt(out: &mut String, indent: usize, name: &str, value: Option<&str>) {
+    if let Some(value) = value {
+        element(out, indent, name, value);
+    }
+}
+
+fn xml_properties(out: &mut String, indent: usize, properties: &[(String, String)]) {
+    if properties.is_empty() {
+        return;
+    }
+    out.push_str(&format!("{:indent$}<properties>\n", ""));
+    for (name, value) in properties {
+        out.push_str(&format!(
+            "{:indent$}  <property name=\"{}\">{}</property>\n",
+            "",
+            escape(name.as_str()),
+            escape(value.as_str())
+        ));
+    }
+    out.push_str(&format!("{:indent$}</properties>\n", ""));
+}
+
+fn spdx_purl_ref(purl: &str) -> Value {
+    json!({
+        "referenceCategory": "PACKAGE-MANAGER",
+        "referenceType": "purl",
+        "referenceLocator": purl,
+    })
+}
+
+/// `name` reduced to letters, digits, `.` and `-`, as SPDX ids and file names allow.
+pub(crate) fn spdx_slug(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,302 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use uuid::Uuid;
+
+use super::sbom::{CYCLONEDX_SPEC_VERSION, TOOL_NAME, TOOL_VERSION};
+use crate::triage::{cyclonedx_justification, openvex_justification, AnalysisState};
+
+const OPENVEX_CONTEXT: &str = "https://openvex.dev/ns/v0.2.0";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VexFormat {
+    #[default]
+    #[serde(rename = "cyclonedx")]
+    CycloneDx,
+    #[serde(rename = "openvex")]
+    OpenVex,
+}
+
+impl VexFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            VexFormat::CycloneDx => "application/vnd.cyclonedx+json; version=1.6",
+            VexFormat::OpenVex => "application/json",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            VexFormat::CycloneDx => "vex.cdx.json",
+            VexFormat::OpenVex => "openvex.json",
+        }
+    }
+
+    /// Value of the `source` column of analysis events written by an import.
+    pub fn event_source(self) -> &'static str {
+        match self {
+            VexFormat::CycloneDx => "cyclonedx-vex",
+            VexFormat::OpenVex => "openvex",
+        }
+    }
+}
+
+/// The project the VEX speaks for.
+#[derive(Debug, Clone)]
+pub struct VexSubject {
+    pub bom_ref: String,
+    pub name: String,
+    pub purl: Option<String>,
+    /// Published as the OpenVEX document author.
+    pub author: String,
+}
+
+/// One advisory on one component, with the project's analysis when there is one.
+#[derive(Debug, Clone)]
+pub struct VexEntry {
+    pub vulnerability: String,
+    /// `osv` or `nvd`.
+    pub source: String,
+    pub aliases: Vec<String>,
+    pub severity: Option<String>,
+    pub cvss_score: Option<f64>,
+    pub cvss_vector: Option<String>,
+    /// Versioned purl of the component.
+    pub purl: String,
+    pub analysis: Option<VexAnalysis>,
+}
+
+#[derive(Debug, Clone)]
+pub struct VexAnalysis {
+    pub state: AnalysisState,
+    pub justification: Option<String>,
+    pub detail: Option<String>,
+    pub first_issued: DateTime<Utc>,
+    pub last_updated: DateTime<Utc>,
+}
+
+pub fn render(format: VexFormat, subject: &VexSubject, entries: &[VexEntry]) -> Value {
+    match format {
+        VexFormat::CycloneDx => to_cyclonedx(subject, entries),
+        VexFormat::OpenVex => to_openvex(subject, entries),
+    }
+}
+
+/// A CycloneDX 1.6 BOM holding only the affected components and one vulnerability per
+/// advisory and component. Unanalysed findings carry no `analysis`.
+pub fn to_cyclonedx(subject: &VexSubject, entries: &[VexEntry]) -> Value {
+    let mut root = Map::new();
+    root.insert("type".into(), json!("application"));
+    root.insert("bom-ref".into(), json!(subject.bom_ref));
+    root.insert("name".into(), json!(subject.name));
+    if let Some(purl) = &subject.purl {
+        root.insert("purl".into(), json!(purl));
+    }
+
+    let mut purls: Vec<&str> = entries.iter().map(|entry| entry.purl.as_str()).collect();
+    purls.sort_unstable();
+    purls.dedup();
+    let components: Vec<Value> = purls
+        .iter()
+        .map(|purl| json!({ "type": "library", "bom-ref": purl, "name": purl, "purl": purl }))
+        .collect();
+
+    let vulnerabilities: Vec<Value> = entries
+        .iter()
+        .map(|entry| {
+            let mut value = Map::new();
+            value.insert(
+                "bom-ref".into(),
+                json!(format!("{}@{}", entry.vulnerability, entry.purl)),
+            );
+            value.insert("id".into(), json!(entry.vulnerability));
+            value.insert("source".into(), advisory_source(entry));
+            if !entry.aliases.is_empty() {
+                let references: Vec<Value> = entry
+                    .aliases
+                    .iter()
+                    .map(|alias| json!({ "id": alias, "source": { "name": alias_source(alias) } }))
+                    .collect();
+                value.insert("references".into(), Value::Array(references));
+            }
+            if let Some(rating) = rating(entry) {
+                value.insert("ratings".into(), json!([rating]));
+            }
+            if let Some(analysis) = &entry.analysis {
+                let mut object = Map::new();
+                object.insert("state".into(), json!(analysis.state.to_cyclonedx()));
+                if let Some(justification) = analysis
+                    .justification
+                    .as_deref()
+                    .and_then(cyclonedx_justification)
+                {
+                    object.insert("justification".into(), json!(justification));
+                }
+                if let Some(detail) = &analysis.detail {
+                    object.insert("detail".into(), json!(detail));
+                }
+                object.insert(
+                    "firstIssued".into(),
+                    json!(timestamp(analysis.first_issued)),
+                );
+                object.insert(
+                    "lastUpdated".into(),
+                    json!(timestamp(analysis.last_updated)),
+                );
+                value.insert("analysis".into(), Value::Object(object));
+            }
+            value.insert("affects".into(), json!([{ "ref": entry.purl }]));
+            Value::Object(value)
+        })
+        .collect();
+
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": CYCLONEDX_SPEC_VERSION,
+        "serialNumber": Uuid::new_v4().urn().to_string(),
+        "version": 1,
+        "metadata": {
+            "timestamp": timestamp(Utc::now()),
+            "tools": {
+                "components": [{
+                    "type": "application",
+                    "name": TOOL_NAME,
+                    "version": TOOL_VERSION,
+                }]
+            },
+            "component": root,
+        },
+        "components": components,
+        "vulnerabilities": vulnerabilities,
+    })
+}
+
+/// An OpenVEX v0.2.0 document. Unanalysed findings are published as under
+/// investigation; the project purl, when set, is the product and each component a
+/// subcomponent of it.
+pub fn to_openvex(subject: &VexSubject, entries: &[VexEntry]) -> Value {
+    let now = Utc::now();
+    let statements: Vec<Value> = entries
+        .iter()
+        .map(|entry| {
+            let state = entry
+                .analysis
+                .as_ref()
+                .map_or(AnalysisState::UnderInvestigation, |analysis| analysis.state);
+            let detail = entry
+                .analysis
+                .as_ref()
+                .and_then(|analysis| analysis.detail.clone());
+
+            let mut vulnerability = Map::new();
+            vulnerability.insert("name".into(), json!(entry.vulnerability));
+            if !entry.aliases.is_empty() {
+                vulnerability.insert("aliases".into(), json!(entry.aliases));
+            }
+            let product = match &subject.purl {
+                Some(purl) => json!({ "@id": purl, "subcomponents": [{ "@id": entry.purl }] }),
+                None => json!({ "@id": entry.purl }),
+            };
+
+            let mut statement = Map::new();
+            statement.insert("vulnerability".into(), Value::Object(vulnerability));
+            statement.insert("products".into(), json!([product]));
+            statement.insert("status".into(), json!(state.to_openvex()));
+            match state {
+                AnalysisState::NotAffected => {
+                    if let Some(justification) = entry
+                        .analysis
+                        .as_ref()
+                        .and_then(|analysis| analysis.justification.as_deref())
+                        .and_then(openvex_justification)
+                    {
+                        statement.insert("justification".into(), json!(justification));
+                    }
+                    if let Some(detail) = detail {
+                        statement.insert("impact_statement".into(), json!(detail));
+                    }
+                }
+                AnalysisState::FalsePositive => {
+                    statement.insert("justification".into(), json!("vulnerable_code_not_present"));
+                    let note = match detail {
+                        Some(detail) => format!("False positive: {detail}"),
+                        None => "False positive".to_string(),
+                    };
+                    statement.insert("impact_statement".into(), json!(note));
+                }
+                AnalysisState::Affected => {
+                    let action = detail.unwrap_or_else(|| {
+                        "Upgrade to a version that is no longer affected.".to_string()
+                    });
+                    statement.insert("action_statement".into(), json!(action));
+                }
+                AnalysisState::Fixed | AnalysisState::UnderInvestigation => {
+                    if let Some(detail) = detail {
+                        statement.insert("status_notes".into(), json!(detail));
+                    }
+                }
+            }
+            if let Some(analysis) = &entry.analysis {
+                statement.insert("timestamp".into(), json!(timestamp(analysis.last_updated)));
+            }
+            Value::Object(statement)
+        })
+        .collect();
+
+    json!({
+        "@context": OPENVEX_CONTEXT,
+        "@id": Uuid::new_v4().urn().to_string(),
+        "author": subject.author,
+        "timestamp": timestamp(now),
+        "version": 1,
+        "tooling": format!("{TOOL_NAME} {TOOL_VERSION}"),
+        "statements": statements,
+    })
+}
+
+fn timestamp(value: DateTime<Utc>) -> String {
+    value.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn advisory_source(entry: &VexEntry) -> Value {
+    match entry.source.as_str() {
+        "nvd" => json!({
+            "name": "NVD",
+            "url": format!("https://nvd.nist.gov/vuln/detail/{}", entry.vulnerability),
+        }),
+        _ => json!({
+            "name": "OSV",
+            "url": format!("https://osv.dev/vulnerability/{}", entry.vulnerability),
+        }),
+    }
+}
+
+fn alias_source(alias: &str) -> &'static str {
+    if alias.starts_with("CVE-") {
+        "NVD"
+    } else if alias.starts_with("GHSA-") {
+        "GitHub"
+    } else {
+        "OSV"
+    }
+}
+
+fn rating(entry: &VexEntry) -> Option<Value> {
+    let mut rating = Map::new();
+    if let Some(score) = entry.cvss_score {
+        rating.insert("score".into(), json!(score));
+    }
+    if let Some(severity) = &entry.severity {
+        rating.insert("severity".into(), json!(severity));
+    }
+    if let Some(vector) = &entry.cvss_vector {
+        let method = if vector.starts_with("CVSS:3.1") {
+            "CVSSv31"
+        } else {
+            "CVSSv3"
+        };
+        rating.insert("method".into(), json!(method));
+        rating.insert("vector".into(), json!(vector));
+    }
+    (!rating.is_empty()).then_some(Value::Object(rating))
+}
@@ -0,0 +1,239 @@
+//! How far the components of a scan are behind their registries.
+//!
+//! For every component with an imported release history this reports the latest stable
+//! release (no pre-releases, nothing yanked), how many stable releases are newer than the
+//! one in use, and its libyear: the time between the release in use and the latest
+//! stable one, in years. Libyears need both release dates, which Maven metadata and
+//! older crates.io index entries do not carry.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseBackend, DatabaseConnection, DbErr, FromQueryResult, Statement, Value};
+use serde::Serialize;
+
+use crate::version::{compare, is_prerelease, Ecosystem};
+
+const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 3600.0;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentFreshness {
+    pub purl: String,
+    pub package_purl: String,
+    pub version: Option<String>,
+    pub latest_stable: Option<String>,
+    /// Stable releases newer than the one in use.
+    pub versions_behind: Option<usize>,
+    pub libyears: Option<f64>,
+    pub released_at: Option<DateTime<Utc>>,
+    pub latest_released_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FreshnessSummary {
+    pub components: usize,
+    /// Components with an imported release history.
+    pub tracked: usize,
+    /// Components with at least one newer stable release.
+    pub outdated: usize,
+    /// Components whose libyears are known, i.e. whose release dates were imported.
+    pub measured: usize,
+    pub versions_behind: usize,
+    pub libyears: f64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ComponentRow {
+    scan_id: i32,
+    purl: String,
+    package_id: i32,
+    package_purl: String,
+    purl_type: String,
+    version: Option<String>,
+}
+
+#[derive(Debug, Clone, FromQueryResult)]
+struct ReleaseRow {
+    package_id: i32,
+    version: String,
+    published_at: Option<DateTime<Utc>>,
+    yanked: bool,
+}
+
+/// Freshness of every component of a scan, most libyears behind first.
+pub async fn scan_freshness(
+    db: &DatabaseConnection,
+    scan_id: i32,
+) -> Result<Vec<ComponentFreshness>, DbErr> {
+    let mut items = load_freshness(db, &[scan_id])
+        .await?
+        .remove(&scan_id)
+        .unwrap_or_default();
+    items.sort_by(|a, b| {
+        b.libyears
+            .unwrap_or(0.0)
+            .total_cmp(&a.libyears.unwrap_or(0.0))
+            .then_with(|| b.versions_behind.cmp(&a.versions_behind))
+            .then_with(|| a.purl.cmp(&b.purl))
+    });
+    Ok(items)
+}
+
+/// Freshness summary of each of the given scans, keyed by scan id. Scans without
+/// components are absent.
+pub async fn scan_summaries(
+    db: &DatabaseConnection,
+    scan_ids: &[i32],
+) -> Result<HashMap<i32, FreshnessSummary>, DbErr> {
+    Ok(load_freshness(db, scan_ids)
+        .await?
+        .into_iter()
+        .map(|(scan_id, items)| (scan_id, summarize(&items)))
+        .collect())
+}
+
+/// Components of the given scans with their freshness, grouped by scan. Components and
+/// release histories are each read in a single query.
+async fn load_freshness(
+    db: &DatabaseConnection,
+    scan_ids: &[i32],
+) -> Result<HashMap<i32, Vec<ComponentFreshness>>, DbErr> {
+    if scan_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let placeholders = (1..=scan_ids.len())
+        .map(|index| format!("${index}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let values: Vec<Value> = scan_ids.iter().map(|&scan_id| scan_id.into()).collect();
+
+    let components = ComponentRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT DISTINCT ON (d.scan_id, COALESCE(v.purl, p.purl)) \
+                    d.scan_id, COALESCE(v.purl, p.purl) AS purl, p.id AS package_id, \
+                    p.purl AS package_purl, p.purl_type, \
+                    COALESCE(v.version, d.resolved_version) AS version \
+             FROM direct_dependencies d \
+             JOIN packages p ON p.id = d.package_id \
+             LEFT JOIN package_versions v ON v.id = d.package_version_id \
+             WHERE d.scan_id IN ({placeholders}) \
+             ORDER BY d.scan_id, COALESCE(v.purl, p.purl), d.id"
+        ),
+        values.clone(),
+    ))
+    .all(db)
+    .await?;
+
+    let mut releases: HashMap<i32, Vec<ReleaseRow>> = HashMap::new();
+    for row in ReleaseRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT r.package_id, r.version, r.published_at, r.yanked \
+             FROM registry_versions r \
+             WHERE r.package_id IN (SELECT package_id FROM direct_dependencies \
+                                    WHERE scan_id IN ({placeholders}))"
+        ),
+        values,
+    ))
+    .all(db)
+    .await?
+    {
+        releases.entry(row.package_id).or_default().push(row);
+    }
+
+    let mut items: HashMap<i32, Vec<ComponentFreshness>> = HashMap::new();
+    for component in components {
+        let history = releases
+            .get(&component.package_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        items
+            .entry(component.scan_id)
+            .or_default()
+            .push(assess(component, history));
+    }
+    Ok(items)
+}
+
+pub fn summarize(items: &[ComponentFreshness]) -> FreshnessSummary {
+    let mut summary = FreshnessSummary {
+        components: items.len(),
+        ..Default::default()
+    };
+    for item in items {
+        if item.latest_stable.is_some() {
+            summary.tracked += 1;
+        }
+        if let Some(behind) = item.versions_behind.filter(|behind| *behind > 0) {
+            summary.outdated += 1;
+            summary.versions_behind += behind;
+        }
+        if let Some(libyears) = item.libyears {
+            summary.measured += 1;
+            summary.libyears += libyears;
+        }
+    }
+    summary.libyears = round(summary.libyears);
+    summary
+}
+
+fn assess(component: ComponentRow, history: &[ReleaseRow]) -> ComponentFreshness {
+    let ecosystem = Ecosystem::from_purl_type(&component.purl_type);
+    let stable: Vec<&ReleaseRow> = history
+        .iter()
+        .filter(|release| !release.yanked && !is_prerelease(ecosystem, &release.version))
+        .collect();
+    let latest = stable
+        .iter()
+        .copied()
+        .max_by(|a, b| compare(ecosystem, &a.version, &b.version));
+
+    let current = component.version.as_deref().and_then(|version| {
+        history
+            .iter()
+            .find(|release| release.version == version)
+            .or_else(|| {
+                history
+                    .iter()
+                    .find(|release| compare(ecosystem, &release.version, version).is_eq())
+            })
+    });
+
+    let versions_behind = match (latest, component.version.as_deref()) {
+        (Some(_), Some(version)) => Some(
+            stable
+                .iter()
+                .filter(|release| compare(ecosystem, &release.version, version).is_gt())
+                .count(),
+        ),
+        _ => None,
+    };
+    let libyears = match (versions_behind, latest, current) {
+        (Some(0), _, _) => Some(0.0),
+        (Some(_), Some(latest), Some(current)) => {
+            match (current.published_at, latest.published_at) {
+                (Some(from), Some(to)) => Some(round(
+                    ((to - from).num_seconds().max(0) as f64) / SECONDS_PER_YEAR,
+                )),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+
+    ComponentFreshness {
+        purl: component.purl,
+        package_purl: component.package_purl,
+        version: component.version,
+        latest_stable: latest.map(|release| release.version.clone()),
+        versions_behind,
+        libyears,
+        released_at: current.and_then(|release| release.published_at),
+        latest_released_at: latest.and_then(|release| release.published_at),
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
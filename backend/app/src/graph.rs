@@ -0,0 +1,180 @@
+pub mod neo4j;
+pub mod postgres;
+
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+
+use crate::config::graph::RetentionMode;
+
+pub use self::neo4j::Neo4jGraphStore;
+pub use self::postgres::PostgresGraphStore;
+
+/// Upper bound for any transitive walk, regardless of what the caller asks for.
+pub const MAX_TRAVERSAL_DEPTH: u32 = 16;
+
+/// Storage backend for the dependency graph of every scan.
+///
+/// Neo4j is used when `neo4j.enabled=true`; otherwise the same queries run as
+/// recursive CTEs over the `dependency_edges` table in Postgres. Queries that take
+/// an optional scan id fall back to the project's latest scan.
+#[async_trait]
+pub trait GraphStore: Send + Sync {
+    /// Short backend name used in logs.
+    fn backend(&self) -> &'static str;
+
+    /// Persist the nodes and edges produced by one scan and mark it as the latest.
+    async fn sync_scan(&self, graph: &ScanGraph) -> anyhow::Result<()>;
+
+    /// Remove every node, edge and snapshot that belongs to the project.
+    async fn delete_project(&self, project_id: i32) -> anyhow::Result<()>;
+
+    /// Latest scan recorded for the project, if any.
+    async fn latest_scan_id(&self, project_id: i32) -> anyhow::Result<Option<i32>>;
+
+    /// Archive or prune the edges of every superseded scan beyond the newest `keep`.
+    async fn apply_retention(
+        &self,
+        project_id: i32,
+        keep: u32,
+        mode: RetentionMode,
+    ) -> anyhow::Result<()>;
+
+    /// Every edge reachable from the project root within `max_depth` hops.
+    async fn dependency_tree(
+        &self,
+        project_id: i32,
+        scan_id: Option<i32>,
+        max_depth: u32,
+    ) -> anyhow::Result<Vec<TreeEdge>>;
+
+    /// All shortest paths from the project root to `purl`.
+    ///
+    /// `purl` may carry a version to target one release, or omit it to match any version.
+    async fn dependency_paths(
+        &self,
+        project_id: i32,
+        scan_id: Option<i32>,
+        purl: &str,
+        max_depth: u32,
+    ) -> anyhow::Result<Vec<DependencyPath>>;
+
+    /// [`dependency_paths`](Self::dependency_paths) for several purls in one query, keyed by
+    /// the requested purl. Purls the root does not reach are left out.
+    async fn dependency_paths_batch(
+        &self,
+        project_id: i32,
+        scan_id: Option<i32>,
+        purls: &[String],
+        max_depth: u32,
+    ) -> anyhow::Result<HashMap<String, Vec<DependencyPath>>>;
+
+    /// Latest scans whose root reaches any of `purls` (versioned or not) within
+    /// `max_depth` hops.
+    async fn reverse_dependents(
+        &self,
+        purls: &[String],
+        max_depth: u32,
+    ) -> anyhow::Result<Vec<Dependent>>;
+}
+
+pub type SharedGraphStore = Arc<dyn GraphStore>;
+
+/// Picks the graph backend from configuration.
+pub async fn init(db: DatabaseConnection) -> anyhow::Result<SharedGraphStore> {
+    match crate::neo4j::init().await? {
+        Some(graph) => Ok(Arc::new(Neo4jGraphStore::new(graph))),
+        None => {
+            tracing::info!("Using Postgres dependency-edge table as graph store");
+            Ok(Arc::new(PostgresGraphStore::new(db)))
+        }
+    }
+}
+
+pub(crate) fn clamp_depth(max_depth: u32) -> u32 {
+    max_depth.clamp(1, MAX_TRAVERSAL_DEPTH)
+}
+
+/// The requested scan, or the project's latest one when none was given.
+pub(crate) async fn resolve_scan<S: GraphStore + ?Sized>(
+    store: &S,
+    project_id: i32,
+    scan_id: Option<i32>,
+) -> anyhow::Result<Option<i32>> {
+    match scan_id {
+        Some(id) => Ok(Some(id)),
+        None => store.latest_scan_id(project_id).await,
+    }
+}
+
+/// Everything a backend needs to record one scan.
+#[derive(Debug, Clone)]
+pub struct ScanGraph {
+    pub project: ProjectNode,
+    pub scan_id: i32,
+    pub packages: Vec<PackageNode>,
+    pub edges: Vec<DependencyEdge>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProjectNode {
+    pub id: i32,
+    pub name: String,
+    pub repo_url: Option<String>,
+    pub package_manager: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PackageNode {
+    pub package_id: i32,
+    pub version_id: i32,
+    /// Versioned purl as reported by the SBOM; identifies the node in the graph.
+    pub purl: String,
+    /// Versionless purl shared by every version of the package.
+    pub package_purl: String,
+    pub purl_type: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    pub version: Option<String>,
+    pub scope: Option<String>,
+    pub bom_ref: Option<String>,
+}
+
+/// Edge between two versioned purls of the same scan; `parent == None` is the project root.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DependencyEdge {
+    pub parent: Option<String>,
+    pub child: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TreeEdge {
+    pub parent: Option<String>,
+    pub purl: String,
+    pub version: Option<String>,
+    pub scope: Option<String>,
+    pub depth: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PathHop {
+    pub purl: String,
+    pub version: Option<String>,
+    pub scope: Option<String>,
+}
+
+/// Hops from the first direct dependency down to the target package.
+pub type DependencyPath = Vec<PathHop>;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Dependent {
+    pub project_id: i32,
+    pub scan_id: i32,
+    pub depth: u32,
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.is_empty())
+}
@@ -0,0 +1,503 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use neo4rs::{query, Graph};
+
+use super::{
+    clamp_depth, non_empty, resolve_scan, DependencyPath, Dependent, GraphStore, PathHop,
+    ScanGraph, TreeEdge,
+};
+use crate::config::graph::RetentionMode;
+
+/// Graph store backed by Neo4j.
+///
+/// Each `(:Package)` groups its releases through `[:HAS_VERSION]->(:PackageVersion)`.
+/// `(:Project)-[:DEPENDS_ON]->(:PackageVersion)` holds direct dependencies and
+/// `(:PackageVersion)-[:DEPENDS_ON]->(:PackageVersion)` the transitive ones; every
+/// edge carries the `scan_id` it was observed in.
+///
+/// Each scan is also snapshotted as `(:Project)-[:HAS_SCAN]->(:Scan)-[:INCLUDES]->(:PackageVersion)`,
+/// with `latest: true` on the project's newest scan. Retention in archive mode turns the
+/// edges of superseded scans into `ARCHIVED_DEPENDS_ON`, which no query follows.
+#[derive(Clone)]
+pub struct Neo4jGraphStore {
+    graph: Graph,
+}
+
+impl Neo4jGraphStore {
+    pub fn new(graph: Graph) -> Self {
+        Self { graph }
+    }
+
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+}
+
+#[async_trait]
+impl GraphStore for Neo4jGraphStore {
+    fn backend(&self) -> &'static str {
+        "neo4j"
+    }
+
+    async fn sync_scan(&self, snapshot: &ScanGraph) -> anyhow::Result<()> {
+        let project = &snapshot.project;
+        let mut tx = self.graph.start_txn().await?;
+
+        tx.run(
+            query(
+                "MERGE (p:Project {id: $project_id}) \
+                 SET p.name = $name, \
+                     p.repo_url = $repo_url, \
+                     p.package_manager = $package_manager, \
+                     p.updated_at = datetime()",
+            )
+            .param("project_id", project.id as i64)
+            .param("name", project.name.as_str())
+            .param("repo_url", project.repo_url.clone().unwrap_or_default())
+            .param(
+                "package_manager",
+                project.package_manager.clone().unwrap_or_default(),
+            ),
+        )
+        .await?;
+
+        tx.run(
+            query(
+                "MATCH (p:Project {id: $project_id}) \
+                 OPTIONAL MATCH (p)-[:HAS_SCAN]->(old:Scan) \
+                 SET old.latest = false \
+                 WITH DISTINCT p \
+                 MERGE (s:Scan {id: $scan_id}) \
+                 ON CREATE SET s.created_at = datetime() \
+                 SET s.project_id = $project_id, \
+                     s.latest = true, \
+                     s.archived = false \
+                 MERGE (p)-[:HAS_SCAN]->(s)",
+            )
+            .param("project_id", project.id as i64)
+            .param("scan_id", snapshot.scan_id as i64),
+        )
+        .await?;
+
+        for pkg in &snapshot.packages {
+            tx.run(
+                query(
+                    "MERGE (pkg:Package {purl: $package_purl}) \
+                     SET pkg.name = $name, \
+                         pkg.type = $type, \
+                         pkg.namespace = $namespace, \
+                         pkg.updated_at = datetime() \
+                     MERGE (v:PackageVersion {purl: $purl}) \
+                     ON CREATE SET v.first_seen_at = datetime() \
+                     SET v.version = $version, \
+                         v.package_purl = $package_purl, \
+                         v.last_seen_at = datetime() \
+                     MERGE (pkg)-[:HAS_VERSION]->(v) \
+                     WITH v \
+                     MATCH (s:Scan {id: $scan_id}) \
+                     MERGE (s)-[i:INCLUDES]->(v) \
+                     SET i.scope = $scope",
+                )
+                .param("scan_id", snapshot.scan_id as i64)
+                .param("scope", pkg.scope.clone().unwrap_or_default())
+                .param("package_purl", pkg.package_purl.as_str())
+                .param("purl", pkg.purl.as_str())
+                .param("version", pkg.version.clone().unwrap_or_default())
+                .param("name", pkg.name.as_str())
+                .param("type", pkg.purl_type.as_str())
+                .param("namespace", pkg.namespace.clone().unwrap_or_default()),
+            )
+            .await?;
+        }
+
+        let by_purl: HashMap<&str, _> = snapshot
+            .packages
+            .iter()
+            .map(|p| (p.purl.as_str(), p))
+            .collect();
+
+        for edge in &snapshot.edges {
+            let Some(child) = by_purl.get(edge.child.as_str()) else {
+                continue;
+            };
+
+            let q = match edge.parent.as_deref() {
+                None => query(
+                    "MATCH (p:Project {id: $project_id}), (pkg:PackageVersion {purl: $purl}) \
+                     MERGE (p)-[r:DEPENDS_ON {scan_id: $scan_id, purl: $purl}]->(pkg) \
+                     SET r.scope = $scope, \
+                         r.resolved_version = $resolved_version, \
+                         r.manager = $manager, \
+                         r.bom_ref = $bom_ref, \
+                         r.updated_at = datetime()",
+                ),
+                Some(parent) => query(
+                    "MATCH (parent:PackageVersion {purl: $parent}), (pkg:PackageVersion {purl: $purl}) \
+                     MERGE (parent)-[r:DEPENDS_ON {scan_id: $scan_id, purl: $purl}]->(pkg) \
+                     SET r.project_id = $project_id, \
+                         r.scope = $scope, \
+                         r.resolved_version = $resolved_version, \
+                         r.manager = $manager, \
+                         r.bom_ref = $bom_ref, \
+                         r.updated_at = datetime()",
+                )
+                .param("parent", parent),
+            };
+
+            tx.run(
+                q.param("project_id", project.id as i64)
+                    .param("scan_id", snapshot.scan_id as i64)
+                    .param("purl", child.purl.as_str())
+                    .param("scope", child.scope.clone().unwrap_or_default())
+                    .param("resolved_version", child.version.clone().unwrap_or_default())
+                    .param("manager", project.package_manager.clone().unwrap_or_default())
+                    .param("bom_ref", child.bom_ref.clone().unwrap_or_default()),
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn delete_project(&self, project_id: i32) -> anyhow::Result<()> {
+        let mut tx = self.graph.start_txn().await?;
+
+        // Transitive edges hang off shared PackageVersion nodes and carry the project id.
+        tx.run(
+            query(
+                "MATCH ()-[r:DEPENDS_ON|ARCHIVED_DEPENDS_ON {project_id: $project_id}]->() \
+                 DELETE r",
+            )
+            .param("project_id", project_id as i64),
+        )
+        .await?;
+
+        tx.run(
+            query(
+                "MATCH (p:Project {id: $project_id}) \
+                 OPTIONAL MATCH (p)-[:HAS_SCAN]->(s:Scan) \
+                 DETACH DELETE s, p",
+            )
+            .param("project_id", project_id as i64),
+        )
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn latest_scan_id(&self, project_id: i32) -> anyhow::Result<Option<i32>> {
+        let mut result = self
+            .graph
+            .execute(
+                query(
+                    "MATCH (:Project {id: $project_id})-[:HAS_SCAN]->(s:Scan {latest: true}) \
+                     RETURN s.id AS scan_id \
+                     ORDER BY scan_id DESC LIMIT 1",
+                )
+                .param("project_id", project_id as i64),
+            )
+            .await?;
+
+        match result.next().await? {
+            Some(row) => {
+                let scan_id: i64 = row.get("scan_id")?;
+                Ok(Some(scan_id as i32))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn apply_retention(
+        &self,
+        project_id: i32,
+        keep: u32,
+        mode: RetentionMode,
+    ) -> anyhow::Result<()> {
+        let mut result = self
+            .graph
+            .execute(
+                query(
+                    "MATCH (:Project {id: $project_id})-[:HAS_SCAN]->(s:Scan) \
+                     WHERE s.latest = false AND coalesce(s.archived, false) = false \
+                     WITH s ORDER BY s.id DESC SKIP $keep \
+                     RETURN collect(s.id) AS stale",
+                )
+                .param("project_id", project_id as i64)
+                .param("keep", i64::from(keep)),
+            )
+            .await?;
+
+        let stale: Vec<i64> = match result.next().await? {
+            Some(row) => row.get("stale")?,
+            None => Vec::new(),
+        };
+
+        if stale.is_empty() {
+            return Ok(());
+        }
+
+        // Relationship types cannot be changed in place, so archiving recreates each edge.
+        let edge_action = match mode {
+            RetentionMode::Archive => {
+                "CREATE (from)-[a:ARCHIVED_DEPENDS_ON]->(to) \
+                 SET a = properties(r), a.archived_at = datetime() \
+                 DELETE r"
+            }
+            RetentionMode::Prune => "DELETE r",
+        };
+
+        let mut tx = self.graph.start_txn().await?;
+
+        tx.run(
+            query(&format!(
+                "MATCH (from:Project {{id: $project_id}})-[r:DEPENDS_ON]->(to) \
+                 WHERE r.scan_id IN $stale \
+                 WITH DISTINCT from, r, to \
+                 {edge_action}"
+            ))
+            .param("project_id", project_id as i64)
+            .param("stale", stale.clone()),
+        )
+        .await?;
+
+        tx.run(
+            query(&format!(
+                "MATCH (s:Scan)-[:INCLUDES]->(from:PackageVersion)-[r:DEPENDS_ON]->(to) \
+                 WHERE s.id IN $stale AND r.scan_id = s.id \
+                 WITH DISTINCT from, r, to \
+                 {edge_action}"
+            ))
+            .param("stale", stale.clone()),
+        )
+        .await?;
+
+        let snapshot_query = match mode {
+            RetentionMode::Archive => query(
+                "MATCH (s:Scan) WHERE s.id IN $stale \
+                 SET s.archived = true, s.archived_at = datetime()",
+            ),
+            RetentionMode::Prune => query("MATCH (s:Scan) WHERE s.id IN $stale DETACH DELETE s"),
+        };
+        tx.run(snapshot_query.param("stale", stale.clone())).await?;
+
+        tx.commit().await?;
+
+        tracing::info!(project_id, scans = ?stale, ?mode, "applied graph retention");
+
+        Ok(())
+    }
+
+    async fn dependency_tree(
+        &self,
+        project_id: i32,
+        scan_id: Option<i32>,
+        max_depth: u32,
+    ) -> anyhow::Result<Vec<TreeEdge>> {
+        let Some(scan_id) = resolve_scan(self, project_id, scan_id).await? else {
+            return Ok(Vec::new());
+        };
+        let depth = clamp_depth(max_depth);
+
+        // Variable-length bounds cannot be parameterised, so the clamped depth is inlined.
+        let cypher = format!(
+            "MATCH (p:Project {{id: $project_id}})-[r:DEPENDS_ON {{scan_id: $scan_id}}]->(pkg:PackageVersion) \
+             RETURN null AS parent, pkg.purl AS purl, r.resolved_version AS version, \
+                    r.scope AS scope, 1 AS depth \
+             UNION \
+             MATCH path = (p:Project {{id: $project_id}})-[rels:DEPENDS_ON*1..{max_parent}]->(parent:PackageVersion) \
+             WHERE all(x IN rels WHERE x.scan_id = $scan_id) \
+             WITH parent, min(length(path)) AS parent_depth \
+             MATCH (parent)-[r:DEPENDS_ON {{scan_id: $scan_id}}]->(pkg:PackageVersion) \
+             RETURN parent.purl AS parent, pkg.purl AS purl, r.resolved_version AS version, \
+                    r.scope AS scope, parent_depth + 1 AS depth",
+            max_parent = depth.saturating_sub(1).max(1),
+        );
+
+        let mut result = self
+            .graph
+            .execute(
+                query(&cypher)
+                    .param("project_id", project_id as i64)
+                    .param("scan_id", scan_id as i64),
+            )
+            .await?;
+
+        let mut edges = Vec::new();
+        while let Some(row) = result.next().await? {
+            let edge_depth: i64 = row.get("depth")?;
+            if edge_depth as u32 > depth {
+                continue;
+            }
+
+            edges.push(TreeEdge {
+                parent: row.get("parent")?,
+                purl: row.get("purl")?,
+                version: non_empty(row.get("version")?),
+                scope: non_empty(row.get("scope")?),
+                depth: edge_depth as u32,
+            });
+        }
+
+        edges.sort_by(|a, b| a.depth.cmp(&b.depth).then_with(|| a.purl.cmp(&b.purl)));
+
+        Ok(edges)
+    }
+
+    async fn dependency_paths(
+        &self,
+        project_id: i32,
+        scan_id: Option<i32>,
+        purl: &str,
+        max_depth: u32,
+    ) -> anyhow::Result<Vec<DependencyPath>> {
+        let Some(scan_id) = resolve_scan(self, project_id, scan_id).await? else {
+            return Ok(Vec::new());
+        };
+        let depth = clamp_depth(max_depth);
+
+        let cypher = format!(
+            "MATCH (p:Project {{id: $project_id}}), (target:PackageVersion) \
+             WHERE target.purl = $purl OR target.package_purl = $purl \
+             MATCH path = allShortestPaths((p)-[:DEPENDS_ON*1..{depth}]->(target)) \
+             WHERE all(r IN relationships(path) WHERE r.scan_id = $scan_id) \
+             RETURN [r IN relationships(path) | endNode(r).purl] AS purls, \
+                    [r IN relationships(path) | r.resolved_version] AS versions, \
+                    [r IN relationships(path) | r.scope] AS scopes"
+        );
+
+        let mut result = self
+            .graph
+            .execute(
+                query(&cypher)
+                    .param("project_id", project_id as i64)
+                    .param("scan_id", scan_id as i64)
+                    .param("purl", purl),
+            )
+            .await?;
+
+        let mut paths = Vec::new();
+        while let Some(row) = result.next().await? {
+            let purls: Vec<String> = row.get("purls")?;
+            let versions: Vec<Option<String>> = row.get("versions")?;
+            let scopes: Vec<Option<String>> = row.get("scopes")?;
+
+            let hops = purls
+                .into_iter()
+                .zip(versions)
+                .zip(scopes)
+                .map(|((purl, version), scope)| PathHop {
+                    purl,
+                    version: non_empty(version),
+                    scope: non_empty(scope),
+                })
+                .collect();
+
+            paths.push(hops);
+        }
+
+        Ok(paths)
+    }
+
+    async fn dependency_paths_batch(
+        &self,
+        project_id: i32,
+        scan_id: Option<i32>,
+        purls: &[String],
+        max_depth: u32,
+    ) -> anyhow::Result<HashMap<String, Vec<DependencyPath>>> {
+        if purls.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let Some(scan_id) = resolve_scan(self, project_id, scan_id).await? else {
+            return Ok(HashMap::new());
+        };
+        let depth = clamp_depth(max_depth);
+
+        let cypher = format!(
+            "UNWIND $purls AS wanted \
+             MATCH (p:Project {{id: $project_id}}), (target:PackageVersion) \
+             WHERE target.purl = wanted OR target.package_purl = wanted \
+             MATCH path = allShortestPaths((p)-[:DEPENDS_ON*1..{depth}]->(target)) \
+             WHERE all(r IN relationships(path) WHERE r.scan_id = $scan_id) \
+             RETURN wanted, \
+                    [r IN relationships(path) | endNode(r).purl] AS purls, \
+                    [r IN relationships(path) | r.resolved_version] AS versions, \
+                    [r IN relationships(path) | r.scope] AS scopes"
+        );
+
+        let mut result = self
+            .graph
+            .execute(
+                query(&cypher)
+                    .param("project_id", project_id as i64)
+                    .param("scan_id", scan_id as i64)
+                    .param("purls", purls.to_vec()),
+            )
+            .await?;
+
+        let mut paths: HashMap<String, Vec<DependencyPath>> = HashMap::new();
+        while let Some(row) = result.next().await? {
+            let wanted: String = row.get("wanted")?;
+            let purls: Vec<String> = row.get("purls")?;
+            let versions: Vec<Option<String>> = row.get("versions")?;
+            let scopes: Vec<Option<String>> = row.get("scopes")?;
+
+            let hops = purls
+                .into_iter()
+                .zip(versions)
+                .zip(scopes)
+                .map(|((purl, version), scope)| PathHop {
+                    purl,
+                    version: non_empty(version),
+                    scope: non_empty(scope),
+                })
+                .collect();
+
+            paths.entry(wanted).or_default().push(hops);
+        }
+
+        Ok(paths)
+    }
+
+    async fn reverse_dependents(
+        &self,
+        purls: &[String],
+        max_depth: u32,
+    ) -> anyhow::Result<Vec<Dependent>> {
+        let depth = clamp_depth(max_depth);
+
+        let cypher = format!(
+            "MATCH (p:Project)-[:HAS_SCAN]->(s:Scan {{latest: true}}) \
+             MATCH path = (p)-[rels:DEPENDS_ON*1..{depth}]->(target:PackageVersion) \
+             WHERE (target.purl IN $purls OR target.package_purl IN $purls) \
+               AND all(r IN rels WHERE r.scan_id = s.id) \
+             RETURN p.id AS project_id, s.id AS scan_id, min(length(path)) AS depth \
+             ORDER BY project_id, scan_id"
+        );
+
+        let mut result = self
+            .graph
+            .execute(query(&cypher).param("purls", purls.to_vec()))
+            .await?;
+
+        let mut dependents = Vec::new();
+        while let Some(row) = result.next().await? {
+            let project_id: i64 = row.get("project_id")?;
+            let scan_id: i64 = row.get("scan_id")?;
+            let depth: i64 = row.get("depth")?;
+
+            dependents.push(Dependent {
+                project_id: project_id as i32,
+                scan_id: scan_id as i32,
+                depth: depth as u32,
+            });
+        }
+
+        Ok(dependents)
+    }
+}
@@ -0,0 +1,496 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseBackend, DatabaseConnection, EntityTrait,
+    FromQueryResult, QueryFilter, QueryOrder, Set, Statement, TransactionTrait, Value,
+};
+
+use super::{
+    clamp_depth, resolve_scan, DependencyPath, Dependent, GraphStore, PathHop, ScanGraph,
+    TreeEdge,
+};
+use crate::{
+    config::graph::RetentionMode,
+    database::insert_in_batches,
+    entity::{dependency_edge, scan},
+};
+
+/// Minimum depth of every version reachable from the root of scan `$1` within `$2` hops.
+const REACHED_CTE: &str = "\
+    WITH RECURSIVE walk(version_id, depth) AS ( \
+        SELECT e.child_version_id, 1 \
+        FROM dependency_edges e \
+        WHERE e.scan_id = $1 AND e.parent_version_id IS NULL \
+      UNION \
+        SELECT e.child_version_id, w.depth + 1 \
+        FROM walk w \
+        JOIN dependency_edges e \
+          ON e.scan_id = $1 AND e.parent_version_id = w.version_id \
+        WHERE w.depth < $2 \
+    ), \
+    reached AS ( \
+        SELECT version_id, MIN(depth) AS depth FROM walk GROUP BY version_id \
+    )";
+
+/// Successful scans of project `$1` older than the newest `$2`; failed scans have no
+/// snapshot to keep.
+const STALE_SCANS_CTE: &str = "\
+    WITH stale AS ( \
+        SELECT id FROM scans WHERE project_id = $1 AND status = 'success' \
+        ORDER BY id DESC OFFSET $2 \
+    )";
+
+/// Graph store that keeps edges in the `dependency_edges` table.
+///
+/// Used when Neo4j is disabled so transitive queries still work on small deployments.
+/// A project's latest scan is its newest successful row in `scans`.
+#[derive(Clone)]
+pub struct PostgresGraphStore {
+    db: DatabaseConnection,
+}
+
+impl PostgresGraphStore {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    async fn scan_nodes(&self, scan_id: i32) -> anyhow::Result<HashMap<i32, PathHop>> {
+        let rows = NodeRow::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "SELECT DISTINCT ON (e.child_version_id) \
+                    e.child_version_id AS version_id, v.purl, e.resolved_version, e.scope \
+             FROM dependency_edges e \
+             JOIN package_versions v ON v.id = e.child_version_id \
+             WHERE e.scan_id = $1 \
+             ORDER BY e.child_version_id, e.id",
+            [scan_id.into()],
+        ))
+        .all(&self.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    row.version_id,
+                    PathHop {
+                        purl: row.purl,
+                        version: row.resolved_version,
+                        scope: row.scope,
+                    },
+                )
+            })
+            .collect())
+    }
+}
+
+#[derive(Debug, FromQueryResult)]
+struct NodeRow {
+    version_id: i32,
+    purl: String,
+    resolved_version: Option<String>,
+    scope: Option<String>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct TreeRow {
+    parent_purl: Option<String>,
+    purl: String,
+    resolved_version: Option<String>,
+    scope: Option<String>,
+    depth: i32,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct PathRow {
+    path: String,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct TargetPathRow {
+    target: String,
+    path: String,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct DependentRow {
+    project_id: i32,
+    scan_id: i32,
+    depth: i32,
+}
+
+#[async_trait]
+impl GraphStore for PostgresGraphStore {
+    fn backend(&self) -> &'static str {
+        "postgres"
+    }
+
+    async fn sync_scan(&self, snapshot: &ScanGraph) -> anyhow::Result<()> {
+        let now = Utc::now();
+        let by_purl: HashMap<&str, _> = snapshot
+            .packages
+            .iter()
+            .map(|p| (p.purl.as_str(), p))
+            .collect();
+
+        let mut models = Vec::with_capacity(snapshot.edges.len());
+        for edge in &snapshot.edges {
+            let Some(child) = by_purl.get(edge.child.as_str()) else {
+                continue;
+            };
+
+            let parent = match edge.parent.as_deref() {
+                None => None,
+                Some(purl) => match by_purl.get(purl) {
+                    Some(parent) => Some(*parent),
+                    None => continue,
+                },
+            };
+
+            models.push(dependency_edge::ActiveModel {
+                scan_id: Set(snapshot.scan_id),
+                project_id: Set(snapshot.project.id),
+                parent_package_id: Set(parent.map(|p| p.package_id)),
+                child_package_id: Set(child.package_id),
+                parent_version_id: Set(parent.map(|p| p.version_id)),
+                child_version_id: Set(Some(child.version_id)),
+                resolved_version: Set(child.version.clone()),
+                scope: Set(child.scope.clone()),
+                created_at: Set(now),
+                ..Default::default()
+            });
+        }
+
+        // One transaction, so a failed batch never leaves the scan with a partial graph.
+        let txn = self.db.begin().await?;
+        insert_in_batches(&txn, models).await?;
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    async fn delete_project(&self, project_id: i32) -> anyhow::Result<()> {
+        dependency_edge::Entity::delete_many()
+            .filter(dependency_edge::Column::ProjectId.eq(project_id))
+            .exec(&self.db)
+            .await?;
+
+        self.db
+            .execute_raw(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                "DELETE FROM dependency_edges_archive WHERE project_id = $1",
+                [project_id.into()],
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn latest_scan_id(&self, project_id: i32) -> anyhow::Result<Option<i32>> {
+        let latest = scan::Entity::find()
+            .filter(scan::Column::ProjectId.eq(project_id))
+            .filter(scan::Column::Status.eq("success"))
+            .order_by_desc(scan::Column::Id)
+            .one(&self.db)
+            .await?;
+
+        Ok(latest.map(|s| s.id))
+    }
+
+    async fn apply_retention(
+        &self,
+        project_id: i32,
+        keep: u32,
+        mode: RetentionMode,
+    ) -> anyhow::Result<()> {
+        let sql = match mode {
+            RetentionMode::Archive => format!(
+                "{STALE_SCANS_CTE}, \
+                 moved AS ( \
+                     DELETE FROM dependency_edges e USING stale \
+                     WHERE e.scan_id = stale.id \
+                     RETURNING e.* \
+                 ) \
+                 INSERT INTO dependency_edges_archive \
+                     (id, scan_id, project_id, parent_package_id, child_package_id, \
+                      parent_version_id, child_version_id, resolved_version, scope, \
+                      created_at, archived_at) \
+                 SELECT id, scan_id, project_id, parent_package_id, child_package_id, \
+                        parent_version_id, child_version_id, resolved_version, scope, \
+                        created_at, NOW() \
+                 FROM moved"
+            ),
+            RetentionMode::Prune => format!(
+                "{STALE_SCANS_CTE} \
+                 DELETE FROM dependency_edges e USING stale WHERE e.scan_id = stale.id"
+            ),
+        };
+
+        // The latest scan is always kept on top of the configured window.
+        let offset = i64::from(keep) + 1;
+        let result = self
+            .db
+            .execute_raw(Statement::from_sql_and_values(
+                DatabaseBackend::Postgres,
+                sql,
+                [project_id.into(), offset.into()],
+            ))
+            .await?;
+
+        if result.rows_affected() > 0 {
+            tracing::info!(
+                project_id,
+                edges = result.rows_affected(),
+                ?mode,
+                "applied graph retention"
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn dependency_tree(
+        &self,
+        project_id: i32,
+        scan_id: Option<i32>,
+        max_depth: u32,
+    ) -> anyhow::Result<Vec<TreeEdge>> {
+        let Some(scan_id) = resolve_scan(self, project_id, scan_id).await? else {
+            return Ok(Vec::new());
+        };
+        let depth = clamp_depth(max_depth) as i32;
+
+        let sql = format!(
+            "{REACHED_CTE} \
+             SELECT parent.purl AS parent_purl, child.purl, e.resolved_version, e.scope, \
+                    COALESCE(r.depth, 0) + 1 AS depth \
+             FROM dependency_edges e \
+             LEFT JOIN reached r ON r.version_id = e.parent_version_id \
+             LEFT JOIN package_versions parent ON parent.id = e.parent_version_id \
+             JOIN package_versions child ON child.id = e.child_version_id \
+             WHERE e.scan_id = $1 \
+               AND (e.parent_version_id IS NULL OR r.depth < $2) \
+             ORDER BY depth, child.purl"
+        );
+
+        let rows = TreeRow::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            sql,
+            [scan_id.into(), depth.into()],
+        ))
+        .all(&self.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| TreeEdge {
+                parent: row.parent_purl,
+                purl: row.purl,
+                version: row.resolved_version,
+                scope: row.scope,
+                depth: row.depth as u32,
+            })
+            .collect())
+    }
+
+    async fn dependency_paths(
+        &self,
+        project_id: i32,
+        scan_id: Option<i32>,
+        purl: &str,
+        max_depth: u32,
+    ) -> anyhow::Result<Vec<DependencyPath>> {
+        let Some(scan_id) = resolve_scan(self, project_id, scan_id).await? else {
+            return Ok(Vec::new());
+        };
+        let depth = clamp_depth(max_depth) as i32;
+
+        // Walk back from the target only along edges that stay on a shortest layer,
+        // which enumerates every shortest path without exploring longer ones.
+        let sql = format!(
+            "{REACHED_CTE}, \
+             target AS ( \
+                 SELECT r.version_id, r.depth \
+                 FROM reached r \
+                 JOIN package_versions v ON v.id = r.version_id \
+                 JOIN packages p ON p.id = v.package_id \
+                 WHERE v.purl = $3 OR p.purl = $3 \
+             ), \
+             paths(version_id, depth, path) AS ( \
+                 SELECT t.version_id, t.depth, t.version_id::text \
+                 FROM target t \
+                 WHERE t.depth = (SELECT MIN(depth) FROM target) \
+               UNION ALL \
+                 SELECT e.parent_version_id, r.depth, e.parent_version_id::text || ',' || ps.path \
+                 FROM paths ps \
+                 JOIN dependency_edges e \
+                   ON e.scan_id = $1 AND e.child_version_id = ps.version_id \
+                 JOIN reached r \
+                   ON r.version_id = e.parent_version_id AND r.depth = ps.depth - 1 \
+             ) \
+             SELECT DISTINCT path FROM paths WHERE depth = 1 ORDER BY path"
+        );
+
+        let rows = PathRow::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            sql,
+            [scan_id.into(), depth.into(), purl.into()],
+        ))
+        .all(&self.db)
+        .await?;
+
+        if rows.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let nodes = self.scan_nodes(scan_id).await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                row.path
+                    .split(',')
+                    .filter_map(|id| id.parse::<i32>().ok())
+                    .filter_map(|id| nodes.get(&id).cloned())
+                    .collect()
+            })
+            .collect())
+    }
+
+    async fn dependency_paths_batch(
+        &self,
+        project_id: i32,
+        scan_id: Option<i32>,
+        purls: &[String],
+        max_depth: u32,
+    ) -> anyhow::Result<HashMap<String, Vec<DependencyPath>>> {
+        if purls.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let Some(scan_id) = resolve_scan(self, project_id, scan_id).await? else {
+            return Ok(HashMap::new());
+        };
+        let depth = clamp_depth(max_depth) as i32;
+        let mut values: Vec<Value> = vec![scan_id.into(), depth.into()];
+        values.extend(purls.iter().map(|purl| purl.clone().into()));
+        let wanted = (3..=values.len())
+            .map(|i| format!("(${i}::text)"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // Same walk as `dependency_paths`, with the shortest layer picked per target.
+        let sql = format!(
+            "{REACHED_CTE}, \
+             target AS ( \
+                 SELECT w.purl AS target, r.version_id, r.depth \
+                 FROM reached r \
+                 JOIN package_versions v ON v.id = r.version_id \
+                 JOIN packages p ON p.id = v.package_id \
+                 JOIN (VALUES {wanted}) AS w(purl) ON w.purl = v.purl OR w.purl = p.purl \
+             ), \
+             shortest AS ( \
+                 SELECT t.target, t.version_id, t.depth \
+                 FROM target t \
+                 WHERE t.depth = (SELECT MIN(depth) FROM target m WHERE m.target = t.target) \
+             ), \
+             paths(target, version_id, depth, path) AS ( \
+                 SELECT s.target, s.version_id, s.depth, s.version_id::text \
+                 FROM shortest s \
+               UNION ALL \
+                 SELECT ps.target, e.parent_version_id, r.depth, \
+                        e.parent_version_id::text || ',' || ps.path \
+                 FROM paths ps \
+                 JOIN dependency_edges e \
+                   ON e.scan_id = $1 AND e.child_version_id = ps.version_id \
+                 JOIN reached r \
+                   ON r.version_id = e.parent_version_id AND r.depth = ps.depth - 1 \
+             ) \
+             SELECT DISTINCT target, path FROM paths WHERE depth = 1 ORDER BY target, path"
+        );
+
+        let rows = TargetPathRow::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            sql,
+            values,
+        ))
+        .all(&self.db)
+        .await?;
+
+        if rows.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let nodes = self.scan_nodes(scan_id).await?;
+
+        let mut paths: HashMap<String, Vec<DependencyPath>> = HashMap::new();
+        for row in rows {
+            let path = row
+                .path
+                .split(',')
+                .filter_map(|id| id.parse::<i32>().ok())
+                .filter_map(|id| nodes.get(&id).cloned())
+                .collect();
+            paths.entry(row.target).or_default().push(path);
+        }
+        Ok(paths)
+    }
+
+    async fn reverse_dependents(
+        &self,
+        purls: &[String],
+        max_depth: u32,
+    ) -> anyhow::Result<Vec<Dependent>> {
+        if purls.is_empty() {
+            return Ok(Vec::new());
+        }
+        let depth = clamp_depth(max_depth) as i32;
+        let mut values: Vec<Value> = vec![depth.into()];
+        values.extend(purls.iter().map(|purl| purl.clone().into()));
+        let placeholders = (2..=values.len())
+            .map(|i| format!("${i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let rows = DependentRow::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            format!(
+                "WITH RECURSIVE up(scan_id, version_id, depth) AS ( \
+                 SELECT e.scan_id, e.parent_version_id, 1 \
+                 FROM dependency_edges e \
+                 JOIN package_versions v ON v.id = e.child_version_id \
+                 JOIN packages p ON p.id = v.package_id \
+                 WHERE (v.purl IN ({placeholders}) OR p.purl IN ({placeholders})) \
+                   AND e.scan_id IN ( \
+                       SELECT MAX(id) FROM scans WHERE status = 'success' GROUP BY project_id \
+                   ) \
+               UNION \
+                 SELECT e.scan_id, e.parent_version_id, u.depth + 1 \
+                 FROM up u \
+                 JOIN dependency_edges e \
+                   ON e.scan_id = u.scan_id AND e.child_version_id = u.version_id \
+                 WHERE u.version_id IS NOT NULL AND u.depth < $1 \
+             ) \
+             SELECT s.project_id, u.scan_id, MIN(u.depth) AS depth \
+             FROM up u \
+             JOIN scans s ON s.id = u.scan_id \
+             WHERE u.version_id IS NULL \
+             GROUP BY s.project_id, u.scan_id \
+             ORDER BY s.project_id, u.scan_id"
+            ),
+            values,
+        ))
+        .all(&self.db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Dependent {
+                project_id: row.project_id,
+                scan_id: row.scan_id,
+                depth: row.depth as u32,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use sea_orm::{DatabaseBackend, DatabaseConnection, DbErr, FromQueryResult, Statement, Value};
+use serde::Serialize;
+
+/// Which projects take part in an inventory. Empty fields do not filter.
+#[derive(Debug, Clone, Default)]
+pub struct InventoryFilter {
+    pub team: Option<String>,
+    pub project_ids: Vec<i32>,
+    pub project_names: Vec<String>,
+}
+
+/// Every third-party component found in the latest successful scan of each selected
+/// project, one entry per versioned purl.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Inventory {
+    pub projects: Vec<InventoryProject>,
+    pub components: Vec<InventoryComponent>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InventoryProject {
+    pub id: i32,
+    pub name: String,
+    pub team: Option<String>,
+    pub scan_id: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InventoryComponent {
+    pub purl: String,
+    pub package_purl: String,
+    pub purl_type: String,
+    pub namespace: Option<String>,
+    pub name: String,
+    pub version: Option<String>,
+    /// Strongest scope across consumers: required, then optional, then excluded.
+    pub scope: Option<String>,
+    /// SPDX expression of the declared licenses.
+    pub license: Option<String>,
+    /// Names of the consuming projects, sorted.
+    pub projects: Vec<String>,
+    /// Owning teams of the consuming projects, sorted.
+    pub teams: Vec<String>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ProjectRow {
+    id: i32,
+    name: String,
+    team: Option<String>,
+    scan_id: i32,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ComponentRow {
+    purl: String,
+    package_purl: String,
+    purl_type: String,
+    namespace: Option<String>,
+    name: String,
+    version: Option<String>,
+    scope: Option<String>,
+    license_expression: Option<String>,
+    project_name: String,
+    team: Option<String>,
+}
+
+/// Merge the latest successful scan of every project matching `filter`.
+pub async fn build_inventory(
+    db: &DatabaseConnection,
+    filter: &InventoryFilter,
+) -> Result<Inventory, DbErr> {
+    let mut conditions = vec![
+        "s.id IN (SELECT MAX(id) FROM scans WHERE status = 'success' GROUP BY project_id)"
+            .to_string(),
+    ];
+    let mut values: Vec<Value> = Vec::new();
+    if let Some(team) = &filter.team {
+        values.push(team.clone().into());
+        conditions.push(format!("pr.team = ${}", values.len()));
+    }
+    if !filter.project_ids.is_empty() {
+        let placeholders = push_all(&mut values, filter.project_ids.iter().copied());
+        conditions.push(format!("pr.id IN ({placeholders})"));
+    }
+    if !filter.project_names.is_empty() {
+        let placeholders = push_all(&mut values, filter.project_names.iter().cloned());
+        conditions.push(format!("pr.name IN ({placeholders})"));
+    }
+    let where_clause = conditions.join(" AND ");
+
+    let projects = ProjectRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT pr.id, pr.name, pr.team, s.id AS scan_id \
+             FROM projects pr JOIN scans s ON s.project_id = pr.id \
+             WHERE {where_clause} \
+             ORDER BY pr.name"
+        ),
+        values.clone(),
+    ))
+    .all(db)
+    .await?;
+
+    let rows = ComponentRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT COALESCE(v.purl, p.purl) AS purl, p.purl AS package_purl, p.purl_type, \
+                    p.namespace, p.name, COALESCE(v.version, d.resolved_version) AS version, \
+                    CASE WHEN d.is_optional AND d.scope IS NULL THEN 'optional' ELSE d.scope END \
+                        AS scope, \
+                    v.license_expression, pr.name AS project_name, pr.team \
+             FROM direct_dependencies d \
+             JOIN scans s ON s.id = d.scan_id \
+             JOIN projects pr ON pr.id = d.project_id \
+             JOIN packages p ON p.id = d.package_id \
+             LEFT JOIN package_versions v ON v.id = d.package_version_id \
+             WHERE {where_clause} \
+             ORDER BY 1, pr.name"
+        ),
+        values,
+    ))
+    .all(db)
+    .await?;
+
+    let mut merged: BTreeMap<String, (InventoryComponent, BTreeSet<String>, BTreeSet<String>)> =
+        BTreeMap::new();
+    for row in rows {
+        let scope = row.scope.clone();
+        let (component, projects, teams) = merged.entry(row.purl.clone()).or_insert_with(|| {
+            (
+                InventoryComponent {
+                    purl: row.purl,
+                    package_purl: row.package_purl,
+                    purl_type: row.purl_type,
+                    namespace: row.namespace,
+                    name: row.name,
+                    version: row.version,
+                    scope: row.scope,
+                    license: row.license_expression,
+                    projects: Vec::new(),
+                    teams: Vec::new(),
+                },
+                BTreeSet::new(),
+                BTreeSet::new(),
+            )
+        });
+        if scope_rank(scope.as_deref()) < scope_rank(component.scope.as_deref()) {
+            component.scope = scope;
+        }
+        projects.insert(row.project_name);
+        teams.extend(row.team);
+    }
+
+    Ok(Inventory {
+        projects: projects
+            .into_iter()
+            .map(|row| InventoryProject {
+                id: row.id,
+                name: row.name,
+                team: row.team,
+                scan_id: row.scan_id,
+            })
+            .collect(),
+        components: merged
+            .into_values()
+            .map(|(mut component, projects, teams)| {
+                component.projects = projects.into_iter().collect();
+                component.teams = teams.into_iter().collect();
+                component
+            })
+            .collect(),
+    })
+}
+
+/// Append `items` as bind values and return their comma-separated placeholders.
+fn push_all<T: Into<Value>>(values: &mut Vec<Value>, items: impl Iterator<Item = T>) -> String {
+    items
+        .map(|item| {
+            values.push(item.into());
+            format!("${}", values.len())
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A missing scope means required in CycloneDX, so it ranks alongside it.
+fn scope_rank(scope: Option<&str>) -> u8 {
+    match scope {
+        None | Some("required") => 0,
+        Some("optional") => 1,
+        Some("excluded") => 2,
+        Some(_) => 3,
+    }
+}
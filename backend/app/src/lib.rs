@@ -1,13 +1,28 @@
 pub mod api;
 pub mod app;
 pub mod auth;
+pub mod cli;
 pub mod config;
 pub mod common;
 pub mod database;
+pub mod diff;
 pub mod entity;
+pub mod export;
+pub mod freshness;
+pub mod graph;
 pub mod id;
+pub mod inventory;
+pub mod license;
 pub mod neo4j;
+pub mod notice;
 pub mod params;
+pub mod policy;
+pub mod purl;
+pub mod registry;
 pub mod serde;
 pub mod server;
+pub mod storage;
+pub mod triage;
+pub mod version;
+pub mod vulnerability;
 pub mod logger;
\ No newline at end of file
@@ -0,0 +1,311 @@
+//! License data of components, normalized to SPDX.
+//!
+//! SBOMs declare licenses as SPDX ids, free-form names or expressions. Everything is
+//! turned into one SPDX expression per component; names that are not on the SPDX list
+//! become `LicenseRef-` ids so they still take part in expressions and filters.
+
+pub mod policy;
+
+use std::{collections::HashMap, sync::LazyLock};
+
+use spdx::{
+    lexer::{Lexer, Token},
+    Expression, LicenseId, LicenseItem, ParseMode,
+};
+
+/// A license as declared for a component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LicenseChoice<'a> {
+    Id(&'a str),
+    Name(&'a str),
+    Expression(&'a str),
+}
+
+/// The licenses of a component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Licenses {
+    /// Normalized SPDX expression.
+    pub expression: String,
+    /// Distinct license ids of the expression, in order of appearance.
+    pub ids: Vec<String>,
+}
+
+/// Values SBOM generators use for "nothing declared".
+const UNDECLARED: [&str; 4] = ["NOASSERTION", "NONE", "UNKNOWN", "UNLICENSED"];
+
+/// Full license names keyed by [`name_key`], pointing at their SPDX id.
+static FULL_NAMES: LazyLock<HashMap<String, &'static str>> = LazyLock::new(|| {
+    let mut names = HashMap::new();
+    // Current ids first so they win over deprecated ones sharing a name.
+    for deprecated in [false, true] {
+        for (id, full_name, _) in spdx::identifiers::LICENSES {
+            let Some(license) = spdx::license_id(id) else {
+                continue;
+            };
+            if license.is_deprecated() != deprecated {
+                continue;
+            }
+            let id = current_id(license);
+            let key = name_key(full_name);
+            // "GNU General Public License v3.0 only" is usually written without "only".
+            if let Some(short) = key.strip_suffix("only") {
+                names.entry(short.to_string()).or_insert(id);
+            }
+            names.entry(key).or_insert(id);
+        }
+    }
+    names
+});
+
+/// Combine the licenses declared for a component. Several declarations all apply, so
+/// they are joined with `AND`. Returns `None` when nothing usable was declared.
+pub fn resolve<'a>(choices: impl IntoIterator<Item = LicenseChoice<'a>>) -> Option<Licenses> {
+    let mut terms: Vec<String> = Vec::new();
+    for choice in choices {
+        let term = match choice {
+            LicenseChoice::Id(id) | LicenseChoice::Name(id) => normalize_name(id),
+            LicenseChoice::Expression(expression) => normalize_expression(expression),
+        };
+        if let Some(term) = term.filter(|term| !terms.contains(term)) {
+            terms.push(term);
+        }
+    }
+
+    let expression = match terms.len() {
+        0 => return None,
+        1 => terms.remove(0),
+        _ => terms
+            .iter()
+            .map(|term| {
+                if term.contains(' ') {
+                    format!("({term})")
+                } else {
+                    term.clone()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" AND "),
+    };
+    let ids = license_ids(&expression);
+    Some(Licenses { expression, ids })
+}
+
+/// Rewrite an expression with SPDX ids: imprecise names and lowercase operators are
+/// fixed, `/` becomes `OR` and GNU licenses use the `-only`/`-or-later` ids. Text that
+/// does not parse as an expression is treated as a single license name.
+pub fn normalize_expression(raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if raw.is_empty() || is_undeclared(raw) {
+        return None;
+    }
+    canonical_expression(raw).or_else(|| normalize_name(raw))
+}
+
+/// The SPDX id for a license id or name, else a `LicenseRef-` id derived from it.
+pub fn normalize_name(raw: &str) -> Option<String> {
+    let name = raw.trim();
+    if name.is_empty() || is_undeclared(name) {
+        return None;
+    }
+
+    let or_later = name.ends_with('+');
+    let bare = name.trim_end_matches('+');
+    let known = spdx::license_id(bare)
+        .or_else(|| {
+            spdx::identifiers::LICENSES
+                .iter()
+                .find(|(id, _, _)| id.eq_ignore_ascii_case(bare))
+                .and_then(|(id, _, _)| spdx::license_id(id))
+        })
+        .map(|license| with_suffix(license, or_later));
+    if let Some(id) = known {
+        return Some(id);
+    }
+
+    if let Some(id) = FULL_NAMES.get(&name_key(name)) {
+        return Some(id.to_string());
+    }
+    if let Some((license, _)) =
+        spdx::imprecise_license_id(name).filter(|(_, matched)| *matched == name.len())
+    {
+        return Some(current_id(license).to_string());
+    }
+
+    let reference = license_ref(name);
+    (!reference.is_empty()).then(|| format!("LicenseRef-{reference}"))
+}
+
+/// Distinct license ids of a normalized expression, in order of appearance.
+pub fn license_ids(expression: &str) -> Vec<String> {
+    let Ok(parsed) = Expression::parse(expression) else {
+        return vec![expression.to_string()];
+    };
+    let mut ids: Vec<String> = Vec::new();
+    for requirement in parsed.requirements() {
+        let id = license_item_id(&requirement.req.license);
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+/// Distinct `WITH` exception ids of a normalized expression, in order of appearance.
+pub fn exception_ids(expression: &str) -> Vec<String> {
+    let Ok(parsed) = Expression::parse(expression) else {
+        return Vec::new();
+    };
+    let mut ids: Vec<String> = Vec::new();
+    for requirement in parsed.requirements() {
+        if let Some(exception) = requirement
+            .req
+            .exception
+            .filter(|e| !ids.iter().any(|id| id == e.name))
+        {
+            ids.push(exception.name.to_string());
+        }
+    }
+    ids
+}
+
+/// Full name and text of a license or exception id from the bundled SPDX list. An
+/// or-later id without its own text uses the one of the version it starts from.
+pub fn license_text(id: &str) -> Option<(&'static str, &'static str)> {
+    if let Some(license) =
+        spdx::license_id(id).or_else(|| spdx::license_id(id.trim_end_matches('+')))
+    {
+        return Some((license.full_name, license.text()));
+    }
+    spdx::exception_id(id).map(|exception| (exception.name, exception.text()))
+}
+
+/// The id of a parsed license, spelled the way [`normalize_expression`] writes it.
+pub fn license_item_id(license: &LicenseItem) -> String {
+    match license {
+        LicenseItem::Spdx { id, or_later } => with_suffix(*id, *or_later),
+        LicenseItem::Other { lic_ref, .. } => format!("LicenseRef-{lic_ref}"),
+    }
+}
+
+fn is_undeclared(value: &str) -> bool {
+    UNDECLARED
+        .iter()
+        .any(|undeclared| undeclared.eq_ignore_ascii_case(value))
+}
+
+fn canonical_expression(raw: &str) -> Option<String> {
+    let raw = fix_id_case(raw);
+    let mut out = String::with_capacity(raw.len());
+    let mut pending: Option<LicenseId> = None;
+    for token in Lexer::new_mode(&raw, ParseMode::LAX) {
+        let token = token.ok()?.token;
+        if let Some(license) = pending.take() {
+            out.push_str(&with_suffix(license, matches!(token, Token::Plus)));
+            if matches!(token, Token::Plus) {
+                continue;
+            }
+        }
+        match token {
+            Token::Spdx(license) => pending = Some(license),
+            Token::LicenseRef { doc_ref, lic_ref } => {
+                if let Some(doc_ref) = doc_ref {
+                    out.push_str(&format!("DocumentRef-{doc_ref}:"));
+                }
+                out.push_str(&format!("LicenseRef-{lic_ref}"));
+            }
+            Token::Exception(exception) => out.push_str(exception.name),
+            Token::Plus => out.push('+'),
+            Token::OpenParen => out.push('('),
+            Token::CloseParen => out.push(')'),
+            Token::With => out.push_str(" WITH "),
+            Token::And => out.push_str(" AND "),
+            Token::Or => out.push_str(" OR "),
+        }
+    }
+    if let Some(license) = pending {
+        out.push_str(&with_suffix(license, false));
+    }
+
+    Expression::parse(&out).ok().map(|_| out)
+}
+
+/// Spell words that are SPDX license or exception ids in another case the way the
+/// list does, since the lexer only knows the exact spelling.
+fn fix_id_case(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut word = String::new();
+    let flush = |word: &mut String, out: &mut String| {
+        let bare = word.trim_end_matches('+');
+        let known = spdx::identifiers::LICENSES
+            .iter()
+            .map(|(id, _, _)| *id)
+            .chain(spdx::identifiers::EXCEPTIONS.iter().map(|(id, _)| *id))
+            .find(|id| id.eq_ignore_ascii_case(bare));
+        match known {
+            Some(id) => {
+                out.push_str(id);
+                out.push_str(&word[bare.len()..]);
+            }
+            None => out.push_str(word),
+        }
+        word.clear();
+    };
+    for c in raw.chars() {
+        if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+' | ':') {
+            word.push(c);
+        } else {
+            flush(&mut word, &mut out);
+            out.push(c);
+        }
+    }
+    flush(&mut word, &mut out);
+    out
+}
+
+/// The id of a license, `or_later` meaning the declaration had a trailing `+`.
+fn with_suffix(license: LicenseId, or_later: bool) -> String {
+    let id = current_id(license);
+    if !or_later {
+        return id.to_string();
+    }
+    if license.is_gnu() {
+        let base = id.trim_end_matches("-only").trim_end_matches("-or-later");
+        if let Some(later) = spdx::license_id(&format!("{base}-or-later")) {
+            return later.name.to_string();
+        }
+    }
+    format!("{id}+")
+}
+
+/// Deprecated GNU ids such as `GPL-2.0` mean the `-only` variant.
+fn current_id(license: LicenseId) -> &'static str {
+    let only = (license.is_deprecated() && license.is_gnu())
+        .then(|| spdx::license_id(&format!("{}-only", license.name)))
+        .flatten();
+    only.map_or(license.name, |only| only.name)
+}
+
+/// Comparison key of a license name: lowercase letters, digits and dots, without
+/// "the", "version" and the British spelling.
+fn name_key(name: &str) -> String {
+    let lower = name.to_lowercase().replace("licence", "license");
+    let lower = lower.strip_prefix("the ").unwrap_or(&lower);
+    lower
+        .replace("version", "")
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '.')
+        .collect()
+}
+
+/// A `LicenseRef-` idstring: letters, digits, `.` and `-` only.
+fn license_ref(name: &str) -> String {
+    let mut reference = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() || c == '.' {
+            reference.push(c);
+        } else if !reference.ends_with('-') {
+            reference.push('-');
+        }
+    }
+    reference.trim_matches('-').to_string()
+}
@@ -0,0 +1,365 @@
+//! Evaluation of component licenses against the configured license policy.
+//!
+//! Each term of a license expression (`<id>` or `<id> WITH <exception>`) is looked up in
+//! the denied, review and allowed lists; a `WITH` term that is not listed itself falls
+//! back to its license, and an or-later license to the version it starts from. The
+//! expression is then evaluated with its `AND`/`OR` structure: it is allowed when the
+//! allowed terms satisfy it, denied when it cannot be satisfied without a denied term,
+//! and needs review otherwise.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::LazyLock,
+};
+
+use chrono::Utc;
+use sea_orm::{
+    ColumnTrait, DatabaseBackend, DatabaseConnection, EntityTrait, FromQueryResult, QueryFilter,
+    Set, Statement, TransactionTrait,
+};
+use spdx::{Expression, LicenseReq};
+use tracing::warn;
+
+use super::{license_item_id, normalize_expression};
+use crate::{
+    config::{license_policy::LicenseOutcome, LicensePolicyConfig},
+    database::insert_in_batches,
+    entity::license_violation,
+    graph::{SharedGraphStore, MAX_TRAVERSAL_DEPTH},
+};
+
+static POLICY: LazyLock<LicensePolicy> =
+    LazyLock::new(|| LicensePolicy::from_config(crate::config::get().license_policy()));
+
+/// The policy from `license_policy` in the configuration.
+pub fn configured() -> &'static LicensePolicy {
+    &POLICY
+}
+
+/// License policy with every term normalized to the spelling of stored expressions.
+#[derive(Debug, Clone, Default)]
+pub struct LicensePolicy {
+    allowed: Vec<String>,
+    denied: Vec<String>,
+    review: Vec<String>,
+    unknown: LicenseOutcome,
+    exceptions: Vec<PackageException>,
+}
+
+#[derive(Debug, Clone)]
+struct PackageException {
+    purl: String,
+    /// Empty covers every license of the package.
+    licenses: Vec<String>,
+}
+
+/// How the policy treats one component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LicenseVerdict {
+    pub outcome: LicenseOutcome,
+    /// Terms that kept the expression from being allowed.
+    pub licenses: Vec<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ComponentRow {
+    package_version_id: Option<i32>,
+    purl: String,
+    package_purl: String,
+    scope: Option<String>,
+    license_expression: Option<String>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ScanRow {
+    id: i32,
+    project_id: i32,
+}
+
+impl LicensePolicy {
+    pub fn from_config(config: &LicensePolicyConfig) -> Self {
+        let exceptions = config
+            .exceptions()
+            .iter()
+            .map(|exception| PackageException {
+                purl: exception.purl.trim().to_string(),
+                licenses: normalize_terms(&exception.licenses),
+            })
+            .collect();
+
+        Self {
+            allowed: normalize_terms(config.allowed()),
+            denied: normalize_terms(config.denied()),
+            review: normalize_terms(config.review()),
+            unknown: config.unknown(),
+            exceptions,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.allowed.is_empty() && self.denied.is_empty() && self.review.is_empty()
+    }
+
+    /// Evaluate the stored license expression of a component. `purl` is the versioned
+    /// purl and `package_purl` the versionless one, both used to find exceptions.
+    pub fn evaluate(
+        &self,
+        purl: &str,
+        package_purl: &str,
+        expression: Option<&str>,
+    ) -> LicenseVerdict {
+        let exceptions: Vec<&PackageException> = self
+            .exceptions
+            .iter()
+            .filter(|exception| exception.purl == purl || exception.purl == package_purl)
+            .collect();
+
+        let Some(expression) = expression else {
+            let outcome = if exceptions.is_empty() {
+                self.unknown
+            } else {
+                LicenseOutcome::Allowed
+            };
+            return LicenseVerdict {
+                outcome,
+                licenses: Vec::new(),
+                reason: "no license declared".to_string(),
+            };
+        };
+
+        let Ok(parsed) = Expression::parse(expression) else {
+            return LicenseVerdict {
+                outcome: self.unknown,
+                licenses: vec![expression.to_string()],
+                reason: format!("{expression} is not a valid SPDX expression"),
+            };
+        };
+
+        let term_outcome = |req: &LicenseReq| self.term_outcome(req, &exceptions).0;
+        if parsed.evaluate(|req| term_outcome(req) == LicenseOutcome::Allowed) {
+            return LicenseVerdict {
+                outcome: LicenseOutcome::Allowed,
+                licenses: Vec::new(),
+                reason: String::new(),
+            };
+        }
+
+        let (outcome, failures) = match parsed
+            .evaluate_with_failures(|req| term_outcome(req) != LicenseOutcome::Denied)
+        {
+            Err(failures) => (LicenseOutcome::Denied, failures),
+            Ok(()) => (
+                LicenseOutcome::Review,
+                parsed
+                    .evaluate_with_failures(|req| term_outcome(req) == LicenseOutcome::Allowed)
+                    .err()
+                    .unwrap_or_default(),
+            ),
+        };
+
+        let mut licenses = Vec::new();
+        let mut reasons = Vec::new();
+        for failure in failures {
+            let term = term(&failure.req);
+            if licenses.contains(&term) {
+                continue;
+            }
+            let reason = match self.term_outcome(&failure.req, &exceptions) {
+                (_, false) => format!("{term} is not covered by the policy"),
+                (LicenseOutcome::Denied, true) => format!("{term} is denied"),
+                (_, true) => format!("{term} requires review"),
+            };
+            reasons.push(reason);
+            licenses.push(term);
+        }
+
+        LicenseVerdict {
+            outcome,
+            licenses,
+            reason: reasons.join("; "),
+        }
+    }
+
+    /// Outcome of one term and whether a list or exception decided it.
+    fn term_outcome(
+        &self,
+        req: &LicenseReq,
+        exceptions: &[&PackageException],
+    ) -> (LicenseOutcome, bool) {
+        let id = license_item_id(&req.license);
+        let mut keys = Vec::with_capacity(3);
+        if let Some(exception) = req.exception {
+            keys.push(format!("{id} WITH {}", exception.name));
+        }
+        // An or-later license may be used under the version it starts from.
+        let base = id.strip_suffix('+').map(str::to_string).or_else(|| {
+            id.strip_suffix("-or-later")
+                .map(|base| format!("{base}-only"))
+        });
+        keys.push(id);
+        keys.extend(base);
+
+        let excepted = exceptions.iter().any(|exception| {
+            exception.licenses.is_empty() || keys.iter().any(|key| listed(&exception.licenses, key))
+        });
+        if excepted {
+            return (LicenseOutcome::Allowed, true);
+        }
+
+        for key in &keys {
+            if listed(&self.denied, key) {
+                return (LicenseOutcome::Denied, true);
+            }
+            if listed(&self.review, key) {
+                return (LicenseOutcome::Review, true);
+            }
+            if listed(&self.allowed, key) {
+                return (LicenseOutcome::Allowed, true);
+            }
+        }
+        (self.unknown, false)
+    }
+}
+
+/// Evaluate every component of a scan against the configured policy and replace the
+/// scan's stored violations. Returns the number of violations.
+pub async fn evaluate_scan(
+    db: &DatabaseConnection,
+    graph: &SharedGraphStore,
+    project_id: i32,
+    scan_id: i32,
+) -> anyhow::Result<usize> {
+    let policy = configured();
+    if policy.is_empty() {
+        return Ok(0);
+    }
+
+    let components = ComponentRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT d.package_version_id, COALESCE(v.purl, p.purl) AS purl, \
+                p.purl AS package_purl, d.scope, v.license_expression \
+         FROM direct_dependencies d \
+         JOIN packages p ON p.id = d.package_id \
+         LEFT JOIN package_versions v ON v.id = d.package_version_id \
+         WHERE d.scan_id = $1 \
+         ORDER BY d.id",
+        [scan_id.into()],
+    ))
+    .all(db)
+    .await?;
+
+    let mut seen = HashSet::new();
+    let flagged: Vec<_> = components
+        .into_iter()
+        .filter(|component| seen.insert(component.purl.clone()))
+        .filter_map(|component| {
+            let verdict = policy.evaluate(
+                &component.purl,
+                &component.package_purl,
+                component.license_expression.as_deref(),
+            );
+            (verdict.outcome != LicenseOutcome::Allowed).then_some((component, verdict))
+        })
+        .collect();
+
+    // One path lookup for the whole scan; violations are stored without paths when the
+    // graph is unavailable.
+    let purls: Vec<String> = flagged
+        .iter()
+        .map(|(component, _)| component.purl.clone())
+        .collect();
+    let mut paths = match graph
+        .dependency_paths_batch(project_id, Some(scan_id), &purls, MAX_TRAVERSAL_DEPTH)
+        .await
+    {
+        Ok(paths) => paths,
+        Err(err) => {
+            warn!(error = ?err, scan_id, "failed to load dependency paths");
+            HashMap::new()
+        }
+    };
+
+    let now = Utc::now();
+    let mut violations = Vec::with_capacity(flagged.len());
+    for (component, verdict) in flagged {
+        let paths = paths.remove(&component.purl).unwrap_or_default();
+        violations.push(license_violation::ActiveModel {
+            project_id: Set(project_id),
+            scan_id: Set(scan_id),
+            package_version_id: Set(component.package_version_id),
+            purl: Set(component.purl),
+            scope: Set(component.scope),
+            license_expression: Set(component.license_expression),
+            outcome: Set(verdict.outcome.as_str().to_string()),
+            licenses: Set(serde_json::json!(verdict.licenses)),
+            reason: Set(verdict.reason),
+            paths: Set(serde_json::to_value(&paths)?),
+            created_at: Set(now),
+            ..Default::default()
+        });
+    }
+
+    let count = violations.len();
+    let txn = db.begin().await?;
+    license_violation::Entity::delete_many()
+        .filter(license_violation::Column::ScanId.eq(scan_id))
+        .exec(&txn)
+        .await?;
+    insert_in_batches(&txn, violations).await?;
+    txn.commit().await?;
+
+    Ok(count)
+}
+
+/// Re-evaluate the latest successful scan of every project, e.g. after the policy changed.
+pub async fn evaluate_latest_scans(
+    db: &DatabaseConnection,
+    graph: &SharedGraphStore,
+) -> anyhow::Result<usize> {
+    let scans = ScanRow::find_by_statement(Statement::from_string(
+        DatabaseBackend::Postgres,
+        "SELECT MAX(id) AS id, project_id FROM scans WHERE status = 'success' GROUP BY project_id",
+    ))
+    .all(db)
+    .await?;
+
+    let mut total = 0;
+    for scan in scans {
+        total += evaluate_scan(db, graph, scan.project_id, scan.id).await?;
+    }
+    Ok(total)
+}
+
+/// A term as written in the policy lists: `<id>` or `<id> WITH <exception>`.
+fn term(req: &LicenseReq) -> String {
+    let id = license_item_id(&req.license);
+    match req.exception {
+        Some(exception) => format!("{id} WITH {}", exception.name),
+        None => id,
+    }
+}
+
+fn listed(terms: &[String], key: &str) -> bool {
+    terms.iter().any(|term| term.eq_ignore_ascii_case(key))
+}
+
+/// Normalize policy entries; entries that are not a single term are skipped.
+fn normalize_terms(entries: &[String]) -> Vec<String> {
+    let mut terms = Vec::new();
+    for entry in entries {
+        let Some(normalized) = normalize_expression(entry) else {
+            continue;
+        };
+        let single = Expression::parse(&normalized)
+            .ok()
+            .filter(|parsed| parsed.requirements().count() == 1);
+        match single {
+            Some(_) => terms.push(normalized),
+            None => {
+                warn!(entry = %entry, "ignoring license policy entry that is not a single license")
+            }
+        }
+    }
+    terms
+}
@@ -0,0 +1,308 @@
+//! Third-party attribution notices for a scan.
+//!
+//! Every shipped component is listed with its version, declared license and copyright.
+//! License texts come from the SPDX list bundled with the `spdx` crate; when the scan
+//! stored its source archive, LICENSE/COPYING/NOTICE files found in vendored package
+//! directories are included for the components they belong to. Copyright lines come
+//! from the stored SBOM, else from those files.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Read,
+};
+
+use anyhow::Context;
+use flate2::read::GzDecoder;
+use sea_orm::{DatabaseBackend, DatabaseConnection, FromQueryResult, Statement};
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::{
+    entity::{project, scan},
+    license::{exception_ids, license_ids, license_text},
+    storage,
+};
+
+/// License files larger than this are left out of the notice.
+const MAX_LICENSE_FILE_BYTES: u64 = 256 * 1024;
+
+/// File name prefixes of license files in the source archive.
+const LICENSE_FILE_PREFIXES: [&str; 4] = ["LICENSE", "LICENCE", "COPYING", "NOTICE"];
+
+#[derive(Debug, Clone)]
+pub struct Notice {
+    pub project: String,
+    /// Revision of the scanned source, when known.
+    pub version: Option<String>,
+    pub scan_id: i32,
+    pub components: Vec<NoticeComponent>,
+    /// Texts of every license referenced by a component without its own license files.
+    pub licenses: Vec<NoticeLicense>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NoticeComponent {
+    /// `namespace/name`, or the bare name.
+    pub name: String,
+    pub version: Option<String>,
+    pub purl: String,
+    pub license: Option<String>,
+    pub copyright: Vec<String>,
+    /// License files shipped with the component in the source archive.
+    pub license_files: Vec<LicenseFile>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LicenseFile {
+    /// Path inside the source archive.
+    pub path: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct NoticeLicense {
+    pub id: String,
+    pub name: String,
+    /// `None` for ids the bundled SPDX list has no text for, e.g. `LicenseRef-` ids.
+    pub text: Option<String>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ComponentRow {
+    purl: String,
+    namespace: Option<String>,
+    name: String,
+    version: Option<String>,
+    license_expression: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SbomDocument {
+    #[serde(default)]
+    components: Vec<SbomComponent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SbomComponent {
+    purl: Option<String>,
+    copyright: Option<String>,
+}
+
+/// Build the notice of a scan. Components in `excluded` scope are left out, and so are
+/// `optional` ones unless `include_optional` is set, since SBOM generators put
+/// development dependencies there.
+pub async fn build_notice(
+    db: &DatabaseConnection,
+    project: &project::Model,
+    scan: &scan::Model,
+    include_optional: bool,
+) -> anyhow::Result<Notice> {
+    let scopes = if include_optional {
+        "'excluded'"
+    } else {
+        "'excluded', 'optional'"
+    };
+    let rows = ComponentRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        format!(
+            "SELECT DISTINCT ON (COALESCE(v.purl, p.purl)) \
+                    COALESCE(v.purl, p.purl) AS purl, p.namespace, p.name, \
+                    COALESCE(v.version, d.resolved_version) AS version, v.license_expression \
+             FROM direct_dependencies d \
+             JOIN packages p ON p.id = d.package_id \
+             LEFT JOIN package_versions v ON v.id = d.package_version_id \
+             WHERE d.scan_id = $1 \
+               AND COALESCE(CASE WHEN d.is_optional AND d.scope IS NULL THEN 'optional' \
+                   ELSE d.scope END, 'required') NOT IN ({scopes}) \
+             ORDER BY COALESCE(v.purl, p.purl), d.id"
+        ),
+        [scan.id.into()],
+    ))
+    .all(db)
+    .await?;
+
+    let copyrights = match &scan.sbom_path {
+        Some(path) => sbom_copyrights(path).await.unwrap_or_else(|err| {
+            warn!(error = ?err, scan_id = scan.id, "failed to read stored SBOM for notice");
+            HashMap::new()
+        }),
+        None => HashMap::new(),
+    };
+    let license_files = match &scan.source_path {
+        Some(path) => source_license_files(path).await.unwrap_or_else(|err| {
+            warn!(error = ?err, scan_id = scan.id, "failed to read source archive for notice");
+            Vec::new()
+        }),
+        None => Vec::new(),
+    };
+
+    let mut licenses: BTreeMap<String, NoticeLicense> = BTreeMap::new();
+    let mut components: Vec<NoticeComponent> = rows
+        .into_iter()
+        .map(|row| {
+            let files: Vec<LicenseFile> = license_files
+                .iter()
+                .filter(|file| {
+                    belongs_to(
+                        &file.path,
+                        row.namespace.as_deref(),
+                        &row.name,
+                        row.version.as_deref(),
+                    )
+                })
+                .cloned()
+                .collect();
+
+            let mut copyright: Vec<String> =
+                copyrights.get(&row.purl).cloned().into_iter().collect();
+            if copyright.is_empty() {
+                for file in &files {
+                    for line in copyright_lines(&file.text) {
+                        if !copyright.contains(&line) {
+                            copyright.push(line);
+                        }
+                    }
+                }
+            }
+
+            let expression = row
+                .license_expression
+                .as_deref()
+                .filter(|_| files.is_empty());
+            if let Some(expression) = expression {
+                for id in license_ids(expression)
+                    .into_iter()
+                    .chain(exception_ids(expression))
+                {
+                    licenses.entry(id.clone()).or_insert_with(|| {
+                        let text = license_text(&id);
+                        NoticeLicense {
+                            name: text.map_or_else(|| id.clone(), |(name, _)| name.to_string()),
+                            text: text.map(|(_, text)| text.to_string()),
+                            id,
+                        }
+                    });
+                }
+            }
+
+            NoticeComponent {
+                name: match &row.namespace {
+                    Some(namespace) if !namespace.is_empty() => {
+                        format!("{namespace}/{}", row.name)
+                    }
+                    _ => row.name,
+                },
+                version: row.version,
+                purl: row.purl,
+                license: row.license_expression,
+                copyright,
+                license_files: files,
+            }
+        })
+        .collect();
+    components.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.version.cmp(&b.version)));
+
+    Ok(Notice {
+        project: project.name.clone(),
+        version: scan.revision.clone(),
+        scan_id: scan.id,
+        components,
+        licenses: licenses.into_values().collect(),
+    })
+}
+
+/// Copyright statements of the stored SBOM's components, keyed by purl.
+async fn sbom_copyrights(path: &str) -> anyhow::Result<HashMap<String, String>> {
+    let bytes = storage::read_artifact(path).await?;
+    let document: SbomDocument =
+        serde_json::from_slice(&bytes).context("failed to parse stored SBOM")?;
+    Ok(document
+        .components
+        .into_iter()
+        .filter_map(|component| {
+            let copyright = component.copyright.filter(|c| !c.trim().is_empty())?;
+            Some((component.purl?, copyright.trim().to_string()))
+        })
+        .collect())
+}
+
+/// License files of vendored packages in a `.tar.gz` source archive. Files at the
+/// archive root belong to the project itself and are skipped.
+async fn source_license_files(path: &str) -> anyhow::Result<Vec<LicenseFile>> {
+    let bytes = storage::read_artifact(path).await?;
+    tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<LicenseFile>> {
+        let mut archive = tar::Archive::new(GzDecoder::new(bytes.as_slice()));
+        let mut files = Vec::new();
+        for entry in archive.entries().context("failed to read source archive")? {
+            let mut entry = entry?;
+            if !entry.header().entry_type().is_file() || entry.size() > MAX_LICENSE_FILE_BYTES {
+                continue;
+            }
+            let path = entry
+                .path()?
+                .to_string_lossy()
+                .trim_start_matches("./")
+                .to_string();
+            let Some((dir, file_name)) = path.rsplit_once('/') else {
+                continue;
+            };
+            let upper = file_name.to_ascii_uppercase();
+            if dir.is_empty()
+                || !LICENSE_FILE_PREFIXES
+                    .iter()
+                    .any(|prefix| upper.starts_with(prefix))
+            {
+                continue;
+            }
+
+            let mut text = String::new();
+            if entry.read_to_string(&mut text).is_ok() {
+                files.push(LicenseFile { path, text });
+            }
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    })
+    .await?
+}
+
+/// Whether a license file sits directly in the directory of a vendored package, as laid
+/// out by `node_modules/<namespace>/<name>`, `vendor/<name>` or `<name>-<version>`.
+fn belongs_to(path: &str, namespace: Option<&str>, name: &str, version: Option<&str>) -> bool {
+    let mut dirs = path.rsplit('/').skip(1);
+    let Some(dir) = dirs.next() else {
+        return false;
+    };
+    let name_matches = dir.eq_ignore_ascii_case(name)
+        || version.is_some_and(|version| {
+            [format!("{name}-{version}"), format!("{name}@{version}")]
+                .iter()
+                .any(|candidate| dir.eq_ignore_ascii_case(candidate))
+        });
+    if !name_matches {
+        return false;
+    }
+    // Scoped npm packages live below their scope directory.
+    match namespace.filter(|namespace| namespace.starts_with('@') || namespace.starts_with("%40")) {
+        Some(namespace) => dirs
+            .next()
+            .is_some_and(|parent| parent.eq_ignore_ascii_case(&namespace.replace("%40", "@"))),
+        None => true,
+    }
+}
+
+/// Lines of a license file that state a copyright.
+fn copyright_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| {
+            let lower = line.to_ascii_lowercase();
+            (lower.starts_with("copyright") && !lower.starts_with("copyright notice"))
+                || lower.starts_with("(c)")
+                || lower.starts_with('©')
+        })
+        // Skip the placeholders of license templates such as "Copyright [yyyy] [name]".
+        .filter(|line| line.len() <= 200 && !line.contains("yyyy") && !line.contains("<year>"))
+        .map(str::to_string)
+        .collect()
+}
@@ -15,7 +15,11 @@ pub struct PaginationParams {
         default = "default_page",
         deserialize_with = "crate::serde::deserialize_number"
     )]
-    #[validate(range(min = 1, message = "page must be at least 1"))]
+    #[validate(range(
+        min = 1,
+        max = 1_000_000,
+        message = "page must be between 1 and 1000000"
+    ))]
     pub page: u64,
     #[serde(
         default = "default_per_page",
@@ -25,6 +29,14 @@ pub struct PaginationParams {
     pub per_page: u64,
 }
 
+impl PaginationParams {
+    /// Rows to skip for the requested page.
+    pub fn offset(&self) -> i64 {
+        let offset = self.page.saturating_sub(1).saturating_mul(self.per_page);
+        i64::try_from(offset).unwrap_or(i64::MAX)
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct Page<T> {
     pub total: u64,
@@ -7,10 +7,7 @@ use crate::common::error::ApiError;
 #[from_request(via(axum::extract::Query), rejection(ApiError))]
 pub struct Query<T>(pub T);
 
-impl<T> HasValidate for Query<T>
-where
-    T: HasValidate,
-{
+impl<T> HasValidate for Query<T> {
     type Validate = T;
 
     fn get_validate(&self) -> &Self::Validate {
@@ -0,0 +1,441 @@
+//! Policy-as-code evaluated against every scan.
+//!
+//! Policies are YAML (or JSON) files in `policies.dir`, one policy per file, so they can
+//! be versioned and reviewed like any other code. A policy applies to every project
+//! unless its scope names projects, teams or tags, and holds rules that each produce
+//! violations with the policy's action (`fail` or `warn`) unless the rule overrides it.
+//! A scan fails when any failing violation remains and warns when only warnings do;
+//! violations covered by a waiver (see [`waiver`]) do not count.
+//!
+//! ```yaml
+//! name: baseline
+//! scope:
+//!   tags: [production]
+//! rules:
+//!   - type: vulnerability_severity
+//!     severity: high
+//!   - type: license_category
+//!     categories: [denied]
+//!   - type: unpinned_versions
+//!     action: warn
+//!   - type: package_health
+//!     kinds: [yanked, deprecated]
+//!   - type: new_dependencies
+//!     action: warn
+//! ```
+
+pub mod rules;
+pub mod waiver;
+
+use std::{collections::HashSet, path::Path};
+
+use anyhow::{bail, Context};
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    sea_query::OnConflict, DatabaseBackend, DatabaseConnection, EntityTrait, FromQueryResult, Set,
+    Statement,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    entity::{policy_evaluation, project, scan},
+    graph::SharedGraphStore,
+    registry::health::HealthKind,
+};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Policy {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub scope: PolicyScope,
+    #[serde(default)]
+    pub action: PolicyAction,
+    pub rules: Vec<PolicyRule>,
+}
+
+/// Projects a policy applies to; an empty scope applies to all of them.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PolicyScope {
+    /// Project names or ids.
+    #[serde(default)]
+    pub projects: Vec<String>,
+    #[serde(default)]
+    pub teams: Vec<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyAction {
+    Warn,
+    #[default]
+    Fail,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PolicyRule {
+    /// Unique within the policy; defaults to the rule type.
+    pub name: Option<String>,
+    /// Overrides the policy's action for this rule.
+    pub action: Option<PolicyAction>,
+    #[serde(flatten)]
+    pub check: RuleCheck,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleCheck {
+    /// Packages that must not be used: purls (a version pins one release, a trailing `*`
+    /// matches a prefix) or namespaces such as `pkg:maven/org.example`.
+    BannedPackages {
+        #[serde(default)]
+        purls: Vec<String>,
+        #[serde(default)]
+        namespaces: Vec<String>,
+    },
+    /// Vulnerabilities of at least this severity, unless triaged as not affected, false
+    /// positive or fixed.
+    VulnerabilitySeverity { severity: Severity },
+    /// Components whose license expression cannot be satisfied without a license of one
+    /// of these categories.
+    LicenseCategory { categories: Vec<LicenseCategory> },
+    /// Components without a resolved version or resolved to a version range.
+    UnpinnedVersions,
+    /// Component versions released more than `days` ago, going by the registry's release
+    /// date. Versions without an imported release date fall back to when a scan first
+    /// reported them.
+    MaxAge { days: u32 },
+    /// Packages that are not on the approved list, in the same syntax as banned ones.
+    ApprovedPackages {
+        #[serde(default)]
+        purls: Vec<String>,
+        #[serde(default)]
+        namespaces: Vec<String>,
+    },
+    /// Components their registry marks as yanked or deprecated, or whose package has had
+    /// no release for `registries.abandoned_after_years`; no kinds means all of them.
+    PackageHealth {
+        #[serde(default)]
+        kinds: Vec<HealthKind>,
+    },
+    /// Packages the project's previous successful scan did not have: new direct
+    /// dependencies, and new transitive ones too when `transitive` is set.
+    NewDependencies {
+        #[serde(default)]
+        transitive: bool,
+    },
+}
+
+impl RuleCheck {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            RuleCheck::BannedPackages { .. } => "banned_packages",
+            RuleCheck::VulnerabilitySeverity { .. } => "vulnerability_severity",
+            RuleCheck::LicenseCategory { .. } => "license_category",
+            RuleCheck::UnpinnedVersions => "unpinned_versions",
+            RuleCheck::MaxAge { .. } => "max_age",
+            RuleCheck::ApprovedPackages { .. } => "approved_packages",
+            RuleCheck::PackageHealth { .. } => "package_health",
+            RuleCheck::NewDependencies { .. } => "new_dependencies",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "low" => Some(Severity::Low),
+            "medium" | "moderate" => Some(Severity::Medium),
+            "high" => Some(Severity::High),
+            "critical" => Some(Severity::Critical),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LicenseCategory {
+    /// Denied by the license policy.
+    Denied,
+    /// Needs review under the license policy.
+    Review,
+    /// Copyleft licenses on the SPDX list.
+    Copyleft,
+    /// Licenses that are not OSI approved, including `LicenseRef-` ones.
+    NonOsi,
+    /// No license declared.
+    Undeclared,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl Verdict {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Pass => "pass",
+            Verdict::Warn => "warn",
+            Verdict::Fail => "fail",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyViolation {
+    pub policy: String,
+    /// `<policy>/<rule>`.
+    pub rule: String,
+    pub rule_type: String,
+    pub action: PolicyAction,
+    /// Versioned purl of the offending component, when the violation is about one.
+    pub purl: Option<String>,
+    pub version: Option<String>,
+    pub message: String,
+    /// Waiver that accepts the violation until it expires.
+    #[serde(default)]
+    pub waiver_id: Option<i32>,
+    #[serde(default)]
+    pub waiver_expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyEvaluation {
+    pub project_id: i32,
+    pub scan_id: i32,
+    pub verdict: Verdict,
+    /// Names of the policies that applied to the project.
+    pub policies: Vec<String>,
+    pub violations: Vec<PolicyViolation>,
+    pub evaluated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ScanRow {
+    id: i32,
+}
+
+impl Policy {
+    pub fn applies_to(&self, project: &project::Model) -> bool {
+        let scope = &self.scope;
+        if scope.projects.is_empty() && scope.teams.is_empty() && scope.tags.is_empty() {
+            return true;
+        }
+        let tags: Vec<String> = serde_json::from_value(project.tags.clone()).unwrap_or_default();
+        scope
+            .projects
+            .iter()
+            .any(|name| *name == project.name || *name == project.id.to_string())
+            || project
+                .team
+                .as_ref()
+                .is_some_and(|team| scope.teams.contains(team))
+            || tags.iter().any(|tag| scope.tags.contains(tag))
+    }
+
+    pub fn rule_id(&self, rule: &PolicyRule) -> String {
+        let name = rule.name.as_deref().unwrap_or(rule.check.type_name());
+        format!("{}/{name}", self.name)
+    }
+}
+
+/// Every policy in `policies.dir`, ordered by file name. Fails on files that do not
+/// parse and on duplicate policy names or rule ids, so a broken policy never passes
+/// a scan silently.
+pub fn load_policies() -> anyhow::Result<Vec<Policy>> {
+    match crate::config::get().policies().dir() {
+        Some(dir) => load_dir(Path::new(dir)),
+        None => Ok(Vec::new()),
+    }
+}
+
+pub fn load_dir(dir: &Path) -> anyhow::Result<Vec<Policy>> {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .with_context(|| format!("failed to read policy directory {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| matches!(ext, "yaml" | "yml" | "json"))
+        })
+        .collect();
+    paths.sort();
+
+    let mut policies: Vec<Policy> = Vec::new();
+    for path in paths {
+        // JSON is valid YAML, so one parser covers both.
+        let policy: Policy = config::Config::builder()
+            .add_source(config::File::from(path.as_path()).format(config::FileFormat::Yaml))
+            .build()
+            .and_then(|source| source.try_deserialize())
+            .with_context(|| format!("invalid policy file {}", path.display()))?;
+
+        if policies.iter().any(|other| other.name == policy.name) {
+            bail!(
+                "duplicate policy name {:?} in {}",
+                policy.name,
+                path.display()
+            );
+        }
+        let mut rule_ids = HashSet::new();
+        for rule in &policy.rules {
+            let id = policy.rule_id(rule);
+            if !rule_ids.insert(id.clone()) {
+                bail!("duplicate rule {id:?} in {}", path.display());
+            }
+        }
+        policies.push(policy);
+    }
+    Ok(policies)
+}
+
+/// Evaluate the policies that apply to the scan's project and store the result as the
+/// scan's latest evaluation.
+pub async fn evaluate_scan(
+    db: &DatabaseConnection,
+    graph: &SharedGraphStore,
+    scan: &scan::Model,
+) -> anyhow::Result<PolicyEvaluation> {
+    let policies = load_policies()?;
+    let project = project::Entity::find_by_id(scan.project_id)
+        .one(db)
+        .await?
+        .with_context(|| format!("project {} of scan {} not found", scan.project_id, scan.id))?;
+
+    let applicable: Vec<&Policy> = policies
+        .iter()
+        .filter(|policy| policy.applies_to(&project))
+        .collect();
+    let mut data = rules::ScanData::load(db, scan.id).await?;
+    if applicable
+        .iter()
+        .flat_map(|policy| &policy.rules)
+        .any(|rule| matches!(rule.check, RuleCheck::NewDependencies { .. }))
+    {
+        data.load_diff(db, graph.as_ref(), scan).await?;
+    }
+    let now = Utc::now();
+
+    let mut violations = Vec::new();
+    for policy in &applicable {
+        for rule in &policy.rules {
+            let action = rule.action.unwrap_or(policy.action);
+            for hit in rules::check(&rule.check, &data, now) {
+                violations.push(PolicyViolation {
+                    policy: policy.name.clone(),
+                    rule: policy.rule_id(rule),
+                    rule_type: rule.check.type_name().to_string(),
+                    action,
+                    purl: hit.purl,
+                    version: hit.version,
+                    message: hit.message,
+                    waiver_id: None,
+                    waiver_expires_at: None,
+                });
+            }
+        }
+    }
+
+    let waivers = waiver::active_waivers(db, project.id, now).await?;
+    for violation in &mut violations {
+        if let Some(waiver) = waiver::find_waiver(&waivers, violation) {
+            violation.waiver_id = Some(waiver.id);
+            violation.waiver_expires_at = Some(waiver.expires_at);
+        }
+    }
+
+    let evaluation = PolicyEvaluation {
+        project_id: project.id,
+        scan_id: scan.id,
+        verdict: verdict(&violations),
+        policies: applicable
+            .iter()
+            .map(|policy| policy.name.clone())
+            .collect(),
+        violations,
+        evaluated_at: now,
+    };
+    store(db, &evaluation).await?;
+    Ok(evaluation)
+}
+
+/// Re-evaluate the latest successful scan of every project. Returns how many failed.
+pub async fn evaluate_latest_scans(
+    db: &DatabaseConnection,
+    graph: &SharedGraphStore,
+) -> anyhow::Result<usize> {
+    let scan_ids = ScanRow::find_by_statement(Statement::from_string(
+        DatabaseBackend::Postgres,
+        "SELECT MAX(id) AS id FROM scans WHERE status = 'success' GROUP BY project_id",
+    ))
+    .all(db)
+    .await?;
+
+    let mut failed = 0;
+    for row in scan_ids {
+        let Some(scan) = scan::Entity::find_by_id(row.id).one(db).await? else {
+            continue;
+        };
+        if evaluate_scan(db, graph, &scan).await?.verdict == Verdict::Fail {
+            failed += 1;
+        }
+    }
+    Ok(failed)
+}
+
+fn verdict(violations: &[PolicyViolation]) -> Verdict {
+    match violations
+        .iter()
+        .filter(|violation| violation.waiver_id.is_none())
+        .map(|violation| violation.action)
+        .max()
+    {
+        Some(PolicyAction::Fail) => Verdict::Fail,
+        Some(PolicyAction::Warn) => Verdict::Warn,
+        None => Verdict::Pass,
+    }
+}
+
+async fn store(db: &DatabaseConnection, evaluation: &PolicyEvaluation) -> anyhow::Result<()> {
+    let model = policy_evaluation::ActiveModel {
+        project_id: Set(evaluation.project_id),
+        scan_id: Set(evaluation.scan_id),
+        verdict: Set(evaluation.verdict.as_str().to_string()),
+        policies: Set(serde_json::to_value(&evaluation.policies)?),
+        violations: Set(serde_json::to_value(&evaluation.violations)?),
+        evaluated_at: Set(evaluation.evaluated_at),
+        ..Default::default()
+    };
+    policy_evaluation::Entity::insert(model)
+        .on_conflict(
+            OnConflict::column(policy_evaluation::Column::ScanId)
+                .update_columns([
+                    policy_evaluation::Column::Verdict,
+                    policy_evaluation::Column::Policies,
+                    policy_evaluation::Column::Violations,
+                    policy_evaluation::Column::EvaluatedAt,
+                ])
+                .to_owned(),
+        )
+        .exec(db)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,371 @@
+//! The checks behind each rule type, run over the stored data of one scan.
+
+use chrono::{DateTime, Utc};
+use sea_orm::{DatabaseBackend, DatabaseConnection, DbErr, FromQueryResult, Statement};
+use spdx::{Expression, LicenseItem};
+
+use super::{LicenseCategory, RuleCheck, Severity};
+use crate::{
+    config::license_policy::LicenseOutcome,
+    diff::{diff_scans, latest_successful_scan, ScanDiff},
+    entity::scan,
+    graph::GraphStore,
+    license,
+};
+
+/// Analysis states that take a finding out of the severity rule.
+const RESOLVED_STATES: [&str; 3] = ["not_affected", "false_positive", "fixed"];
+
+/// Components, vulnerability findings and package health findings of one scan, one
+/// component per versioned purl.
+#[derive(Debug, Clone, Default)]
+pub struct ScanData {
+    pub components: Vec<Component>,
+    pub findings: Vec<Finding>,
+    pub health: Vec<HealthFinding>,
+    /// Changes since the project's previous successful scan; only loaded for policies
+    /// that look at them, and absent for a project's first scan.
+    pub diff: Option<ScanDiff>,
+}
+
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct Component {
+    pub purl: String,
+    pub package_purl: String,
+    pub version: Option<String>,
+    pub declared_constraint: Option<String>,
+    pub license_expression: Option<String>,
+    /// When any scan first reported the version.
+    pub first_seen_at: Option<DateTime<Utc>>,
+    /// When the registry published the version, if its release history was imported.
+    pub released_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct Finding {
+    pub osv_id: String,
+    pub purl: String,
+    pub version: Option<String>,
+    pub severity: Option<String>,
+    pub analysis_state: Option<String>,
+}
+
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct HealthFinding {
+    pub purl: String,
+    pub version: Option<String>,
+    pub kind: String,
+    pub detail: String,
+}
+
+/// One violation found by a rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleHit {
+    pub purl: Option<String>,
+    pub version: Option<String>,
+    pub message: String,
+}
+
+impl ScanData {
+    pub async fn load(db: &DatabaseConnection, scan_id: i32) -> Result<Self, DbErr> {
+        let components = Component::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "SELECT DISTINCT ON (COALESCE(v.purl, p.purl)) \
+                    COALESCE(v.purl, p.purl) AS purl, p.purl AS package_purl, \
+                    COALESCE(v.version, d.resolved_version) AS version, \
+                    d.declared_constraint, v.license_expression, v.first_seen_at, \
+                    r.published_at AS released_at \
+             FROM direct_dependencies d \
+             JOIN packages p ON p.id = d.package_id \
+             LEFT JOIN package_versions v ON v.id = d.package_version_id \
+             LEFT JOIN registry_versions r ON r.package_id = p.id \
+                  AND r.version = COALESCE(v.version, d.resolved_version) \
+             WHERE d.scan_id = $1 \
+             ORDER BY COALESCE(v.purl, p.purl), d.id",
+            [scan_id.into()],
+        ))
+        .all(db)
+        .await?;
+
+        let findings = Finding::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "SELECT vu.osv_id, f.purl, f.version, vu.severity, a.state AS analysis_state \
+             FROM vulnerability_findings f \
+             JOIN vulnerabilities vu ON vu.id = f.vulnerability_id \
+             LEFT JOIN vulnerability_analyses a ON a.project_id = f.project_id \
+                  AND a.vulnerability_id = f.vulnerability_id AND a.purl = f.purl \
+             WHERE f.scan_id = $1 \
+             ORDER BY vu.osv_id, f.purl",
+            [scan_id.into()],
+        ))
+        .all(db)
+        .await?;
+
+        let health = HealthFinding::find_by_statement(Statement::from_sql_and_values(
+            DatabaseBackend::Postgres,
+            "SELECT purl, version, kind, detail FROM package_health_findings \
+             WHERE scan_id = $1 ORDER BY purl, kind",
+            [scan_id.into()],
+        ))
+        .all(db)
+        .await?;
+
+        Ok(Self {
+            components,
+            findings,
+            health,
+            diff: None,
+        })
+    }
+
+    /// Diff the scan against the project's previous successful scan, if there is one.
+    pub async fn load_diff(
+        &mut self,
+        db: &DatabaseConnection,
+        graph: &dyn GraphStore,
+        scan: &scan::Model,
+    ) -> Result<(), DbErr> {
+        if let Some(previous) = latest_successful_scan(db, scan.project_id, Some(scan.id)).await? {
+            self.diff = Some(diff_scans(db, graph, scan.project_id, previous, scan.id).await?);
+        }
+        Ok(())
+    }
+}
+
+/// Run one rule over a scan.
+pub fn check(rule: &RuleCheck, data: &ScanData, now: DateTime<Utc>) -> Vec<RuleHit> {
+    match rule {
+        RuleCheck::BannedPackages { purls, namespaces } => data
+            .components
+            .iter()
+            .filter(|component| matches_any(component, purls, namespaces))
+            .map(|component| hit(component, format!("{} is banned", component.purl)))
+            .collect(),
+        RuleCheck::VulnerabilitySeverity { severity } => data
+            .findings
+            .iter()
+            .filter(|finding| {
+                !finding
+                    .analysis_state
+                    .as_deref()
+                    .is_some_and(|state| RESOLVED_STATES.contains(&state))
+            })
+            .filter_map(|finding| {
+                let rated = finding.severity.as_deref().and_then(Severity::from_name)?;
+                (rated >= *severity).then(|| RuleHit {
+                    purl: Some(finding.purl.clone()),
+                    version: finding.version.clone(),
+                    message: format!(
+                        "{} ({}) affects {}",
+                        finding.osv_id,
+                        finding.severity.as_deref().unwrap_or_default(),
+                        finding.purl
+                    ),
+                })
+            })
+            .collect(),
+        RuleCheck::LicenseCategory { categories } => data
+            .components
+            .iter()
+            .filter_map(|component| {
+                categories
+                    .iter()
+                    .find_map(|category| license_category(component, *category))
+                    .map(|message| hit(component, message))
+            })
+            .collect(),
+        RuleCheck::UnpinnedVersions => data
+            .components
+            .iter()
+            .filter_map(|component| {
+                let message = match (&component.version, &component.declared_constraint) {
+                    (None, _) => format!("{} has no resolved version", component.purl),
+                    (Some(version), _) if is_range(version) => {
+                        format!("{} resolves to the range {version}", component.purl)
+                    }
+                    (_, Some(constraint)) if is_range(constraint) => {
+                        format!("{} is declared as {constraint}", component.purl)
+                    }
+                    _ => return None,
+                };
+                Some(hit(component, message))
+            })
+            .collect(),
+        RuleCheck::MaxAge { days } => data
+            .components
+            .iter()
+            .filter_map(|component| {
+                let (since, event) = match component.released_at {
+                    Some(released_at) => (released_at, "was released"),
+                    None => (component.first_seen_at?, "was first seen"),
+                };
+                let age = (now - since).num_days();
+                (age > i64::from(*days)).then(|| {
+                    hit(
+                        component,
+                        format!("{} {event} {age} days ago", component.purl),
+                    )
+                })
+            })
+            .collect(),
+        RuleCheck::ApprovedPackages { purls, namespaces } => data
+            .components
+            .iter()
+            .filter(|component| !matches_any(component, purls, namespaces))
+            .map(|component| {
+                hit(
+                    component,
+                    format!("{} is not on the approved list", component.package_purl),
+                )
+            })
+            .collect(),
+        RuleCheck::PackageHealth { kinds } => data
+            .health
+            .iter()
+            .filter(|finding| {
+                kinds.is_empty() || kinds.iter().any(|kind| kind.as_str() == finding.kind)
+            })
+            .map(|finding| RuleHit {
+                purl: Some(finding.purl.clone()),
+                version: finding.version.clone(),
+                message: format!("{} is {}: {}", finding.purl, finding.kind, finding.detail),
+            })
+            .collect(),
+        RuleCheck::NewDependencies { transitive } => data
+            .diff
+            .iter()
+            .flat_map(|diff| {
+                let transitive = if *transitive {
+                    diff.new_transitive.as_slice()
+                } else {
+                    &[]
+                };
+                diff.added.iter().chain(transitive)
+            })
+            .map(|entry| RuleHit {
+                purl: Some(entry.purl.clone()),
+                version: entry.version.clone(),
+                message: format!(
+                    "{} is a new {} dependency",
+                    entry.purl,
+                    if entry.direct { "direct" } else { "transitive" }
+                ),
+            })
+            .collect(),
+    }
+}
+
+fn hit(component: &Component, message: String) -> RuleHit {
+    RuleHit {
+        purl: Some(component.purl.clone()),
+        version: component.version.clone(),
+        message,
+    }
+}
+
+/// Whether a component matches a purl pattern. Patterns with a version compare against
+/// the versioned purl without qualifiers, others against the package purl; a trailing
+/// `*` matches any suffix.
+pub fn purl_matches(pattern: &str, purl: &str, package_purl: &str) -> bool {
+    let pattern = normalize_pattern(pattern);
+    let target = if pattern.contains('@') {
+        purl.split(['?', '#']).next().unwrap_or(purl)
+    } else {
+        package_purl
+    };
+    glob_matches(&pattern, target)
+}
+
+/// Whether a package lives in a namespace such as `pkg:maven/org.example`.
+pub fn namespace_matches(pattern: &str, package_purl: &str) -> bool {
+    let pattern = normalize_pattern(pattern);
+    let pattern = pattern.trim_end_matches('/');
+    match pattern.strip_suffix('*') {
+        Some(prefix) => package_purl.starts_with(prefix),
+        None => package_purl
+            .strip_prefix(pattern)
+            .is_some_and(|rest| rest.starts_with('/')),
+    }
+}
+
+fn matches_any(component: &Component, purls: &[String], namespaces: &[String]) -> bool {
+    purls
+        .iter()
+        .any(|pattern| purl_matches(pattern, &component.purl, &component.package_purl))
+        || namespaces
+            .iter()
+            .any(|pattern| namespace_matches(pattern, &component.package_purl))
+}
+
+/// Purls encode the `@` of npm scopes; accept patterns written either way.
+fn normalize_pattern(pattern: &str) -> String {
+    pattern.trim().replace("/@", "/%40")
+}
+
+fn glob_matches(pattern: &str, value: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => value.starts_with(prefix),
+        None => pattern == value,
+    }
+}
+
+/// Why a component's license falls into `category`, if it does.
+fn license_category(component: &Component, category: LicenseCategory) -> Option<String> {
+    let Some(expression) = component.license_expression.as_deref() else {
+        return (category == LicenseCategory::Undeclared)
+            .then(|| format!("{} declares no license", component.purl));
+    };
+
+    match category {
+        LicenseCategory::Undeclared => None,
+        LicenseCategory::Denied | LicenseCategory::Review => {
+            let policy = license::policy::configured();
+            if policy.is_empty() {
+                return None;
+            }
+            let verdict =
+                policy.evaluate(&component.purl, &component.package_purl, Some(expression));
+            let expected = match category {
+                LicenseCategory::Denied => LicenseOutcome::Denied,
+                _ => LicenseOutcome::Review,
+            };
+            (verdict.outcome == expected).then(|| {
+                format!(
+                    "{} is licensed {expression}: {}",
+                    component.purl, verdict.reason
+                )
+            })
+        }
+        LicenseCategory::Copyleft | LicenseCategory::NonOsi => {
+            let parsed = Expression::parse(expression).ok()?;
+            let in_category = |license: &LicenseItem| match license {
+                LicenseItem::Spdx { id, .. } => match category {
+                    LicenseCategory::Copyleft => id.is_copyleft(),
+                    _ => !id.is_osi_approved(),
+                },
+                LicenseItem::Other { .. } => category == LicenseCategory::NonOsi,
+            };
+            let label = match category {
+                LicenseCategory::Copyleft => "copyleft",
+                _ => "not OSI approved",
+            };
+            (!parsed.evaluate(|req| !in_category(&req.license))).then(|| {
+                format!(
+                    "{} is licensed {expression}, which is {label}",
+                    component.purl
+                )
+            })
+        }
+    }
+}
+
+/// Whether a version string is a range rather than one release.
+fn is_range(version: &str) -> bool {
+    let version = version.trim();
+    version.is_empty()
+        || version.eq_ignore_ascii_case("latest")
+        || version.contains(['^', '~', '*', '>', '<', '|', ',', '[', '(', ' '])
+        || version
+            .split('.')
+            .any(|part| part.eq_ignore_ascii_case("x"))
+}
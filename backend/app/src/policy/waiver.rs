@@ -0,0 +1,173 @@
+//! Time-boxed waivers of policy violations.
+//!
+//! A waiver accepts the violations of one rule on the components of a project that match
+//! its purl pattern until it expires or is revoked. Waived violations stay in the
+//! evaluation but no longer count toward the verdict; once the waiver lapses they count
+//! again on the next evaluation, which `GET /api/scans/{id}/verdict` runs on every call.
+//! A reminder goes out once per waiver when its expiry is `policies.waiver_reminder_days`
+//! away: it is logged under the `policy_waiver` target and, when
+//! `policies.waiver_webhook_url` is set, posted there.
+
+use std::{str::FromStr, time::Duration};
+
+use chrono::{DateTime, Utc};
+use packageurl::PackageUrl;
+use reqwest::Client;
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, DatabaseBackend, DatabaseConnection, DbErr, EntityTrait,
+    FromQueryResult, QueryFilter, QueryOrder, Statement,
+};
+use serde::Serialize;
+use tracing::warn;
+
+use super::{rules::purl_matches, PolicyViolation};
+use crate::{entity::policy_waiver, purl::base_purl};
+
+#[derive(Debug, FromQueryResult)]
+struct ExpiringWaiver {
+    id: i32,
+    project_id: i32,
+    project_name: String,
+    rule: String,
+    purl: String,
+    approver: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Body posted to the waiver webhook.
+#[derive(Debug, Serialize)]
+struct ReminderPayload<'a> {
+    event: &'static str,
+    waiver_id: i32,
+    project_id: i32,
+    project: &'a str,
+    rule: &'a str,
+    purl: &'a str,
+    approver: &'a str,
+    expires_at: DateTime<Utc>,
+}
+
+/// Waivers of a project that are neither revoked nor expired at `now`.
+pub async fn active_waivers(
+    db: &DatabaseConnection,
+    project_id: i32,
+    now: DateTime<Utc>,
+) -> Result<Vec<policy_waiver::Model>, DbErr> {
+    policy_waiver::Entity::find()
+        .filter(policy_waiver::Column::ProjectId.eq(project_id))
+        .filter(policy_waiver::Column::RevokedAt.is_null())
+        .filter(policy_waiver::Column::ExpiresAt.gt(now))
+        .order_by_asc(policy_waiver::Column::ExpiresAt)
+        .all(db)
+        .await
+}
+
+/// The waiver covering a violation, preferring the one that runs longest.
+pub fn find_waiver<'a>(
+    waivers: &'a [policy_waiver::Model],
+    violation: &PolicyViolation,
+) -> Option<&'a policy_waiver::Model> {
+    let purl = violation.purl.as_deref()?;
+    let package_purl = PackageUrl::from_str(purl)
+        .map(|parsed| base_purl(&parsed))
+        .unwrap_or_else(|_| purl.to_string());
+    waivers
+        .iter()
+        .filter(|waiver| waiver.rule == violation.rule)
+        .filter(|waiver| purl_matches(&waiver.purl, purl, &package_purl))
+        .max_by_key(|waiver| waiver.expires_at)
+}
+
+/// Send the reminders of waivers expiring within the reminder window that have none yet.
+/// Returns the number of reminders sent.
+pub async fn remind_expiring(db: &DatabaseConnection) -> Result<usize, DbErr> {
+    let now = Utc::now();
+    let horizon = now
+        + chrono::Duration::days(
+            crate::config::get()
+                .policies()
+                .waiver_reminder_days()
+                .into(),
+        );
+    let waivers = ExpiringWaiver::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT w.id, w.project_id, pr.name AS project_name, w.rule, w.purl, w.approver, \
+                w.expires_at \
+         FROM policy_waivers w \
+         JOIN projects pr ON pr.id = w.project_id \
+         WHERE w.revoked_at IS NULL AND w.reminded_at IS NULL \
+           AND w.expires_at > $1 AND w.expires_at <= $2 \
+         ORDER BY w.expires_at, w.id",
+        [now.into(), horizon.into()],
+    ))
+    .all(db)
+    .await?;
+
+    if waivers.is_empty() {
+        return Ok(0);
+    }
+
+    for waiver in &waivers {
+        warn!(
+            target: "policy_waiver",
+            project = waiver.project_name,
+            rule = waiver.rule,
+            purl = waiver.purl,
+            approver = waiver.approver,
+            expires_at = %waiver.expires_at,
+            "policy waiver is about to expire"
+        );
+    }
+
+    let reminded = match crate::config::get().policies().waiver_webhook_url() {
+        Some(url) => notify(url, &waivers).await,
+        None => waivers.iter().map(|waiver| waiver.id).collect(),
+    };
+    if !reminded.is_empty() {
+        policy_waiver::Entity::update_many()
+            .col_expr(policy_waiver::Column::RemindedAt, Expr::value(now))
+            .filter(policy_waiver::Column::Id.is_in(reminded.clone()))
+            .exec(db)
+            .await?;
+    }
+
+    Ok(reminded.len())
+}
+
+/// Post each reminder to the webhook and return the waiver ids it accepted.
+async fn notify(url: &str, waivers: &[ExpiringWaiver]) -> Vec<i32> {
+    let client = match Client::builder().timeout(Duration::from_secs(10)).build() {
+        Ok(client) => client,
+        Err(err) => {
+            warn!(error = ?err, "failed to build the waiver webhook client");
+            return Vec::new();
+        }
+    };
+
+    let mut reminded = Vec::new();
+    for waiver in waivers {
+        let payload = ReminderPayload {
+            event: "policy_waiver_expiring",
+            waiver_id: waiver.id,
+            project_id: waiver.project_id,
+            project: &waiver.project_name,
+            rule: &waiver.rule,
+            purl: &waiver.purl,
+            approver: &waiver.approver,
+            expires_at: waiver.expires_at,
+        };
+        let sent = client
+            .post(url)
+            .json(&payload)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        match sent {
+            Ok(_) => reminded.push(waiver.id),
+            Err(err) => {
+                warn!(error = ?err, waiver_id = waiver.id, "failed to post waiver reminder")
+            }
+        }
+    }
+    reminded
+}
@@ -0,0 +1,8 @@
+use packageurl::PackageUrl;
+
+/// Identity shared by every version of a package: type, namespace and name only.
+pub fn base_purl(parsed: &PackageUrl) -> String {
+    let mut base = parsed.clone();
+    base.without_version().clear_qualifiers().without_subpath();
+    base.to_string()
+}
@@ -0,0 +1,292 @@
+//! Release histories of scanned packages, imported from local registry mirrors.
+//!
+//! Only packages some scan has reported are stored; packages first seen after an import
+//! get their history on the next one. Each import replaces the stored releases of the
+//! packages it covers. The same histories flag yanked, deprecated and abandoned
+//! components of scans (see [`health`]).
+
+pub mod cargo;
+pub mod health;
+pub mod maven;
+pub mod npm;
+pub mod pypi;
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QuerySelect, Set, TransactionTrait,
+};
+use walkdir::WalkDir;
+
+use crate::entity::{package, registry_version};
+
+/// Rows written per insert.
+const BATCH_SIZE: usize = 500;
+
+/// A package and every release its registry lists.
+#[derive(Debug, Clone)]
+pub struct RegistryPackage {
+    pub purl_type: &'static str,
+    pub namespace: Option<String>,
+    pub name: String,
+    pub releases: Vec<RegistryRelease>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RegistryRelease {
+    pub version: String,
+    pub published_at: Option<DateTime<Utc>>,
+    pub yanked: bool,
+    pub yanked_reason: Option<String>,
+    /// npm deprecation message.
+    pub deprecated: Option<String>,
+}
+
+/// Where to read registry metadata from; unset sources are skipped.
+#[derive(Debug, Clone, Default)]
+pub struct RegistrySources {
+    pub cargo_index: Option<PathBuf>,
+    pub npm_dir: Option<PathBuf>,
+    pub pypi_dir: Option<PathBuf>,
+    pub maven_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Default)]
+pub struct RegistrySummary {
+    pub files: usize,
+    /// Packages whose releases were stored.
+    pub packages: usize,
+    pub releases: usize,
+    /// Packages in the metadata that no scan has reported.
+    pub unknown: usize,
+}
+
+#[derive(Debug, sea_orm::FromQueryResult)]
+struct KnownPackage {
+    id: i32,
+    purl_type: String,
+    namespace: Option<String>,
+    name: String,
+}
+
+impl RegistrySources {
+    pub fn is_empty(&self) -> bool {
+        self.cargo_index.is_none()
+            && self.npm_dir.is_none()
+            && self.pypi_dir.is_none()
+            && self.maven_dir.is_none()
+    }
+}
+
+/// Key identifying a package across registries and SBOMs: npm and Cargo names are
+/// compared case-insensitively and PyPI names after PEP 503 normalization.
+pub fn package_key(purl_type: &str, namespace: Option<&str>, name: &str) -> String {
+    let purl_type = purl_type.to_ascii_lowercase();
+    let namespace = namespace
+        .filter(|namespace| !namespace.is_empty())
+        .map(|namespace| namespace.replace("%40", "@"));
+    let (namespace, name) = match purl_type.as_str() {
+        "npm" | "cargo" => (
+            namespace.map(|namespace| namespace.to_lowercase()),
+            name.to_lowercase(),
+        ),
+        "pypi" => (None, normalize_pypi_name(name)),
+        _ => (namespace, name.to_string()),
+    };
+    match namespace {
+        Some(namespace) => format!("{purl_type}/{namespace}/{name}"),
+        None => format!("{purl_type}/{name}"),
+    }
+}
+
+fn normalize_pypi_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+        }
+    }
+    normalized
+}
+
+/// Import every configured source and store the releases of known packages.
+pub async fn import(
+    db: &DatabaseConnection,
+    sources: &RegistrySources,
+) -> anyhow::Result<RegistrySummary> {
+    if sources.is_empty() {
+        anyhow::bail!("no registry metadata sources configured");
+    }
+
+    let known: Vec<KnownPackage> = package::Entity::find()
+        .select_only()
+        .column(package::Column::Id)
+        .column(package::Column::PurlType)
+        .column(package::Column::Namespace)
+        .column(package::Column::Name)
+        .filter(package::Column::PurlType.is_in(["cargo", "npm", "pypi", "maven"]))
+        .into_model::<KnownPackage>()
+        .all(db)
+        .await?;
+    let ids: HashMap<String, i32> = known
+        .iter()
+        .map(|package| {
+            (
+                package_key(
+                    &package.purl_type,
+                    package.namespace.as_deref(),
+                    &package.name,
+                ),
+                package.id,
+            )
+        })
+        .collect();
+
+    let mut summary = RegistrySummary::default();
+
+    if let Some(index) = &sources.cargo_index {
+        let names: Vec<String> = known
+            .iter()
+            .filter(|package| package.purl_type == "cargo")
+            .map(|package| package.name.clone())
+            .collect();
+        let source = index.clone();
+        let packages = tokio::task::spawn_blocking(move || cargo::read_crates(&source, &names))
+            .await?
+            .with_context(|| format!("failed to read crates.io index {}", index.display()))?;
+        summary.files += packages.len();
+        store_all(db, &ids, packages, &mut summary).await?;
+        tracing::info!("Imported crates.io index {}", index.display());
+    }
+
+    let file_sources: [(&Option<PathBuf>, &str); 3] = [
+        (&sources.npm_dir, "npm"),
+        (&sources.pypi_dir, "pypi"),
+        (&sources.maven_dir, "maven"),
+    ];
+    for (dir, registry) in file_sources {
+        let Some(dir) = dir else {
+            continue;
+        };
+        let source = dir.clone();
+        let (files, packages) =
+            tokio::task::spawn_blocking(move || read_metadata_dir(&source, registry))
+                .await?
+                .with_context(|| format!("failed to read {registry} metadata {}", dir.display()))?;
+        summary.files += files;
+        store_all(db, &ids, packages, &mut summary).await?;
+        tracing::info!("Imported {registry} metadata {}", dir.display());
+    }
+
+    Ok(summary)
+}
+
+/// Parse the metadata files below `dir`. Files that do not parse are logged and skipped.
+fn read_metadata_dir(dir: &Path, registry: &str) -> anyhow::Result<(usize, Vec<RegistryPackage>)> {
+    if !dir.is_dir() {
+        anyhow::bail!("{} is not a directory", dir.display());
+    }
+
+    let mut files = 0;
+    let mut packages = Vec::new();
+    for entry in WalkDir::new(dir).into_iter().filter_map(Result::ok) {
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy();
+        let parsed = match registry {
+            "maven" => {
+                if !(file_name.starts_with("maven-metadata") && file_name.ends_with(".xml")) {
+                    continue;
+                }
+                fs::read_to_string(path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|text| maven::parse(&text))
+            }
+            _ => {
+                if !file_name.ends_with(".json") {
+                    continue;
+                }
+                fs::read(path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|bytes| {
+                        match registry {
+                            "npm" => npm::parse(&bytes),
+                            _ => pypi::parse(&bytes),
+                        }
+                        .map(Some)
+                    })
+            }
+        };
+        files += 1;
+        match parsed {
+            Ok(Some(package)) => packages.push(package),
+            Ok(None) => {}
+            Err(err) => {
+                tracing::warn!(error = ?err, path = %path.display(), "skipping registry metadata")
+            }
+        }
+    }
+    Ok((files, packages))
+}
+
+async fn store_all(
+    db: &DatabaseConnection,
+    ids: &HashMap<String, i32>,
+    packages: Vec<RegistryPackage>,
+    summary: &mut RegistrySummary,
+) -> anyhow::Result<()> {
+    let now = Utc::now();
+    for package in packages {
+        let key = package_key(
+            package.purl_type,
+            package.namespace.as_deref(),
+            &package.name,
+        );
+        let Some(&package_id) = ids.get(&key) else {
+            summary.unknown += 1;
+            continue;
+        };
+
+        let mut releases = package.releases;
+        releases.sort_by(|a, b| a.version.cmp(&b.version));
+        releases.dedup_by(|a, b| a.version == b.version);
+
+        let txn = db.begin().await?;
+        registry_version::Entity::delete_many()
+            .filter(registry_version::Column::PackageId.eq(package_id))
+            .exec(&txn)
+            .await?;
+        for batch in releases.chunks(BATCH_SIZE) {
+            let models = batch.iter().map(|release| registry_version::ActiveModel {
+                package_id: Set(package_id),
+                version: Set(release.version.clone()),
+                published_at: Set(release.published_at),
+                yanked: Set(release.yanked),
+                yanked_reason: Set(release.yanked_reason.clone()),
+                deprecated: Set(release.deprecated.clone()),
+                imported_at: Set(now),
+                ..Default::default()
+            });
+            registry_version::Entity::insert_many(models)
+                .exec(&txn)
+                .await?;
+        }
+        txn.commit().await?;
+
+        summary.packages += 1;
+        summary.releases += releases.len();
+    }
+    Ok(())
+}
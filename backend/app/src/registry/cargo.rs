@@ -0,0 +1,68 @@
+//! crates.io index clones: one file per crate, one JSON line per published version.
+
+use std::{fs, io::ErrorKind, path::Path};
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use super::{RegistryPackage, RegistryRelease};
+
+#[derive(Debug, Deserialize)]
+struct IndexLine {
+    name: String,
+    vers: String,
+    #[serde(default)]
+    yanked: bool,
+    /// Publish time; only present on versions published since crates.io began recording it.
+    pubtime: Option<DateTime<Utc>>,
+}
+
+/// Path of a crate's file inside the index, e.g. `se/rd/serde` or `3/s/syn`.
+pub fn index_path(name: &str) -> String {
+    let name = name.to_ascii_lowercase();
+    match name.len() {
+        1 => format!("1/{name}"),
+        2 => format!("2/{name}"),
+        3 => format!("3/{}/{name}", &name[..1]),
+        _ => format!("{}/{}/{name}", &name[..2], &name[2..4]),
+    }
+}
+
+/// Read the index entries of the given crates. Crates missing from the index are skipped.
+pub fn read_crates(index: &Path, names: &[String]) -> anyhow::Result<Vec<RegistryPackage>> {
+    let mut packages = Vec::new();
+    for name in names {
+        if !name.is_ascii() || name.is_empty() {
+            continue;
+        }
+        let text = match fs::read_to_string(index.join(index_path(name))) {
+            Ok(text) => text,
+            Err(err) if err.kind() == ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        };
+        let lines: Vec<IndexLine> = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        let Some(first) = lines.first() else {
+            continue;
+        };
+        packages.push(RegistryPackage {
+            purl_type: "cargo",
+            namespace: None,
+            name: first.name.clone(),
+            releases: lines
+                .into_iter()
+                .map(|line| RegistryRelease {
+                    version: line.vers,
+                    published_at: line.pubtime,
+                    yanked: line.yanked,
+                    yanked_reason: None,
+                    deprecated: None,
+                })
+                .collect(),
+        });
+    }
+    Ok(packages)
+}
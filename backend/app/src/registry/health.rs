@@ -0,0 +1,155 @@
+//! Findings for scanned components that their registry marks as yanked (crates.io, PyPI)
+//! or deprecated (npm), and for packages without a release for
+//! `registries.abandoned_after_years`. Findings are rebuilt for a scan whenever it is
+//! checked, so they follow the latest imported registry metadata.
+
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{
+    ColumnTrait, DatabaseBackend, DatabaseConnection, DbErr, EntityTrait, FromQueryResult,
+    QueryFilter, Set, Statement, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{database::insert_in_batches, entity::package_health_finding};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthKind {
+    Yanked,
+    Deprecated,
+    Abandoned,
+}
+
+impl HealthKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HealthKind::Yanked => "yanked",
+            HealthKind::Deprecated => "deprecated",
+            HealthKind::Abandoned => "abandoned",
+        }
+    }
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ComponentStatus {
+    package_id: i32,
+    purl: String,
+    version: Option<String>,
+    yanked: Option<bool>,
+    yanked_reason: Option<String>,
+    deprecated: Option<String>,
+    last_release_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ScanRow {
+    id: i32,
+    project_id: i32,
+}
+
+/// Rebuild the health findings of a scan. Returns the number of findings.
+pub async fn check_scan(
+    db: &DatabaseConnection,
+    project_id: i32,
+    scan_id: i32,
+) -> Result<usize, DbErr> {
+    let statuses = ComponentStatus::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "WITH components AS ( \
+             SELECT DISTINCT ON (COALESCE(v.purl, p.purl)) \
+                    d.package_id, COALESCE(v.purl, p.purl) AS purl, \
+                    COALESCE(v.version, d.resolved_version) AS version \
+             FROM direct_dependencies d \
+             JOIN packages p ON p.id = d.package_id \
+             LEFT JOIN package_versions v ON v.id = d.package_version_id \
+             WHERE d.scan_id = $1 \
+             ORDER BY COALESCE(v.purl, p.purl), d.id) \
+         SELECT c.package_id, c.purl, c.version, r.yanked, r.yanked_reason, r.deprecated, \
+                l.last_release_at \
+         FROM components c \
+         LEFT JOIN registry_versions r ON r.package_id = c.package_id \
+              AND r.version = c.version \
+         LEFT JOIN (SELECT package_id, MAX(published_at) AS last_release_at \
+                    FROM registry_versions GROUP BY package_id) l \
+              ON l.package_id = c.package_id \
+         WHERE r.id IS NOT NULL OR l.package_id IS NOT NULL \
+         ORDER BY c.purl",
+        [scan_id.into()],
+    ))
+    .all(db)
+    .await?;
+
+    let now = Utc::now();
+    let years = crate::config::get().registries().abandoned_after_years();
+    let abandoned_before = now - Duration::days(i64::from(years) * 365);
+
+    let mut findings = Vec::new();
+    for status in statuses {
+        let mut found: Vec<(HealthKind, String)> = Vec::new();
+        if status.yanked == Some(true) {
+            let detail = status
+                .yanked_reason
+                .clone()
+                .unwrap_or_else(|| "no reason given".to_string());
+            found.push((HealthKind::Yanked, detail));
+        }
+        if let Some(message) = &status.deprecated {
+            found.push((HealthKind::Deprecated, message.clone()));
+        }
+        if let Some(last) = status
+            .last_release_at
+            .filter(|last| *last < abandoned_before)
+        {
+            found.push((
+                HealthKind::Abandoned,
+                format!(
+                    "no release since {} (over {years} years)",
+                    last.date_naive()
+                ),
+            ));
+        }
+
+        for (kind, detail) in found {
+            findings.push(package_health_finding::ActiveModel {
+                project_id: Set(project_id),
+                scan_id: Set(scan_id),
+                package_id: Set(status.package_id),
+                purl: Set(status.purl.clone()),
+                version: Set(status.version.clone()),
+                kind: Set(kind.as_str().to_string()),
+                detail: Set(detail),
+                last_release_at: Set(status.last_release_at),
+                created_at: Set(now),
+                ..Default::default()
+            });
+        }
+    }
+
+    let count = findings.len();
+    let txn = db.begin().await?;
+    package_health_finding::Entity::delete_many()
+        .filter(package_health_finding::Column::ScanId.eq(scan_id))
+        .exec(&txn)
+        .await?;
+    insert_in_batches(&txn, findings).await?;
+    txn.commit().await?;
+    Ok(count)
+}
+
+/// Rebuild the health findings of the latest successful scan of every project, e.g.
+/// after registry metadata was imported.
+pub async fn check_latest_scans(db: &DatabaseConnection) -> Result<usize, DbErr> {
+    let scans = ScanRow::find_by_statement(Statement::from_string(
+        DatabaseBackend::Postgres,
+        "SELECT MAX(id) AS id, project_id FROM scans WHERE status = 'success' \
+         GROUP BY project_id",
+    ))
+    .all(db)
+    .await?;
+
+    let mut total = 0;
+    for scan in scans {
+        total += check_scan(db, scan.project_id, scan.id).await?;
+    }
+    Ok(total)
+}
@@ -0,0 +1,71 @@
+//! Maven repository metadata (`<groupId path>/<artifactId>/maven-metadata.xml`).
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::Deserialize;
+
+use super::{RegistryPackage, RegistryRelease};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Metadata {
+    group_id: Option<String>,
+    artifact_id: Option<String>,
+    versioning: Option<Versioning>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Versioning {
+    release: Option<String>,
+    latest: Option<String>,
+    versions: Option<Versions>,
+    /// `yyyyMMddHHmmss` in UTC.
+    last_updated: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Versions {
+    #[serde(default)]
+    version: Vec<String>,
+}
+
+/// Parse artifact-level metadata. Maven records no per-version dates, so only the
+/// release (else latest) version gets one: the metadata's `lastUpdated`. Returns `None`
+/// for group- or version-level metadata.
+pub fn parse(text: &str) -> anyhow::Result<Option<RegistryPackage>> {
+    let metadata: Metadata = quick_xml::de::from_str(text)?;
+    let (Some(group_id), Some(artifact_id), Some(versioning)) =
+        (metadata.group_id, metadata.artifact_id, metadata.versioning)
+    else {
+        return Ok(None);
+    };
+    let Some(versions) = versioning.versions.filter(|v| !v.version.is_empty()) else {
+        return Ok(None);
+    };
+
+    let updated_at = versioning
+        .last_updated
+        .as_deref()
+        .and_then(|stamp| NaiveDateTime::parse_from_str(stamp, "%Y%m%d%H%M%S").ok())
+        .map(|stamp| DateTime::<Utc>::from_naive_utc_and_offset(stamp, Utc));
+    let newest = versioning.release.or(versioning.latest);
+
+    let releases = versions
+        .version
+        .into_iter()
+        .map(|version| RegistryRelease {
+            published_at: updated_at.filter(|_| newest.as_deref() == Some(version.as_str())),
+            version,
+            yanked: false,
+            yanked_reason: None,
+            deprecated: None,
+        })
+        .collect();
+
+    Ok(Some(RegistryPackage {
+        purl_type: "maven",
+        namespace: Some(group_id.trim().to_string()),
+        name: artifact_id.trim().to_string(),
+        releases,
+    }))
+}
@@ -0,0 +1,59 @@
+//! npm packuments, as served by `https://registry.npmjs.org/<name>`.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{RegistryPackage, RegistryRelease};
+
+#[derive(Debug, Deserialize)]
+struct Packument {
+    name: String,
+    /// Version manifests; a deprecated version carries a `deprecated` message.
+    #[serde(default)]
+    versions: HashMap<String, Value>,
+    /// Publish time per version, next to `created` and `modified`.
+    #[serde(default)]
+    time: HashMap<String, Value>,
+}
+
+pub fn parse(bytes: &[u8]) -> anyhow::Result<RegistryPackage> {
+    let packument: Packument = serde_json::from_slice(bytes)?;
+    let (namespace, name) = match packument.name.split_once('/') {
+        Some((scope, name)) if scope.starts_with('@') => {
+            (Some(scope.to_string()), name.to_string())
+        }
+        _ => (None, packument.name.clone()),
+    };
+
+    let releases = packument
+        .versions
+        .into_iter()
+        .map(|(version, manifest)| RegistryRelease {
+            published_at: packument
+                .time
+                .get(&version)
+                .and_then(Value::as_str)
+                .and_then(|time| time.parse::<DateTime<Utc>>().ok()),
+            version,
+            yanked: false,
+            yanked_reason: None,
+            // An empty message is how npm un-deprecates a version.
+            deprecated: manifest
+                .get("deprecated")
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|message| !message.is_empty())
+                .map(str::to_string),
+        })
+        .collect();
+
+    Ok(RegistryPackage {
+        purl_type: "npm",
+        namespace,
+        name,
+        releases,
+    })
+}
@@ -0,0 +1,154 @@
+//! PyPI project metadata: the JSON API (`/pypi/<name>/json`) or the simple repository
+//! API in its JSON form (PEP 691, with the PEP 700 `versions` list).
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{RegistryPackage, RegistryRelease};
+
+#[derive(Debug, Deserialize)]
+struct JsonApiProject {
+    info: JsonApiInfo,
+    #[serde(default)]
+    releases: HashMap<String, Vec<JsonApiFile>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonApiInfo {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonApiFile {
+    upload_time_iso_8601: Option<DateTime<Utc>>,
+    #[serde(default)]
+    yanked: bool,
+    yanked_reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SimpleProject {
+    name: String,
+    #[serde(default)]
+    versions: Vec<String>,
+    #[serde(default)]
+    files: Vec<SimpleFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SimpleFile {
+    filename: String,
+    #[serde(rename = "upload-time")]
+    upload_time: Option<DateTime<Utc>>,
+    /// `false`, `true` or the reason as a string.
+    #[serde(default)]
+    yanked: Value,
+}
+
+/// Earliest upload and whether every file was yanked, per version.
+#[derive(Debug, Default)]
+struct Uploads {
+    published_at: Option<DateTime<Utc>>,
+    files: usize,
+    yanked: usize,
+    yanked_reason: Option<String>,
+}
+
+impl Uploads {
+    fn add(&mut self, uploaded: Option<DateTime<Utc>>, yanked: bool, reason: Option<String>) {
+        self.published_at = match (self.published_at, uploaded) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.files += 1;
+        self.yanked += usize::from(yanked);
+        if self.yanked_reason.is_none() {
+            self.yanked_reason = reason.filter(|reason| yanked && !reason.trim().is_empty());
+        }
+    }
+
+    fn release(self, version: String) -> RegistryRelease {
+        let yanked = self.files > 0 && self.yanked == self.files;
+        RegistryRelease {
+            version,
+            published_at: self.published_at,
+            yanked,
+            yanked_reason: self.yanked_reason.filter(|_| yanked),
+            deprecated: None,
+        }
+    }
+}
+
+pub fn parse(bytes: &[u8]) -> anyhow::Result<RegistryPackage> {
+    let document: Value = serde_json::from_slice(bytes)?;
+    if document.get("info").is_some() {
+        let project: JsonApiProject = serde_json::from_value(document)?;
+        let releases = project
+            .releases
+            .into_iter()
+            .map(|(version, files)| {
+                let mut uploads = Uploads::default();
+                for file in files {
+                    uploads.add(file.upload_time_iso_8601, file.yanked, file.yanked_reason);
+                }
+                uploads.release(version)
+            })
+            .collect();
+        return Ok(package(&project.info.name, releases));
+    }
+
+    let project: SimpleProject = serde_json::from_value(document)?;
+    let mut uploads: BTreeMap<String, Uploads> = project
+        .versions
+        .into_iter()
+        .map(|version| (version, Uploads::default()))
+        .collect();
+    for file in project.files {
+        let Some(version) = file_version(&file.filename) else {
+            continue;
+        };
+        let (yanked, reason) = match file.yanked {
+            Value::Bool(yanked) => (yanked, None),
+            Value::String(reason) => (true, Some(reason)),
+            _ => (false, None),
+        };
+        uploads
+            .entry(version)
+            .or_default()
+            .add(file.upload_time, yanked, reason);
+    }
+    let releases = uploads
+        .into_iter()
+        .map(|(version, uploads)| uploads.release(version))
+        .collect();
+    Ok(package(&project.name, releases))
+}
+
+fn package(name: &str, releases: Vec<RegistryRelease>) -> RegistryPackage {
+    RegistryPackage {
+        purl_type: "pypi",
+        namespace: None,
+        name: name.to_string(),
+        releases,
+    }
+}
+
+/// Version of a distribution file: the second dash-separated field of a wheel or egg, or
+/// what follows the last dash of an sdist's name.
+fn file_version(filename: &str) -> Option<String> {
+    if let Some(stem) = filename
+        .strip_suffix(".whl")
+        .or_else(|| filename.strip_suffix(".egg"))
+    {
+        return stem.split('-').nth(1).map(str::to_string);
+    }
+    let stem = [".tar.gz", ".tar.bz2", ".tar.xz", ".tgz", ".zip"]
+        .iter()
+        .find_map(|ext| filename.strip_suffix(ext))?;
+    stem.rsplit_once('-')
+        .map(|(_, version)| version.to_string())
+        .filter(|version| !version.is_empty())
+}
@@ -0,0 +1,150 @@
+use std::path::Path;
+
+use anyhow::{anyhow, Context};
+use aws_config::BehaviorVersion;
+use aws_sdk_s3::{
+    Client as S3Client,
+    config::{Credentials, Region},
+    primitives::ByteStream,
+};
+
+use crate::config::S3Config;
+
+/// Build a client from `s3` configuration, failing when S3 is disabled or incomplete.
+pub async fn configured_s3_client() -> anyhow::Result<S3Client> {
+    let cfg = crate::config::get().s3();
+
+    if !cfg.enabled() {
+        return Err(anyhow!("s3 is disabled"));
+    }
+
+    let region = cfg
+        .region()
+        .filter(|r| !r.is_empty())
+        .ok_or_else(|| anyhow!("s3 region is required when s3.enabled=true"))?;
+
+    build_s3_client(cfg, region).await
+}
+
+pub async fn build_s3_client(cfg: &S3Config, region: &str) -> anyhow::Result<S3Client> {
+    let mut loader =
+        aws_config::defaults(BehaviorVersion::latest()).region(Region::new(region.to_string()));
+
+    if let Some(endpoint) = cfg.endpoint() {
+        loader = loader.endpoint_url(endpoint);
+    }
+
+    let shared = loader.load().await;
+    let mut builder = aws_sdk_s3::config::Builder::from(&shared);
+
+    if let (Some(access_key), Some(secret)) = (cfg.access_key_id(), cfg.secret_access_key()) {
+        builder = builder
+            .credentials_provider(Credentials::new(access_key, secret, None, None, "static"));
+    }
+
+    if let Some(endpoint) = cfg.endpoint() {
+        builder = builder.endpoint_url(endpoint);
+    }
+
+    Ok(S3Client::from_conf(builder.build()))
+}
+
+pub async fn upload_bytes_to_s3(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    body: Vec<u8>,
+) -> anyhow::Result<String> {
+    client
+        .put_object()
+        .bucket(bucket)
+        .key(key)
+        .body(ByteStream::from(body))
+        .send()
+        .await?;
+
+    Ok(format!("s3://{}/{}", bucket, key))
+}
+
+pub async fn upload_file_to_s3(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    path: &Path,
+) -> anyhow::Result<String> {
+    let body = ByteStream::from_path(path)
+        .await
+        .with_context(|| format!("failed to open path {:?} for upload", path))?;
+
+    client
+        .put_object()
+        .bucket(bucket)
+        .key(key)
+        .body(body)
+        .send()
+        .await?;
+
+    Ok(format!("s3://{}/{}", bucket, key))
+}
+
+/// Split `s3://bucket/key` into its parts.
+fn parse_s3_path(path: &str) -> Option<(&str, &str)> {
+    path.strip_prefix("s3://")?.split_once('/')
+}
+
+/// Read a stored artifact, either `s3://bucket/key` or a local path.
+pub async fn read_artifact(path: &str) -> anyhow::Result<Vec<u8>> {
+    if let Some((bucket, key)) = parse_s3_path(path) {
+        let client = configured_s3_client().await?;
+        let object = client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .with_context(|| format!("failed to fetch {path}"))?;
+        let body = object.body.collect().await?;
+        return Ok(body.into_bytes().to_vec());
+    }
+
+    tokio::fs::read(path)
+        .await
+        .with_context(|| format!("failed to read artifact {path}"))
+}
+
+/// Remove a stored artifact, either `s3://bucket/key` or a local path.
+///
+/// Local artifacts live in a `check-deps-*` temp dir, which is removed once empty.
+pub async fn delete_artifact(path: &str) -> anyhow::Result<()> {
+    if let Some((bucket, key)) = parse_s3_path(path) {
+        let client = configured_s3_client().await?;
+        client
+            .delete_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .with_context(|| format!("failed to delete {path}"))?;
+        return Ok(());
+    }
+
+    let local = Path::new(path);
+    match tokio::fs::remove_file(local).await {
+        Ok(()) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err).with_context(|| format!("failed to delete {path}")),
+    }
+
+    if let Some(dir) = local.parent() {
+        let is_scan_dir = dir.starts_with(std::env::temp_dir())
+            && dir
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with("check-deps-"));
+
+        if is_scan_dir {
+            let _ = tokio::fs::remove_dir_all(dir).await;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,244 @@
+//! Triage of vulnerability findings: analysis states, their history and VEX import.
+
+pub mod vex;
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, Set,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::Principal,
+    entity::{vulnerability_analysis, vulnerability_analysis_event},
+};
+
+/// CycloneDX `impactAnalysisJustification` values.
+pub const CYCLONEDX_JUSTIFICATIONS: [&str; 9] = [
+    "code_not_present",
+    "code_not_reachable",
+    "requires_configuration",
+    "requires_dependency",
+    "requires_environment",
+    "protected_by_compiler",
+    "protected_at_runtime",
+    "protected_at_perimeter",
+    "protected_by_mitigating_control",
+];
+
+/// OpenVEX (and CISA minimum requirements) justification labels.
+pub const OPENVEX_JUSTIFICATIONS: [&str; 5] = [
+    "component_not_present",
+    "vulnerable_code_not_present",
+    "vulnerable_code_not_in_execute_path",
+    "vulnerable_code_cannot_be_controlled_by_adversary",
+    "inline_mitigations_already_exist",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalysisState {
+    NotAffected,
+    Affected,
+    Fixed,
+    UnderInvestigation,
+    FalsePositive,
+}
+
+impl AnalysisState {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AnalysisState::NotAffected => "not_affected",
+            AnalysisState::Affected => "affected",
+            AnalysisState::Fixed => "fixed",
+            AnalysisState::UnderInvestigation => "under_investigation",
+            AnalysisState::FalsePositive => "false_positive",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        serde_json::from_value(serde_json::Value::String(name.to_string())).ok()
+    }
+
+    /// CycloneDX `impactAnalysisState`.
+    pub fn to_cyclonedx(self) -> &'static str {
+        match self {
+            AnalysisState::NotAffected => "not_affected",
+            AnalysisState::Affected => "exploitable",
+            AnalysisState::Fixed => "resolved",
+            AnalysisState::UnderInvestigation => "in_triage",
+            AnalysisState::FalsePositive => "false_positive",
+        }
+    }
+
+    pub fn from_cyclonedx(state: &str) -> Option<Self> {
+        match state {
+            "not_affected" => Some(AnalysisState::NotAffected),
+            "exploitable" => Some(AnalysisState::Affected),
+            "resolved" | "resolved_with_pedigree" => Some(AnalysisState::Fixed),
+            "in_triage" => Some(AnalysisState::UnderInvestigation),
+            "false_positive" => Some(AnalysisState::FalsePositive),
+            _ => None,
+        }
+    }
+
+    /// OpenVEX status. OpenVEX has no false positive status; those are published as
+    /// not affected because the vulnerable code is not present.
+    pub fn to_openvex(self) -> &'static str {
+        match self {
+            AnalysisState::NotAffected | AnalysisState::FalsePositive => "not_affected",
+            AnalysisState::Affected => "affected",
+            AnalysisState::Fixed => "fixed",
+            AnalysisState::UnderInvestigation => "under_investigation",
+        }
+    }
+
+    pub fn from_openvex(status: &str) -> Option<Self> {
+        match status {
+            "not_affected" => Some(AnalysisState::NotAffected),
+            "affected" => Some(AnalysisState::Affected),
+            "fixed" => Some(AnalysisState::Fixed),
+            "under_investigation" => Some(AnalysisState::UnderInvestigation),
+            _ => None,
+        }
+    }
+}
+
+pub fn is_justification(value: &str) -> bool {
+    CYCLONEDX_JUSTIFICATIONS.contains(&value) || OPENVEX_JUSTIFICATIONS.contains(&value)
+}
+
+/// The CycloneDX justification closest to a stored one.
+pub fn cyclonedx_justification(value: &str) -> Option<&'static str> {
+    if let Some(known) = CYCLONEDX_JUSTIFICATIONS
+        .iter()
+        .find(|known| **known == value)
+    {
+        return Some(known);
+    }
+    match value {
+        "component_not_present" | "vulnerable_code_not_present" => Some("code_not_present"),
+        "vulnerable_code_not_in_execute_path" => Some("code_not_reachable"),
+        "vulnerable_code_cannot_be_controlled_by_adversary" => Some("requires_environment"),
+        "inline_mitigations_already_exist" => Some("protected_by_mitigating_control"),
+        _ => None,
+    }
+}
+
+/// The OpenVEX justification closest to a stored one.
+pub fn openvex_justification(value: &str) -> Option<&'static str> {
+    if let Some(known) = OPENVEX_JUSTIFICATIONS.iter().find(|known| **known == value) {
+        return Some(known);
+    }
+    match value {
+        "code_not_present" => Some("vulnerable_code_not_present"),
+        "code_not_reachable" => Some("vulnerable_code_not_in_execute_path"),
+        "requires_configuration" | "requires_dependency" | "requires_environment" => {
+            Some("vulnerable_code_cannot_be_controlled_by_adversary")
+        }
+        "protected_by_compiler"
+        | "protected_at_runtime"
+        | "protected_at_perimeter"
+        | "protected_by_mitigating_control" => Some("inline_mitigations_already_exist"),
+        _ => None,
+    }
+}
+
+/// A requested analysis of one advisory on one component.
+#[derive(Debug, Clone)]
+pub struct AnalysisChange {
+    pub vulnerability_id: i32,
+    pub purl: String,
+    pub state: AnalysisState,
+    pub justification: Option<String>,
+    pub detail: Option<String>,
+}
+
+impl AnalysisChange {
+    /// Not affected needs a known justification; other states must not carry one.
+    pub fn validate(&self) -> Result<(), String> {
+        match (self.state, self.justification.as_deref()) {
+            (AnalysisState::NotAffected, None) => {
+                Err("not_affected requires a justification".to_string())
+            }
+            (AnalysisState::NotAffected, Some(justification))
+                if !is_justification(justification) =>
+            {
+                Err(format!("unknown justification {justification:?}"))
+            }
+            (AnalysisState::NotAffected, Some(_)) | (_, None) => Ok(()),
+            (state, Some(_)) => Err(format!("{} does not take a justification", state.as_str())),
+        }
+    }
+}
+
+/// Set the analysis of a project's component and append the change to its history.
+/// Returns `None` without writing when nothing differs from the current analysis.
+pub async fn record_analysis<C: ConnectionTrait>(
+    db: &C,
+    project_id: i32,
+    change: &AnalysisChange,
+    author: &Principal,
+    source: &str,
+) -> Result<Option<vulnerability_analysis::Model>, DbErr> {
+    let now = Utc::now();
+    let existing = vulnerability_analysis::Entity::find()
+        .filter(vulnerability_analysis::Column::ProjectId.eq(project_id))
+        .filter(vulnerability_analysis::Column::VulnerabilityId.eq(change.vulnerability_id))
+        .filter(vulnerability_analysis::Column::Purl.eq(change.purl.as_str()))
+        .one(db)
+        .await?;
+
+    let analysis = match existing {
+        Some(current)
+            if current.state == change.state.as_str()
+                && current.justification == change.justification
+                && current.detail == change.detail =>
+        {
+            return Ok(None);
+        }
+        Some(current) => {
+            let mut active: vulnerability_analysis::ActiveModel = current.into();
+            active.state = Set(change.state.as_str().to_string());
+            active.justification = Set(change.justification.clone());
+            active.detail = Set(change.detail.clone());
+            active.author_id = Set(author.id.clone());
+            active.author = Set(author.name.clone());
+            active.updated_at = Set(now);
+            active.update(db).await?
+        }
+        None => {
+            vulnerability_analysis::ActiveModel {
+                project_id: Set(project_id),
+                vulnerability_id: Set(change.vulnerability_id),
+                purl: Set(change.purl.clone()),
+                state: Set(change.state.as_str().to_string()),
+                justification: Set(change.justification.clone()),
+                detail: Set(change.detail.clone()),
+                author_id: Set(author.id.clone()),
+                author: Set(author.name.clone()),
+                created_at: Set(now),
+                updated_at: Set(now),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?
+        }
+    };
+
+    vulnerability_analysis_event::ActiveModel {
+        analysis_id: Set(analysis.id),
+        state: Set(analysis.state.clone()),
+        justification: Set(analysis.justification.clone()),
+        detail: Set(analysis.detail.clone()),
+        author_id: Set(author.id.clone()),
+        author: Set(author.name.clone()),
+        source: Set(source.to_string()),
+        created_at: Set(now),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+
+    Ok(Some(analysis))
+}
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::AnalysisState;
+use crate::export::vex::VexFormat;
+
+/// One assessment of a VEX document, independent of its format.
+#[derive(Debug, Clone)]
+pub struct VexStatement {
+    /// Advisory id as written in the document (CVE-..., GHSA-...).
+    pub vulnerability: String,
+    pub aliases: Vec<String>,
+    /// Affected components: purls, or bom-refs that did not resolve to one.
+    pub components: Vec<String>,
+    pub state: AnalysisState,
+    pub justification: Option<String>,
+    pub detail: Option<String>,
+}
+
+/// Read a CycloneDX VEX or OpenVEX document. Assessments in states check-deps does not
+/// model (e.g. CycloneDX without an analysis) are left out.
+pub fn parse(document: &Value) -> anyhow::Result<(VexFormat, Vec<VexStatement>)> {
+    if document.get("bomFormat").and_then(Value::as_str) == Some("CycloneDX") {
+        let bom: CycloneDxVex = serde_json::from_value(document.clone())?;
+        return Ok((VexFormat::CycloneDx, bom.statements()));
+    }
+    let context = document.get("@context").and_then(Value::as_str);
+    if context.is_some_and(|context| context.starts_with("https://openvex.dev/ns")) {
+        let vex: OpenVex = serde_json::from_value(document.clone())?;
+        return Ok((VexFormat::OpenVex, vex.statements()));
+    }
+    anyhow::bail!("not a CycloneDX or OpenVEX document")
+}
+
+#[derive(Debug, Deserialize)]
+struct CycloneDxVex {
+    #[serde(default)]
+    components: Vec<CycloneDxComponent>,
+    #[serde(default)]
+    vulnerabilities: Vec<CycloneDxVulnerability>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CycloneDxComponent {
+    #[serde(rename = "bom-ref")]
+    bom_ref: Option<String>,
+    purl: Option<String>,
+    #[serde(default)]
+    components: Vec<CycloneDxComponent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CycloneDxVulnerability {
+    id: String,
+    #[serde(default)]
+    references: Vec<CycloneDxReference>,
+    analysis: Option<CycloneDxAnalysis>,
+    #[serde(default)]
+    affects: Vec<CycloneDxAffects>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CycloneDxReference {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CycloneDxAnalysis {
+    state: Option<String>,
+    justification: Option<String>,
+    detail: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CycloneDxAffects {
+    #[serde(rename = "ref")]
+    reference: String,
+}
+
+impl CycloneDxVex {
+    fn statements(&self) -> Vec<VexStatement> {
+        let mut purls = HashMap::new();
+        collect_purls(&self.components, &mut purls);
+
+        self.vulnerabilities
+            .iter()
+            .filter_map(|vulnerability| {
+                let analysis = vulnerability.analysis.as_ref()?;
+                let state = AnalysisState::from_cyclonedx(analysis.state.as_deref()?)?;
+                let components = vulnerability
+                    .affects
+                    .iter()
+                    .map(|affects| {
+                        // BOM-Links (`urn:cdx:<serial>/<version>#<bom-ref>`) point into
+                        // the SBOM the VEX was written for.
+                        let reference = match affects.reference.split_once('#') {
+                            Some((prefix, bom_ref)) if prefix.starts_with("urn:cdx:") => bom_ref,
+                            _ => affects.reference.as_str(),
+                        };
+                        purls
+                            .get(reference)
+                            .cloned()
+                            .unwrap_or_else(|| reference.to_string())
+                    })
+                    .collect();
+                Some(VexStatement {
+                    vulnerability: vulnerability.id.clone(),
+                    aliases: vulnerability
+                        .references
+                        .iter()
+                        .map(|reference| reference.id.clone())
+                        .collect(),
+                    components,
+                    state,
+                    justification: analysis.justification.clone(),
+                    detail: analysis.detail.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+fn collect_purls(components: &[CycloneDxComponent], purls: &mut HashMap<String, String>) {
+    for component in components {
+        if let (Some(bom_ref), Some(purl)) = (&component.bom_ref, &component.purl) {
+            purls.insert(bom_ref.clone(), purl.clone());
+        }
+        collect_purls(&component.components, purls);
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenVex {
+    #[serde(default)]
+    statements: Vec<OpenVexStatement>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenVexStatement {
+    vulnerability: OpenVexVulnerability,
+    #[serde(default)]
+    products: Vec<OpenVexProduct>,
+    status: String,
+    justification: Option<String>,
+    impact_statement: Option<String>,
+    action_statement: Option<String>,
+    status_notes: Option<String>,
+}
+
+/// v0.2 uses an object, earlier versions a plain id.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OpenVexVulnerability {
+    Id(String),
+    Object {
+        name: String,
+        #[serde(default)]
+        aliases: Vec<String>,
+    },
+}
+
+/// v0.2 uses objects with optional subcomponents, earlier versions plain ids.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OpenVexProduct {
+    Id(String),
+    Object {
+        #[serde(rename = "@id")]
+        id: String,
+        #[serde(default)]
+        subcomponents: Vec<OpenVexProduct>,
+    },
+}
+
+impl OpenVexProduct {
+    /// The subcomponents when listed, else the product itself.
+    fn components(&self) -> Vec<String> {
+        match self {
+            OpenVexProduct::Id(id) => vec![id.clone()],
+            OpenVexProduct::Object { id, subcomponents } if subcomponents.is_empty() => {
+                vec![id.clone()]
+            }
+            OpenVexProduct::Object { subcomponents, .. } => subcomponents
+                .iter()
+                .flat_map(OpenVexProduct::components)
+                .collect(),
+        }
+    }
+}
+
+impl OpenVex {
+    fn statements(&self) -> Vec<VexStatement> {
+        self.statements
+            .iter()
+            .filter_map(|statement| {
+                let state = AnalysisState::from_openvex(&statement.status)?;
+                let (vulnerability, aliases) = match &statement.vulnerability {
+                    OpenVexVulnerability::Id(id) => (id.clone(), Vec::new()),
+                    OpenVexVulnerability::Object { name, aliases } => {
+                        (name.clone(), aliases.clone())
+                    }
+                };
+                let detail = statement
+                    .impact_statement
+                    .clone()
+                    .or_else(|| statement.action_statement.clone())
+                    .or_else(|| statement.status_notes.clone());
+                Some(VexStatement {
+                    vulnerability,
+                    aliases,
+                    components: statement
+                        .products
+                        .iter()
+                        .flat_map(OpenVexProduct::components)
+                        .collect(),
+                    state,
+                    justification: statement.justification.clone(),
+                    detail,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,82 @@
+mod generic;
+mod maven;
+mod pep440;
+mod range;
+mod rubygems;
+mod semver;
+mod vers;
+
+use std::cmp::Ordering;
+
+pub use range::VersionRange;
+
+#[derive(Debug, thiserror::Error)]
+pub enum VersionError {
+    #[error("invalid version range {0:?}: {1}")]
+    InvalidRange(String, String),
+}
+
+/// Version scheme used to order and match versions, picked from the purl type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ecosystem {
+    /// SemVer with Cargo requirement syntax.
+    Cargo,
+    /// SemVer with node-semver range syntax.
+    Npm,
+    /// PEP 440 versions and specifiers.
+    Pypi,
+    /// Maven `ComparableVersion` ordering and interval ranges.
+    Maven,
+    /// SemVer with a `v` prefix; pseudo-versions order as pre-releases.
+    Golang,
+    /// `Gem::Version` ordering and `~>` requirements.
+    Gem,
+    /// Dotted segments compared numerically where possible.
+    Generic,
+}
+
+impl Ecosystem {
+    pub fn from_purl_type(purl_type: &str) -> Self {
+        match purl_type.to_ascii_lowercase().as_str() {
+            "cargo" => Ecosystem::Cargo,
+            "npm" | "semver" => Ecosystem::Npm,
+            "pypi" => Ecosystem::Pypi,
+            "maven" => Ecosystem::Maven,
+            "golang" | "go" => Ecosystem::Golang,
+            "gem" => Ecosystem::Gem,
+            _ => Ecosystem::Generic,
+        }
+    }
+}
+
+/// Order two versions under the ecosystem's rules.
+///
+/// Versions the scheme cannot parse fall back to the generic segment comparison.
+pub fn compare(ecosystem: Ecosystem, a: &str, b: &str) -> Ordering {
+    match ecosystem {
+        Ecosystem::Cargo | Ecosystem::Npm | Ecosystem::Golang => semver::compare(a, b),
+        Ecosystem::Pypi => pep440::compare(a, b),
+        Ecosystem::Maven => maven::compare(a, b),
+        Ecosystem::Gem => rubygems::compare(a, b),
+        Ecosystem::Generic => generic::compare(a, b),
+    }
+}
+
+/// Like [`compare`], with a missing version ordered before any known one.
+pub fn compare_opt(ecosystem: Ecosystem, a: Option<&str>, b: Option<&str>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => compare(ecosystem, a, b),
+        (a, b) => a.is_some().cmp(&b.is_some()),
+    }
+}
+
+/// Whether the version is a pre-release (alpha, beta, rc, dev, snapshot, pseudo-version...).
+pub fn is_prerelease(ecosystem: Ecosystem, version: &str) -> bool {
+    match ecosystem {
+        Ecosystem::Cargo | Ecosystem::Npm | Ecosystem::Golang => semver::is_prerelease(version),
+        Ecosystem::Pypi => pep440::is_prerelease(version),
+        Ecosystem::Maven => maven::is_prerelease(version),
+        Ecosystem::Gem => rubygems::is_prerelease(version),
+        Ecosystem::Generic => generic::is_prerelease(version),
+    }
+}
@@ -0,0 +1,46 @@
+use std::cmp::Ordering;
+
+const PRERELEASE_MARKERS: [&str; 8] = [
+    "alpha",
+    "beta",
+    "rc",
+    "pre",
+    "preview",
+    "dev",
+    "snapshot",
+    "milestone",
+];
+
+/// Orders dotted versions segment by segment, numerically where both segments are numbers.
+pub(super) fn compare(a: &str, b: &str) -> Ordering {
+    let (left, right) = (segments(a), segments(b));
+
+    for (l, r) in left.iter().zip(right.iter()) {
+        let ord = match (l.parse::<u64>(), r.parse::<u64>()) {
+            (Ok(l), Ok(r)) => l.cmp(&r),
+            _ => l.cmp(r),
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+
+    left.len().cmp(&right.len())
+}
+
+pub(super) fn is_prerelease(version: &str) -> bool {
+    segments(version).iter().any(|segment| {
+        let segment = segment.to_ascii_lowercase();
+        PRERELEASE_MARKERS
+            .iter()
+            .any(|marker| segment.starts_with(marker))
+    })
+}
+
+fn segments(version: &str) -> Vec<&str> {
+    version
+        .trim()
+        .trim_start_matches(['v', 'V'])
+        .split(['.', '-', '+', '_'])
+        .collect()
+}
@@ -0,0 +1,287 @@
+use std::cmp::Ordering;
+
+use super::range::{comparators, Alternatives, Constraint, Op};
+
+/// Known qualifiers in release order; the empty string is the release itself.
+const QUALIFIERS: [&str; 7] = ["alpha", "beta", "milestone", "rc", "snapshot", "", "sp"];
+
+/// One item of Maven's `ComparableVersion`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    Int(u64),
+    Str(String),
+    List(Vec<Item>),
+}
+
+/// Port of `org.apache.maven.artifact.versioning.ComparableVersion` parsing.
+fn parse(version: &str) -> Vec<Item> {
+    let version = version.trim().to_ascii_lowercase();
+
+    // Stack of open lists; `-` and digit/letter transitions open a nested list.
+    let mut stack: Vec<Vec<Item>> = vec![Vec::new()];
+    let mut token = String::new();
+    let mut is_digit = false;
+
+    let flush = |stack: &mut Vec<Vec<Item>>, token: &mut String, is_digit: bool| {
+        let item = if is_digit {
+            Item::Int(token.parse().unwrap_or(0))
+        } else {
+            Item::Str(canonical_qualifier(token, false))
+        };
+        stack.last_mut().expect("root list").push(item);
+        token.clear();
+    };
+
+    let mut chars = version.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                if token.is_empty() {
+                    stack.last_mut().expect("root list").push(Item::Int(0));
+                } else {
+                    flush(&mut stack, &mut token, is_digit);
+                }
+            }
+            '-' => {
+                if token.is_empty() {
+                    stack.last_mut().expect("root list").push(Item::Int(0));
+                } else {
+                    flush(&mut stack, &mut token, is_digit);
+                }
+                stack.push(Vec::new());
+            }
+            c if c.is_ascii_digit() => {
+                if !is_digit && !token.is_empty() {
+                    // Letters directly followed by digits (`rc1`, `a1`) open a nested list.
+                    let item = Item::Str(canonical_qualifier(&token, true));
+                    stack.last_mut().expect("root list").push(item);
+                    token.clear();
+                    stack.push(Vec::new());
+                }
+                is_digit = true;
+                token.push(c);
+            }
+            c => {
+                if is_digit && !token.is_empty() {
+                    flush(&mut stack, &mut token, true);
+                    stack.push(Vec::new());
+                }
+                is_digit = false;
+                token.push(c);
+            }
+        }
+        if chars.peek().is_none() && !token.is_empty() {
+            flush(&mut stack, &mut token, is_digit);
+        }
+    }
+
+    // Close nested lists from the innermost out, trimming trailing null items.
+    while stack.len() > 1 {
+        let mut list = stack.pop().expect("nested list");
+        normalize(&mut list);
+        stack.last_mut().expect("root list").push(Item::List(list));
+    }
+    let mut root = stack.pop().unwrap_or_default();
+    normalize(&mut root);
+    root
+}
+
+fn canonical_qualifier(token: &str, followed_by_digit: bool) -> String {
+    match token {
+        "a" if followed_by_digit => "alpha".into(),
+        "b" if followed_by_digit => "beta".into(),
+        "m" if followed_by_digit => "milestone".into(),
+        "ga" | "final" | "release" => String::new(),
+        "cr" => "rc".into(),
+        other => other.into(),
+    }
+}
+
+fn is_null(item: &Item) -> bool {
+    match item {
+        Item::Int(n) => *n == 0,
+        Item::Str(s) => s.is_empty(),
+        Item::List(list) => list.is_empty(),
+    }
+}
+
+/// Drop null items (`0`, empty qualifiers, empty lists) from the end, looking past
+/// nested lists, as Maven does: `1.0-1` becomes `1-1`.
+fn normalize(list: &mut Vec<Item>) {
+    let mut i = list.len();
+    while i > 0 {
+        i -= 1;
+        if is_null(&list[i]) {
+            list.remove(i);
+        } else if !matches!(list[i], Item::List(_)) {
+            break;
+        }
+    }
+}
+
+fn qualifier_rank(qualifier: &str) -> (usize, &str) {
+    match QUALIFIERS.iter().position(|q| *q == qualifier) {
+        Some(index) => (index, ""),
+        // Unknown qualifiers sort after all known ones, alphabetically.
+        None => (QUALIFIERS.len(), qualifier),
+    }
+}
+
+/// Compare one item against another, `None` standing for a missing item.
+fn compare_items(left: Option<&Item>, right: Option<&Item>) -> Ordering {
+    match (left, right) {
+        (None, None) => Ordering::Equal,
+        (Some(item), None) => compare_to_null(item),
+        (None, Some(item)) => compare_to_null(item).reverse(),
+        (Some(Item::Int(a)), Some(Item::Int(b))) => a.cmp(b),
+        (Some(Item::Int(_)), Some(_)) => Ordering::Greater,
+        (Some(Item::Str(_)), Some(Item::Int(_))) => Ordering::Less,
+        (Some(Item::Str(a)), Some(Item::Str(b))) => qualifier_rank(a).cmp(&qualifier_rank(b)),
+        (Some(Item::Str(_)), Some(Item::List(_))) => Ordering::Less,
+        (Some(Item::List(_)), Some(Item::Int(_))) => Ordering::Less,
+        (Some(Item::List(_)), Some(Item::Str(_))) => Ordering::Greater,
+        (Some(Item::List(a)), Some(Item::List(b))) => compare_lists(a, b),
+    }
+}
+
+fn compare_to_null(item: &Item) -> Ordering {
+    match item {
+        Item::Int(n) => n.cmp(&0),
+        Item::Str(s) => qualifier_rank(s).cmp(&qualifier_rank("")),
+        Item::List(list) => list.first().map_or(Ordering::Equal, compare_to_null),
+    }
+}
+
+fn compare_lists(a: &[Item], b: &[Item]) -> Ordering {
+    (0..a.len().max(b.len()))
+        .map(|i| compare_items(a.get(i), b.get(i)))
+        .find(|ord| *ord != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+pub(super) fn compare(a: &str, b: &str) -> Ordering {
+    compare_lists(&parse(a), &parse(b))
+}
+
+/// Anything ordered before the plain release of the same version (alpha, beta, milestone,
+/// rc, snapshot) is a pre-release.
+pub(super) fn is_prerelease(version: &str) -> bool {
+    fn contains_prerelease(items: &[Item]) -> bool {
+        items.iter().any(|item| match item {
+            Item::Str(s) => qualifier_rank(s) < qualifier_rank(""),
+            Item::List(list) => contains_prerelease(list),
+            Item::Int(_) => false,
+        })
+    }
+    contains_prerelease(&parse(version))
+}
+
+/// Maven version ranges: `[1.0,2.0)`, `(,1.0]`, `[1.2]` and unions such as
+/// `(,1.0],[1.2,)`. Plain comparators are accepted too; a bare version is an exact match.
+pub(super) fn range(input: &str) -> Result<Alternatives, String> {
+    if !input.starts_with(['[', '(']) {
+        return comparators(input);
+    }
+
+    let mut alternatives = Vec::new();
+    let mut rest = input.trim();
+    while !rest.is_empty() {
+        let close = rest
+            .find([']', ')'])
+            .ok_or_else(|| format!("unclosed interval in {input:?}"))?;
+        let (interval, tail) = rest.split_at(close + 1);
+        alternatives.push(parse_interval(interval)?);
+        rest = tail.trim_start_matches([',', ' ']).trim();
+    }
+    Ok(alternatives)
+}
+
+fn parse_interval(interval: &str) -> Result<Vec<Constraint>, String> {
+    if !interval.starts_with(['[', '(']) {
+        return Err(format!("invalid interval {interval:?}"));
+    }
+    let lower_inclusive = interval.starts_with('[');
+    let upper_inclusive = interval.ends_with(']');
+    let body = &interval[1..interval.len() - 1];
+
+    let Some((lower, upper)) = body.split_once(',') else {
+        let version = body.trim();
+        if version.is_empty() || !(lower_inclusive && upper_inclusive) {
+            return Err(format!("invalid interval {interval:?}"));
+        }
+        return Ok(vec![Constraint::new(Op::Eq, version)]);
+    };
+
+    let mut set = Vec::new();
+    if !lower.trim().is_empty() {
+        let op = if lower_inclusive { Op::Ge } else { Op::Gt };
+        set.push(Constraint::new(op, lower.trim()));
+    }
+    if !upper.trim().is_empty() {
+        let op = if upper_inclusive { Op::Le } else { Op::Lt };
+        set.push(Constraint::new(op, upper.trim()));
+    }
+    Ok(set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_qualifiers() {
+        let ascending = [
+            "1-alpha",
+            "1-alpha-1",
+            "1-beta",
+            "1-milestone",
+            "1-rc",
+            "1-snapshot",
+            "1",
+            "1-sp",
+            "1-foo",
+            "1-1",
+            "1.0.1",
+            "1.1",
+        ];
+        for pair in ascending.windows(2) {
+            assert_eq!(compare(pair[0], pair[1]), Ordering::Less, "{pair:?}");
+            assert_eq!(compare(pair[1], pair[0]), Ordering::Greater, "{pair:?}");
+        }
+    }
+
+    #[test]
+    fn pads_and_aliases() {
+        let equal = [
+            ("1.0", "1"),
+            ("1.0.0", "1"),
+            ("1-ga", "1"),
+            ("1-final", "1.0"),
+            ("1.0-release", "1"),
+            ("1-cr1", "1-rc1"),
+            ("1-a1", "1-alpha-1"),
+            ("1-b2", "1-beta-2"),
+            ("1-m3", "1-milestone-3"),
+            ("1.0-SNAPSHOT", "1-snapshot"),
+            ("1.0-1", "1-1"),
+        ];
+        for (a, b) in equal {
+            assert_eq!(compare(a, b), Ordering::Equal, "{a} == {b}");
+        }
+    }
+
+    #[test]
+    fn detects_prereleases() {
+        let cases = [
+            ("1.0-alpha-1", true),
+            ("1.0-RC1", true),
+            ("2.0-SNAPSHOT", true),
+            ("1.0", false),
+            ("1.0-sp1", false),
+            ("1.0-jre", false),
+        ];
+        for (version, expected) in cases {
+            assert_eq!(is_prerelease(version), expected, "{version}");
+        }
+    }
+}
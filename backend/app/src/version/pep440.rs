@@ -0,0 +1,328 @@
+use std::cmp::Ordering;
+
+use super::{
+    generic,
+    range::{tokens, Alternatives, Constraint, Op},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum LocalSegment {
+    Alpha(String),
+    Numeric(u64),
+}
+
+/// Sort key of a PEP 440 version; field order matches the comparison order.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Pep440 {
+    epoch: u64,
+    /// Release segments without trailing zeros, so `1.0` == `1.0.0`.
+    release: Vec<u64>,
+    /// `(phase, number)`; -1 for dev-only releases, 3 for final releases.
+    pre: (i8, u64),
+    post: Option<u64>,
+    /// `(0, n)` for `.devN`, `(1, 0)` when absent so dev releases sort first.
+    dev: (u8, u64),
+    local: Option<Vec<LocalSegment>>,
+}
+
+/// Parse a version under the PEP 440 normalisation rules (alternate spellings,
+/// separators, implicit numbers, `v` prefix).
+fn parse(version: &str) -> Option<Pep440> {
+    let lower = version.trim().to_ascii_lowercase();
+    let lower = lower.strip_prefix('v').unwrap_or(&lower);
+    let (public, local) = match lower.split_once('+') {
+        Some((public, local)) => (public, Some(local)),
+        None => (lower, None),
+    };
+
+    let (epoch, rest) = match public.split_once('!') {
+        Some((epoch, rest)) => (epoch.parse().ok()?, rest),
+        None => (0, public),
+    };
+
+    let mut cursor = Cursor { rest };
+    let mut release = Vec::new();
+    loop {
+        release.push(cursor.number()?);
+        if !cursor.rest.starts_with('.') || !cursor.peek_digit_after(1) {
+            break;
+        }
+        cursor.advance(1);
+    }
+
+    let mut pre = None;
+    if let Some(phase) = cursor.tagged(&[
+        ("alpha", 0),
+        ("a", 0),
+        ("beta", 1),
+        ("b", 1),
+        ("preview", 2),
+        ("pre", 2),
+        ("rc", 2),
+        ("c", 2),
+    ]) {
+        cursor.separator();
+        pre = Some((phase, cursor.number().unwrap_or(0)));
+    }
+
+    let mut post = None;
+    if cursor.rest.starts_with('-') && cursor.peek_digit_after(1) {
+        cursor.advance(1);
+        post = cursor.number();
+    } else if cursor
+        .tagged(&[("post", ()), ("rev", ()), ("r", ())])
+        .is_some()
+    {
+        cursor.separator();
+        post = Some(cursor.number().unwrap_or(0));
+    }
+
+    let mut dev = None;
+    if cursor.tagged(&[("dev", ())]).is_some() {
+        cursor.separator();
+        dev = Some(cursor.number().unwrap_or(0));
+    }
+
+    if !cursor.rest.is_empty() {
+        return None;
+    }
+
+    while release.len() > 1 && release.last() == Some(&0) {
+        release.pop();
+    }
+
+    let pre = match (pre, post, dev) {
+        (Some(pre), _, _) => pre,
+        (None, None, Some(_)) => (-1, 0),
+        _ => (3, 0),
+    };
+
+    let local = local.map(|local| {
+        local
+            .split(['.', '-', '_'])
+            .map(|seg| match seg.parse::<u64>() {
+                Ok(n) => LocalSegment::Numeric(n),
+                Err(_) => LocalSegment::Alpha(seg.to_string()),
+            })
+            .collect()
+    });
+
+    Some(Pep440 {
+        epoch,
+        release,
+        pre,
+        post,
+        dev: dev.map_or((1, 0), |n| (0, n)),
+        local,
+    })
+}
+
+struct Cursor<'a> {
+    rest: &'a str,
+}
+
+impl Cursor<'_> {
+    fn advance(&mut self, n: usize) {
+        self.rest = &self.rest[n..];
+    }
+
+    fn peek_digit_after(&self, n: usize) -> bool {
+        self.rest[n..].starts_with(|c: char| c.is_ascii_digit())
+    }
+
+    fn number(&mut self) -> Option<u64> {
+        let end = self
+            .rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest.len());
+        if end == 0 {
+            return None;
+        }
+        let value = self.rest[..end].parse().ok()?;
+        self.advance(end);
+        Some(value)
+    }
+
+    fn separator(&mut self) {
+        if self.rest.starts_with(['.', '-', '_']) {
+            self.advance(1);
+        }
+    }
+
+    /// Consume an optional separator followed by one of `words`, or nothing at all.
+    fn tagged<T: Copy>(&mut self, words: &[(&str, T)]) -> Option<T> {
+        let start = self.rest;
+        self.separator();
+        match words.iter().find(|(w, _)| self.rest.starts_with(w)) {
+            Some((word, value)) => {
+                self.advance(word.len());
+                Some(*value)
+            }
+            None => {
+                self.rest = start;
+                None
+            }
+        }
+    }
+}
+
+pub(super) fn compare(a: &str, b: &str) -> Ordering {
+    match (parse(a), parse(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        _ => generic::compare(a, b),
+    }
+}
+
+pub(super) fn is_prerelease(version: &str) -> bool {
+    match parse(version) {
+        Some(v) => v.pre.0 < 3 || v.dev.0 == 0,
+        None => generic::is_prerelease(version),
+    }
+}
+
+/// Release prefix of a wildcard bound (`1.4` of `1.4.*`) bumped to the next release,
+/// as the smallest version that no longer matches.
+fn bump(prefix: &[&str]) -> Result<String, String> {
+    let mut numbers = prefix
+        .iter()
+        .map(|p| {
+            p.parse::<u64>()
+                .map_err(|_| format!("invalid release segment {p:?}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let last = numbers.last_mut().ok_or("empty release")?;
+    *last += 1;
+    Ok(numbers
+        .iter()
+        .map(u64::to_string)
+        .collect::<Vec<_>>()
+        .join("."))
+}
+
+fn specifier(symbol: &str, version: &str) -> Result<Vec<Constraint>, String> {
+    if let Some(prefix) = version.strip_suffix(".*") {
+        let segments: Vec<&str> = prefix.split('.').collect();
+        let lower = format!("{prefix}.dev0");
+        let upper = format!("{}.dev0", bump(&segments)?);
+        return match symbol {
+            "==" | "" => Ok(vec![
+                Constraint::new(Op::Ge, lower),
+                Constraint::new(Op::Lt, upper),
+            ]),
+            "!=" => Ok(vec![Constraint::Outside(lower, upper)]),
+            other => Err(format!("wildcard not allowed with {other:?}")),
+        };
+    }
+
+    match symbol {
+        // Compatible release: `~=1.4.5` is `>=1.4.5, ==1.4.*`.
+        "~=" => {
+            let release: Vec<&str> = version
+                .split(|c: char| !c.is_ascii_digit() && c != '.')
+                .next()
+                .unwrap_or_default()
+                .split('.')
+                .collect();
+            if release.len() < 2 {
+                return Err(format!(
+                    "~= needs at least two release segments: {version:?}"
+                ));
+            }
+            let upper = bump(&release[..release.len() - 1])?;
+            Ok(vec![
+                Constraint::new(Op::Ge, version),
+                Constraint::new(Op::Lt, format!("{upper}.dev0")),
+            ])
+        }
+        "===" => Ok(vec![Constraint::new(Op::Eq, version)]),
+        other => Op::from_symbol(other)
+            .map(|op| vec![Constraint::new(op, version)])
+            .ok_or_else(|| format!("unsupported operator {other:?}")),
+    }
+}
+
+/// PEP 440 specifier sets (`>=1.0,!=1.3.*,<2`); `||` is accepted for alternatives.
+pub(super) fn specifiers(input: &str) -> Result<Alternatives, String> {
+    input
+        .split("||")
+        .map(|alternative| {
+            let mut set = Vec::new();
+            for (symbol, version) in tokens(alternative)? {
+                set.extend(specifier(symbol, version)?);
+            }
+            Ok(set)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_as_in_pep_440() {
+        // The example ordering from the PEP, with an epoch on top.
+        let ascending = [
+            "1.0.dev456",
+            "1.0a1",
+            "1.0a2.dev456",
+            "1.0a12.dev456",
+            "1.0a12",
+            "1.0b1.dev456",
+            "1.0b2",
+            "1.0b2.post345.dev456",
+            "1.0b2.post345",
+            "1.0rc1.dev456",
+            "1.0rc1",
+            "1.0",
+            "1.0+abc.5",
+            "1.0+abc.7",
+            "1.0+5",
+            "1.0.post456.dev34",
+            "1.0.post456",
+            "1.1.dev1",
+            "2.0",
+            "1!0.1",
+        ];
+        for pair in ascending.windows(2) {
+            assert_eq!(compare(pair[0], pair[1]), Ordering::Less, "{pair:?}");
+            assert_eq!(compare(pair[1], pair[0]), Ordering::Greater, "{pair:?}");
+        }
+    }
+
+    #[test]
+    fn normalises_alternate_spellings() {
+        let equal = [
+            ("1.0", "1.0.0"),
+            ("v1.0", "1.0"),
+            ("0!1.0", "1.0"),
+            ("1.0alpha1", "1.0a1"),
+            ("1.0-beta.2", "1.0b2"),
+            ("1.0c1", "1.0rc1"),
+            ("1.0pre", "1.0rc0"),
+            ("1.0-1", "1.0.post1"),
+            ("1.0.rev2", "1.0.post2"),
+            ("1.0.post", "1.0.post0"),
+            ("1.0-dev", "1.0.dev0"),
+        ];
+        for (a, b) in equal {
+            assert_eq!(compare(a, b), Ordering::Equal, "{a} == {b}");
+        }
+    }
+
+    #[test]
+    fn detects_prereleases() {
+        let cases = [
+            ("1.0.dev1", true),
+            ("1.0a1", true),
+            ("1.0rc2", true),
+            ("1.0.post1.dev1", true),
+            ("1.0", false),
+            ("1.0.post1", false),
+            ("1!2.0", false),
+        ];
+        for (version, expected) in cases {
+            assert_eq!(is_prerelease(version), expected, "{version}");
+        }
+    }
+}
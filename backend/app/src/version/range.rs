@@ -0,0 +1,188 @@
+use std::{cmp::Ordering, fmt};
+
+use super::{compare, maven, pep440, rubygems, semver, vers, Ecosystem, VersionError};
+
+/// Longest first so `<=` is not read as `<`.
+const OPERATORS: [&str; 12] = [
+    "===", "~=", "~>", "<=", ">=", "==", "!=", "<", ">", "=", "^", "~",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Op {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Constraint {
+    Cmp(Op, String),
+    /// Anything outside the half-open interval `[lower, upper)`.
+    Outside(String, String),
+}
+
+impl Constraint {
+    pub(super) fn new(op: Op, version: impl Into<String>) -> Self {
+        Constraint::Cmp(op, version.into())
+    }
+
+    fn matches(&self, ecosystem: Ecosystem, version: &str) -> bool {
+        match self {
+            Constraint::Cmp(op, bound) => op.holds(compare(ecosystem, version, bound)),
+            Constraint::Outside(lower, upper) => {
+                compare(ecosystem, version, lower) == Ordering::Less
+                    || compare(ecosystem, version, upper) != Ordering::Less
+            }
+        }
+    }
+}
+
+impl Op {
+    pub(super) fn holds(self, ord: Ordering) -> bool {
+        match self {
+            Op::Lt => ord == Ordering::Less,
+            Op::Le => ord != Ordering::Greater,
+            Op::Gt => ord == Ordering::Greater,
+            Op::Ge => ord != Ordering::Less,
+            Op::Eq => ord == Ordering::Equal,
+            Op::Ne => ord != Ordering::Equal,
+        }
+    }
+
+    /// Plain comparison operators shared by every syntax; a bare version means equality.
+    pub(super) fn from_symbol(symbol: &str) -> Option<Op> {
+        match symbol {
+            "" | "=" | "==" => Some(Op::Eq),
+            "!=" => Some(Op::Ne),
+            "<" => Some(Op::Lt),
+            "<=" => Some(Op::Le),
+            ">" => Some(Op::Gt),
+            ">=" => Some(Op::Ge),
+            _ => None,
+        }
+    }
+}
+
+/// Any of the inner sets must hold; every constraint of a set must hold.
+pub(super) type Alternatives = Vec<Vec<Constraint>>;
+
+/// A parsed version range that can be evaluated against concrete versions.
+///
+/// Accepts the ecosystem's native syntax (Cargo and node-semver requirements, PEP 440
+/// specifiers, Maven intervals, RubyGems requirements), plain comparators such as
+/// `>=2.0.0, <2.17.1 || =1.2.3`, and `vers:` URIs like `vers:npm/>=1.0.0|<2.0.0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRange {
+    ecosystem: Ecosystem,
+    raw: String,
+    alternatives: Alternatives,
+}
+
+impl VersionRange {
+    pub fn parse(ecosystem: Ecosystem, input: &str) -> Result<Self, VersionError> {
+        let raw = input.trim();
+        let invalid = |reason: String| VersionError::InvalidRange(input.to_string(), reason);
+
+        let (ecosystem, alternatives) = match raw.strip_prefix("vers:") {
+            Some(rest) => vers::parse(rest).map_err(invalid)?,
+            None => {
+                let alternatives = match ecosystem {
+                    Ecosystem::Cargo => semver::cargo_range(raw),
+                    Ecosystem::Npm => semver::npm_range(raw),
+                    Ecosystem::Pypi => pep440::specifiers(raw),
+                    Ecosystem::Maven => maven::range(raw),
+                    Ecosystem::Gem => rubygems::requirements(raw),
+                    Ecosystem::Golang | Ecosystem::Generic => comparators(raw),
+                }
+                .map_err(invalid)?;
+                (ecosystem, alternatives)
+            }
+        };
+
+        if alternatives.is_empty() {
+            return Err(invalid("empty range".into()));
+        }
+
+        Ok(Self {
+            ecosystem,
+            raw: raw.to_string(),
+            alternatives,
+        })
+    }
+
+    /// Scheme the range is evaluated with; a `vers:` URI overrides the one passed to `parse`.
+    pub fn ecosystem(&self) -> Ecosystem {
+        self.ecosystem
+    }
+
+    pub fn contains(&self, version: &str) -> bool {
+        self.alternatives
+            .iter()
+            .any(|set| set.iter().all(|c| c.matches(self.ecosystem, version)))
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+/// Plain comparator syntax: `>=1.0, <2.0 || =3.1`.
+pub(super) fn comparators(input: &str) -> Result<Alternatives, String> {
+    input
+        .split("||")
+        .map(|alternative| {
+            tokens(alternative)?
+                .into_iter()
+                .map(|(symbol, version)| {
+                    Op::from_symbol(symbol)
+                        .map(|op| Constraint::new(op, version))
+                        .ok_or_else(|| format!("unsupported operator {symbol:?}"))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Split one alternative into `(operator, version)` pairs.
+///
+/// Comparators may be separated by commas or whitespace, and an operator may be
+/// separated from its version by spaces (`< 2.0`). The operator is empty for a bare version.
+pub(super) fn tokens(alternative: &str) -> Result<Vec<(&str, &str)>, String> {
+    let mut pairs = Vec::new();
+    let mut words = alternative
+        .split([',', ' ', '\t'])
+        .map(str::trim)
+        .filter(|w| !w.is_empty());
+
+    while let Some(word) = words.next() {
+        let (symbol, rest) = split_operator(word);
+        let version = if rest.is_empty() {
+            words
+                .next()
+                .ok_or_else(|| format!("operator {symbol:?} without version"))?
+        } else {
+            rest
+        };
+        if !split_operator(version).0.is_empty() {
+            return Err(format!("unexpected operator in {version:?}"));
+        }
+        pairs.push((symbol, version));
+    }
+
+    if pairs.is_empty() {
+        return Err("empty constraint".into());
+    }
+    Ok(pairs)
+}
+
+fn split_operator(word: &str) -> (&str, &str) {
+    OPERATORS
+        .iter()
+        .find_map(|op| word.strip_prefix(op).map(|rest| (*op, rest.trim())))
+        .unwrap_or(("", word))
+}
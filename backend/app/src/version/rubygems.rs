@@ -0,0 +1,208 @@
+use std::cmp::Ordering;
+
+use super::range::{tokens, Alternatives, Constraint, Op};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Num(u64),
+    Str(String),
+}
+
+/// `Gem::Version#segments`: runs of digits or letters, with `-` read as `.pre.`.
+fn segments(version: &str) -> Vec<Segment> {
+    let version = version.trim().replace('-', ".pre.");
+    let mut segments = Vec::new();
+    let mut current = String::new();
+
+    let mut push = |current: &mut String| {
+        if current.is_empty() {
+            return;
+        }
+        segments.push(match current.parse::<u64>() {
+            Ok(n) => Segment::Num(n),
+            Err(_) => Segment::Str(std::mem::take(current)),
+        });
+        current.clear();
+    };
+
+    for c in version.chars() {
+        if !c.is_ascii_alphanumeric() {
+            push(&mut current);
+            continue;
+        }
+        let switches = current
+            .chars()
+            .last()
+            .is_some_and(|last| last.is_ascii_digit() != c.is_ascii_digit());
+        if switches {
+            push(&mut current);
+        }
+        current.push(c);
+    }
+    push(&mut current);
+    segments
+}
+
+/// `Gem::Version#canonical_segments`: trailing zeros dropped from the release part and
+/// from the pre-release part separately.
+fn canonical(version: &str) -> Vec<Segment> {
+    let mut all = segments(version);
+    let split = all
+        .iter()
+        .position(|s| matches!(s, Segment::Str(_)))
+        .unwrap_or(all.len());
+    let mut pre = all.split_off(split);
+
+    for part in [&mut all, &mut pre] {
+        while part.last() == Some(&Segment::Num(0)) {
+            part.pop();
+        }
+    }
+    all.extend(pre);
+    all
+}
+
+pub(super) fn compare(a: &str, b: &str) -> Ordering {
+    let (left, right) = (canonical(a), canonical(b));
+    let zero = Segment::Num(0);
+
+    for i in 0..left.len().max(right.len()) {
+        let l = left.get(i).unwrap_or(&zero);
+        let r = right.get(i).unwrap_or(&zero);
+        let ord = match (l, r) {
+            (Segment::Num(l), Segment::Num(r)) => l.cmp(r),
+            (Segment::Str(_), Segment::Num(_)) => Ordering::Less,
+            (Segment::Num(_), Segment::Str(_)) => Ordering::Greater,
+            (Segment::Str(l), Segment::Str(r)) => l.cmp(r),
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    Ordering::Equal
+}
+
+/// Any letter makes a gem version a pre-release.
+pub(super) fn is_prerelease(version: &str) -> bool {
+    version.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+/// `Gem::Version#bump`: drop pre-release segments and the last release segment, then
+/// increment what is left (`1.2.3` -> `1.3`, `1.2` -> `2`).
+fn bump(version: &str) -> Result<String, String> {
+    let mut numbers: Vec<u64> = segments(version)
+        .into_iter()
+        .map_while(|s| match s {
+            Segment::Num(n) => Some(n),
+            Segment::Str(_) => None,
+        })
+        .collect();
+    if numbers.len() > 1 {
+        numbers.pop();
+    }
+    let last = numbers
+        .last_mut()
+        .ok_or_else(|| format!("cannot bump {version:?}"))?;
+    *last += 1;
+    Ok(numbers
+        .iter()
+        .map(u64::to_string)
+        .collect::<Vec<_>>()
+        .join("."))
+}
+
+/// Gem requirements (`~> 1.2, >= 1.2.3`); `||` is accepted for alternatives.
+pub(super) fn requirements(input: &str) -> Result<Alternatives, String> {
+    input
+        .split("||")
+        .map(|alternative| {
+            let mut set = Vec::new();
+            for (symbol, version) in tokens(alternative)? {
+                match symbol {
+                    "~>" => {
+                        set.push(Constraint::new(Op::Ge, version));
+                        set.push(Constraint::new(Op::Lt, bump(version)?));
+                    }
+                    other => set.push(
+                        Op::from_symbol(other)
+                            .map(|op| Constraint::new(op, version))
+                            .ok_or_else(|| format!("unsupported operator {other:?}"))?,
+                    ),
+                }
+            }
+            Ok(set)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::{Ecosystem, VersionRange};
+
+    #[test]
+    fn orders_prereleases_before_releases() {
+        let ascending = [
+            "1.0.0.a",
+            "1.0.0.a2",
+            "1.0.0.b1",
+            "1.0.0-rc1",
+            "1.0.0.rc1",
+            "1.0.0.rc2",
+            "1.0.0",
+            "1.0.1",
+            "1.9",
+            "1.10",
+        ];
+        for pair in ascending.windows(2) {
+            assert_eq!(compare(pair[0], pair[1]), Ordering::Less, "{pair:?}");
+            assert_eq!(compare(pair[1], pair[0]), Ordering::Greater, "{pair:?}");
+        }
+    }
+
+    #[test]
+    fn drops_trailing_zeros() {
+        let equal = [
+            ("1.0", "1.0.0"),
+            ("1", "1.0.0.0"),
+            ("1.0.a", "1.a"),
+            ("1.0.0.rc.0", "1.rc"),
+        ];
+        for (a, b) in equal {
+            assert_eq!(compare(a, b), Ordering::Equal, "{a} == {b}");
+        }
+    }
+
+    #[test]
+    fn detects_prereleases() {
+        let cases = [
+            ("1.0.0.pre", true),
+            ("1.0.0-rc1", true),
+            ("2.0.0.beta3", true),
+            ("1.0.0", false),
+        ];
+        for (version, expected) in cases {
+            assert_eq!(is_prerelease(version), expected, "{version}");
+        }
+    }
+
+    #[test]
+    fn pessimistic_requirements() {
+        let cases = [
+            ("~> 1.2", "1.9.9", true),
+            ("~> 1.2", "2.0", false),
+            ("~> 1.2.3", "1.2.9", true),
+            ("~> 1.2.3", "1.3.0", false),
+            ("~> 1.2.3", "1.2.2", false),
+            ("~> 1.2, >= 1.2.5", "1.2.4", false),
+        ];
+        for (requirement, version, expected) in cases {
+            let range = VersionRange::parse(Ecosystem::Gem, requirement).unwrap();
+            assert_eq!(
+                range.contains(version),
+                expected,
+                "{version} in {requirement}"
+            );
+        }
+    }
+}
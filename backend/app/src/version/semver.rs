@@ -0,0 +1,335 @@
+use std::cmp::Ordering;
+
+use super::{
+    generic,
+    range::{tokens, Alternatives, Constraint, Op},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Identifier {
+    Numeric(u64),
+    Alpha(String),
+}
+
+impl Ord for Identifier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Identifier::Numeric(a), Identifier::Numeric(b)) => a.cmp(b),
+            (Identifier::Numeric(_), Identifier::Alpha(_)) => Ordering::Less,
+            (Identifier::Alpha(_), Identifier::Numeric(_)) => Ordering::Greater,
+            (Identifier::Alpha(a), Identifier::Alpha(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SemVer {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: Vec<Identifier>,
+}
+
+impl Ord for SemVer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                // A pre-release sorts before the release it precedes.
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            })
+    }
+}
+
+impl PartialOrd for SemVer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Lenient SemVer 2.0 parse: accepts a `v`/`=` prefix and a missing minor or patch.
+/// Build metadata (including Go's `+incompatible`) is ignored.
+fn parse(version: &str) -> Option<SemVer> {
+    let version = version.trim().trim_start_matches(['v', 'V', '=']);
+    let version = version.split('+').next()?;
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+
+    let mut parts = core.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+    let patch = parts.next().map_or(Some(0), |p| p.parse().ok())?;
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let pre = match pre {
+        Some("") => return None,
+        Some(pre) => pre
+            .split('.')
+            .map(|id| match id.parse::<u64>() {
+                Ok(n) => Identifier::Numeric(n),
+                _ => Identifier::Alpha(id.to_string()),
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    Some(SemVer {
+        major,
+        minor,
+        patch,
+        pre,
+    })
+}
+
+pub(super) fn compare(a: &str, b: &str) -> Ordering {
+    match (parse(a), parse(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        _ => generic::compare(a, b),
+    }
+}
+
+/// Go pseudo-versions (`v0.0.0-20191109021931-daa7c04131f5`) are pre-releases too.
+pub(super) fn is_prerelease(version: &str) -> bool {
+    match parse(version) {
+        Some(v) => !v.pre.is_empty(),
+        None => generic::is_prerelease(version),
+    }
+}
+
+/// A version with optional trailing components, as written in requirements (`1`, `1.2.x`).
+#[derive(Debug, Clone, Default)]
+struct Partial {
+    major: Option<u64>,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    /// Pre-release suffix including the leading `-`, only kept for full versions.
+    pre: String,
+}
+
+impl Partial {
+    fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim_start_matches(['v', 'V']);
+        let input = input.split('+').next().unwrap_or_default();
+        let (core, pre) = match input.split_once('-') {
+            Some((core, pre)) => (core, format!("-{pre}")),
+            None => (input, String::new()),
+        };
+
+        let mut numbers = Vec::new();
+        for part in core.split('.') {
+            if matches!(part, "*" | "x" | "X") {
+                break;
+            }
+            numbers.push(
+                part.parse::<u64>()
+                    .map_err(|_| format!("invalid version component {part:?}"))?,
+            );
+        }
+        if numbers.len() > 3 {
+            return Err(format!("too many version components in {input:?}"));
+        }
+
+        Ok(Partial {
+            major: numbers.first().copied(),
+            minor: numbers.get(1).copied(),
+            patch: numbers.get(2).copied(),
+            pre: if numbers.len() == 3 {
+                pre
+            } else {
+                String::new()
+            },
+        })
+    }
+
+    fn floor(&self) -> String {
+        format!(
+            "{}.{}.{}{}",
+            self.major.unwrap_or(0),
+            self.minor.unwrap_or(0),
+            self.patch.unwrap_or(0),
+            self.pre
+        )
+    }
+}
+
+/// Lowest pre-release of a version, so `<2.0.0-0` excludes `2.0.0-beta` as well.
+fn below(major: u64, minor: u64, patch: u64) -> Constraint {
+    Constraint::new(Op::Lt, format!("{major}.{minor}.{patch}-0"))
+}
+
+/// Translate one `(operator, version)` requirement into plain constraints.
+///
+/// `bare_is_caret` selects Cargo's meaning of a bare version; npm treats it as an x-range.
+fn expand(symbol: &str, version: &str, bare_is_caret: bool) -> Result<Vec<Constraint>, String> {
+    let v = Partial::parse(version)?;
+    let symbol = match symbol {
+        "" if bare_is_caret => "^",
+        "" | "==" => "=",
+        other => other,
+    };
+
+    let Some(major) = v.major else {
+        // `*`, `x` and friends: anything, except that `<*` / `>*` match nothing.
+        return Ok(match symbol {
+            "<" | ">" => vec![below(0, 0, 0)],
+            _ => Vec::new(),
+        });
+    };
+    let floor = Constraint::new(Op::Ge, v.floor());
+
+    let constraints = match (symbol, v.minor, v.patch) {
+        ("=", None, _) => vec![floor, below(major + 1, 0, 0)],
+        ("=", Some(minor), None) => vec![floor, below(major, minor + 1, 0)],
+        ("=", Some(_), Some(_)) => vec![Constraint::new(Op::Eq, v.floor())],
+
+        ("^", _, _) if major > 0 || v.minor.is_none() => vec![floor, below(major + 1, 0, 0)],
+        ("^", Some(minor), _) if minor > 0 || v.patch.is_none() => {
+            vec![floor, below(0, minor + 1, 0)]
+        }
+        ("^", Some(_), Some(patch)) => vec![floor, below(0, 0, patch + 1)],
+
+        ("~", None, _) => vec![floor, below(major + 1, 0, 0)],
+        ("~", Some(minor), _) => vec![floor, below(major, minor + 1, 0)],
+
+        (">", None, _) => vec![Constraint::new(Op::Ge, format!("{}.0.0", major + 1))],
+        (">", Some(minor), None) => {
+            vec![Constraint::new(Op::Ge, format!("{major}.{}.0", minor + 1))]
+        }
+        (">", Some(_), Some(_)) => vec![Constraint::new(Op::Gt, v.floor())],
+
+        (">=", _, _) => vec![floor],
+
+        ("<", _, Some(_)) => vec![Constraint::new(Op::Lt, v.floor())],
+        ("<", _, None) => vec![Constraint::new(Op::Lt, format!("{}-0", v.floor()))],
+
+        ("<=", None, _) => vec![below(major + 1, 0, 0)],
+        ("<=", Some(minor), None) => vec![below(major, minor + 1, 0)],
+        ("<=", Some(_), Some(_)) => vec![Constraint::new(Op::Le, v.floor())],
+
+        ("!=", _, _) => vec![Constraint::new(Op::Ne, v.floor())],
+
+        (other, _, _) => return Err(format!("unsupported operator {other:?}")),
+    };
+
+    Ok(constraints)
+}
+
+/// Cargo requirement syntax: comma-separated comparators where a bare version means `^`.
+pub(super) fn cargo_range(input: &str) -> Result<Alternatives, String> {
+    input
+        .split("||")
+        .map(|alternative| {
+            let mut set = Vec::new();
+            for (symbol, version) in tokens(alternative)? {
+                set.extend(expand(symbol, version, true)?);
+            }
+            Ok(set)
+        })
+        .collect()
+}
+
+/// node-semver range syntax: `||` alternatives, whitespace-separated comparators,
+/// hyphen ranges (`1.2.3 - 2.3.4`), x-ranges, `^` and `~`.
+pub(super) fn npm_range(input: &str) -> Result<Alternatives, String> {
+    input
+        .split("||")
+        .map(|alternative| {
+            let alternative = alternative.trim();
+            if alternative.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            if let Some((low, high)) = alternative.split_once(" - ") {
+                let mut set = expand(">=", low.trim(), false)?;
+                set.extend(expand("<=", high.trim(), false)?);
+                return Ok(set);
+            }
+
+            let mut set = Vec::new();
+            for (symbol, version) in tokens(alternative)? {
+                set.extend(expand(symbol, version, false)?);
+            }
+            Ok(set)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::version::{Ecosystem, VersionRange};
+
+    #[test]
+    fn orders_as_in_semver() {
+        let ascending = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.1",
+            "1.10.0",
+        ];
+        for pair in ascending.windows(2) {
+            assert_eq!(compare(pair[0], pair[1]), Ordering::Less, "{pair:?}");
+            assert_eq!(compare(pair[1], pair[0]), Ordering::Greater, "{pair:?}");
+        }
+    }
+
+    #[test]
+    fn orders_go_pseudo_versions() {
+        let ascending = [
+            "v0.0.0-20191109021931-daa7c04131f5",
+            "v0.0.0-20200101000000-0123456789ab",
+            "v1.2.3-pre",
+            "v1.2.3-pre.0.20191109021931-daa7c04131f5",
+            "v1.2.3",
+            "v1.2.4-0.20191109021931-daa7c04131f5",
+            "v1.2.4",
+        ];
+        for pair in ascending.windows(2) {
+            assert_eq!(compare(pair[0], pair[1]), Ordering::Less, "{pair:?}");
+        }
+        assert_eq!(compare("v2.0.0+incompatible", "v2.0.0"), Ordering::Equal);
+        assert!(is_prerelease("v0.0.0-20191109021931-daa7c04131f5"));
+        assert!(is_prerelease("v1.2.4-0.20191109021931-daa7c04131f5"));
+        assert!(!is_prerelease("v2.0.0+incompatible"));
+    }
+
+    #[test]
+    fn cargo_and_npm_ranges() {
+        let cases = [
+            (Ecosystem::Cargo, "0.2.3", "0.2.9", true),
+            (Ecosystem::Cargo, "0.2.3", "0.3.0", false),
+            (Ecosystem::Cargo, "^0.0.3", "0.0.4", false),
+            (Ecosystem::Cargo, "~1.2", "1.2.7", true),
+            (Ecosystem::Npm, "1.2.3", "1.2.4", false),
+            (Ecosystem::Npm, "1.2.x", "1.2.9", true),
+            (Ecosystem::Npm, "1.2.3 - 2.3", "2.3.9", true),
+            (Ecosystem::Npm, "1.2.3 - 2.3", "2.4.0", false),
+            (Ecosystem::Npm, "<2.0.0", "2.0.0-beta", true),
+            (Ecosystem::Npm, "<2", "2.0.0-beta", false),
+            (Ecosystem::Npm, ">=1.0.0 <1.1.0 || >=2.0.0", "2.5.0", true),
+        ];
+        for (ecosystem, input, version, expected) in cases {
+            let range = VersionRange::parse(ecosystem, input).unwrap();
+            assert_eq!(range.contains(version), expected, "{version} in {input}");
+        }
+    }
+}
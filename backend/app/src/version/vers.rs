@@ -0,0 +1,150 @@
+use super::{
+    compare,
+    range::{Alternatives, Constraint, Op},
+    Ecosystem,
+};
+
+/// Parse the part of a `vers:` URI after the prefix, e.g. `npm/>=1.0.0|<2.0.0`.
+///
+/// Constraints are sorted by version and turned into intervals as the vers spec
+/// describes: a leading `<`/`<=`, a trailing `>`/`>=`, and every `>`..`<` pair in
+/// between, plus the `=` versions. `!=` versions are excluded from all of them.
+pub(super) fn parse(input: &str) -> Result<(Ecosystem, Alternatives), String> {
+    let (scheme, constraints) = input
+        .split_once('/')
+        .ok_or("vers URI needs a scheme, e.g. vers:npm/>=1.0.0")?;
+    let ecosystem = Ecosystem::from_purl_type(scheme);
+
+    let constraints = constraints.trim();
+    if constraints == "*" {
+        return Ok((ecosystem, vec![Vec::new()]));
+    }
+
+    let mut equal = Vec::new();
+    let mut excluded = Vec::new();
+    let mut bounds: Vec<(Op, String)> = Vec::new();
+    for constraint in constraints.split('|').map(str::trim) {
+        let (symbol, version) = split(constraint);
+        if version.is_empty() {
+            return Err(format!("missing version in {constraint:?}"));
+        }
+        let version = version.to_string();
+        match symbol {
+            "" | "=" => equal.push(version),
+            "!=" => excluded.push(version),
+            "<" => bounds.push((Op::Lt, version)),
+            "<=" => bounds.push((Op::Le, version)),
+            ">" => bounds.push((Op::Gt, version)),
+            ">=" => bounds.push((Op::Ge, version)),
+            other => return Err(format!("unsupported operator {other:?}")),
+        }
+    }
+
+    bounds.sort_by(|(_, a), (_, b)| compare(ecosystem, a, b));
+
+    let is_lower = |op: Op| matches!(op, Op::Gt | Op::Ge);
+    let mut intervals: Vec<Vec<Constraint>> = Vec::new();
+    if let Some((op, version)) = bounds.first().filter(|(op, _)| !is_lower(*op)) {
+        intervals.push(vec![Constraint::new(*op, version)]);
+    }
+    for pair in bounds.windows(2) {
+        let [(low_op, low), (high_op, high)] = pair else {
+            continue;
+        };
+        if is_lower(*low_op) && !is_lower(*high_op) {
+            intervals.push(vec![
+                Constraint::new(*low_op, low),
+                Constraint::new(*high_op, high),
+            ]);
+        }
+    }
+    if let Some((op, version)) = bounds.last().filter(|(op, _)| is_lower(*op)) {
+        intervals.push(vec![Constraint::new(*op, version)]);
+    }
+    intervals.extend(
+        equal
+            .into_iter()
+            .map(|version| vec![Constraint::new(Op::Eq, version)]),
+    );
+
+    if intervals.is_empty() {
+        if excluded.is_empty() {
+            return Err("no usable constraints".into());
+        }
+        // Only `!=` constraints: everything except those versions.
+        intervals.push(Vec::new());
+    }
+
+    for set in &mut intervals {
+        set.extend(
+            excluded
+                .iter()
+                .map(|version| Constraint::new(Op::Ne, version.as_str())),
+        );
+    }
+
+    Ok((ecosystem, intervals))
+}
+
+fn split(constraint: &str) -> (&str, &str) {
+    for symbol in ["<=", ">=", "!=", "<", ">", "="] {
+        if let Some(version) = constraint.strip_prefix(symbol) {
+            return (symbol, version.trim());
+        }
+    }
+    ("", constraint)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::version::{Ecosystem, VersionRange};
+
+    fn vers(input: &str) -> VersionRange {
+        VersionRange::parse(Ecosystem::Generic, input).unwrap()
+    }
+
+    #[test]
+    fn takes_the_scheme_from_the_uri() {
+        assert_eq!(vers("vers:pypi/>=1.0").ecosystem(), Ecosystem::Pypi);
+        assert_eq!(vers("vers:maven/1.0").ecosystem(), Ecosystem::Maven);
+    }
+
+    #[test]
+    fn matches_constraints() {
+        let cases = [
+            ("vers:npm/>=1.0.0|<2.0.0", "1.5.0", true),
+            ("vers:npm/>=1.0.0|<2.0.0", "2.0.0", false),
+            ("vers:npm/>=1.0.0|<2.0.0", "0.9.9", false),
+            ("vers:npm/*", "0.0.1", true),
+            ("vers:npm/*", "99.0.0", true),
+            ("vers:npm/!=1.2.3", "1.2.4", true),
+            ("vers:npm/!=1.2.3", "1.2.3", false),
+            ("vers:npm/1.0.0|1.0.2", "1.0.2", true),
+            ("vers:npm/1.0.0|1.0.2", "1.0.1", false),
+            ("vers:npm/<1.0.0|>=2.0.0|<3.0.0|!=2.5.0", "0.5.0", true),
+            ("vers:npm/<1.0.0|>=2.0.0|<3.0.0|!=2.5.0", "1.5.0", false),
+            ("vers:npm/<1.0.0|>=2.0.0|<3.0.0|!=2.5.0", "2.1.0", true),
+            ("vers:npm/<1.0.0|>=2.0.0|<3.0.0|!=2.5.0", "2.5.0", false),
+            ("vers:npm/<1.0.0|>=2.0.0|<3.0.0|!=2.5.0", "3.0.0", false),
+            ("vers:pypi/>=1.0|<1.1", "1.1.dev0", true),
+            ("vers:maven/>=1.0|<=2.0", "2.0-SNAPSHOT", true),
+        ];
+        for (input, version, expected) in cases {
+            assert_eq!(
+                vers(input).contains(version),
+                expected,
+                "{version} in {input}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_uris() {
+        for input in ["vers:npm", "vers:npm/>=", "vers:npm/>=1.0|<", "vers:npm/"] {
+            assert!(
+                VersionRange::parse(Ecosystem::Generic, input).is_err(),
+                "{input}"
+            );
+        }
+    }
+}
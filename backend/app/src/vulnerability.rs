@@ -0,0 +1,17 @@
+//! Offline vulnerability data: OSV and NVD imports, matching of scanned components and
+//! exploit likelihood from EPSS and the KEV catalog.
+
+pub mod alerts;
+pub mod cpe;
+pub mod cvss;
+pub mod epss;
+pub mod kev;
+pub mod matcher;
+pub mod nvd;
+pub mod osv;
+pub mod risk;
+
+pub use alerts::{raise_for_latest_scans, raise_kev_alerts};
+pub use matcher::{match_scan, rematch_latest_scans};
+pub use nvd::import_feeds;
+pub use osv::{import_dir, ImportSummary};
@@ -0,0 +1,173 @@
+//! Alerts for findings whose advisory is known to be exploited in the wild.
+//!
+//! An alert is raised once per project, advisory and component, the first time a scan
+//! reports the finding while the CVE is in the KEV catalog. Findings triaged as not
+//! affected, false positive or fixed do not raise one. Every alert is logged under the
+//! `kev_alert` target and, when `vulnerabilities.kev_webhook_url` is set, posted there.
+
+use std::time::Duration;
+
+use chrono::{NaiveDate, Utc};
+use reqwest::Client;
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, DatabaseBackend, DatabaseConnection, DbErr, EntityTrait,
+    FromQueryResult, QueryFilter, Statement,
+};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::entity::kev_alert;
+
+#[derive(Debug, FromQueryResult)]
+struct RaisedAlert {
+    id: i32,
+    project_id: i32,
+    project_name: String,
+    scan_id: i32,
+    cve: String,
+    osv_id: String,
+    purl: String,
+    due_date: Option<NaiveDate>,
+    known_ransomware: bool,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ScanRow {
+    id: i32,
+}
+
+/// Body posted to the alert webhook.
+#[derive(Debug, Serialize)]
+struct AlertPayload<'a> {
+    event: &'static str,
+    alert_id: i32,
+    project_id: i32,
+    project: &'a str,
+    scan_id: i32,
+    cve: &'a str,
+    advisory: &'a str,
+    purl: &'a str,
+    due_date: Option<NaiveDate>,
+    known_ransomware: bool,
+}
+
+/// Raise alerts for the KEV-listed findings of a scan that have none yet. Returns the
+/// number of new alerts.
+pub async fn raise_kev_alerts(db: &DatabaseConnection, scan_id: i32) -> Result<usize, DbErr> {
+    let alerts = RaisedAlert::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "WITH raised AS ( \
+             INSERT INTO kev_alerts (project_id, scan_id, vulnerability_id, cve, purl, \
+                                     due_date, known_ransomware, created_at) \
+             SELECT DISTINCT ON (f.vulnerability_id, f.purl) \
+                    f.project_id, f.scan_id, f.vulnerability_id, ke.cve, f.purl, \
+                    ke.due_date, ke.known_ransomware, $2 \
+             FROM vulnerability_findings f \
+             JOIN vulnerabilities vu ON vu.id = f.vulnerability_id \
+             JOIN kev_entries ke ON ke.cve = vu.osv_id \
+                  OR vu.aliases @> jsonb_build_array(ke.cve) \
+             LEFT JOIN vulnerability_analyses a ON a.project_id = f.project_id \
+                  AND a.vulnerability_id = f.vulnerability_id AND a.purl = f.purl \
+             WHERE f.scan_id = $1 \
+               AND (a.state IS NULL \
+                    OR a.state NOT IN ('not_affected', 'false_positive', 'fixed')) \
+             ORDER BY f.vulnerability_id, f.purl, ke.date_added \
+             ON CONFLICT (project_id, vulnerability_id, purl) DO NOTHING \
+             RETURNING id, project_id, scan_id, vulnerability_id, cve, purl, due_date, \
+                       known_ransomware) \
+         SELECT r.id, r.project_id, pr.name AS project_name, r.scan_id, r.cve, vu.osv_id, \
+                r.purl, r.due_date, r.known_ransomware \
+         FROM raised r \
+         JOIN projects pr ON pr.id = r.project_id \
+         JOIN vulnerabilities vu ON vu.id = r.vulnerability_id \
+         ORDER BY r.id",
+        [scan_id.into(), Utc::now().into()],
+    ))
+    .all(db)
+    .await?;
+
+    if alerts.is_empty() {
+        return Ok(0);
+    }
+
+    for alert in &alerts {
+        warn!(
+            target: "kev_alert",
+            project = alert.project_name,
+            scan_id = alert.scan_id,
+            cve = alert.cve,
+            purl = alert.purl,
+            due_date = ?alert.due_date,
+            known_ransomware = alert.known_ransomware,
+            "known exploited vulnerability in project component"
+        );
+    }
+
+    if let Some(url) = crate::config::get().vulnerabilities().kev_webhook_url() {
+        let notified = notify(url, &alerts).await;
+        if !notified.is_empty() {
+            kev_alert::Entity::update_many()
+                .col_expr(kev_alert::Column::NotifiedAt, Expr::value(Utc::now()))
+                .filter(kev_alert::Column::Id.is_in(notified))
+                .exec(db)
+                .await?;
+        }
+    }
+
+    Ok(alerts.len())
+}
+
+/// Raise alerts for the latest successful scan of every project, e.g. after the KEV
+/// catalog was refreshed.
+pub async fn raise_for_latest_scans(db: &DatabaseConnection) -> Result<usize, DbErr> {
+    let scans = ScanRow::find_by_statement(Statement::from_string(
+        DatabaseBackend::Postgres,
+        "SELECT MAX(id) AS id FROM scans WHERE status = 'success' GROUP BY project_id",
+    ))
+    .all(db)
+    .await?;
+
+    let mut total = 0;
+    for scan in scans {
+        total += raise_kev_alerts(db, scan.id).await?;
+    }
+    Ok(total)
+}
+
+/// Post each alert to the webhook and return the ids it accepted.
+async fn notify(url: &str, alerts: &[RaisedAlert]) -> Vec<i32> {
+    let client = match Client::builder().timeout(Duration::from_secs(10)).build() {
+        Ok(client) => client,
+        Err(err) => {
+            warn!(error = ?err, "failed to build the KEV alert webhook client");
+            return Vec::new();
+        }
+    };
+
+    let mut notified = Vec::new();
+    for alert in alerts {
+        let payload = AlertPayload {
+            event: "kev_alert",
+            alert_id: alert.id,
+            project_id: alert.project_id,
+            project: &alert.project_name,
+            scan_id: alert.scan_id,
+            cve: &alert.cve,
+            advisory: &alert.osv_id,
+            purl: &alert.purl,
+            due_date: alert.due_date,
+            known_ransomware: alert.known_ransomware,
+        };
+        let sent = client
+            .post(url)
+            .json(&payload)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        match sent {
+            Ok(_) => notified.push(alert.id),
+            Err(err) => warn!(error = ?err, alert_id = alert.id, "failed to post KEV alert"),
+        }
+    }
+    notified
+}
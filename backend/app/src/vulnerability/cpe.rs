@@ -0,0 +1,89 @@
+/// The fields of a CPE name that matching looks at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cpe {
+    /// `a` (application), `o` (operating system) or `h` (hardware).
+    pub part: String,
+    pub vendor: String,
+    pub product: String,
+    /// `None` for the `*` (any) and `-` (not applicable) values.
+    pub version: Option<String>,
+}
+
+impl Cpe {
+    /// Parse a CPE 2.3 formatted string (`cpe:2.3:a:vendor:product:1.0:*:...`) or a
+    /// CPE 2.2 URI (`cpe:/a:vendor:product:1.0`). Vendor and product are lowercased.
+    pub fn parse(name: &str) -> Option<Cpe> {
+        let name = name.trim();
+        let fields: Vec<String> = if let Some(rest) = name.strip_prefix("cpe:2.3:") {
+            split_escaped(rest)
+        } else if let Some(rest) = name.strip_prefix("cpe:/") {
+            rest.split(':').map(str::to_string).collect()
+        } else {
+            return None;
+        };
+
+        let field = |index: usize| fields.get(index).map(String::as_str).unwrap_or("*");
+        let part = field(0);
+        if !matches!(part, "a" | "o" | "h") {
+            return None;
+        }
+        let (vendor, product) = (field(1), field(2));
+        if is_wildcard(vendor) || is_wildcard(product) {
+            return None;
+        }
+        let version = Some(field(3))
+            .filter(|version| !is_wildcard(version) && !version.is_empty())
+            .map(unescape);
+
+        Some(Cpe {
+            part: part.to_string(),
+            vendor: vendor.to_ascii_lowercase(),
+            product: product.to_ascii_lowercase(),
+            version,
+        })
+    }
+
+    /// CPE 2.3 name of the product with every field after it wildcarded, so all
+    /// versions of a package share one key.
+    pub fn versionless(&self) -> String {
+        format!(
+            "cpe:2.3:{}:{}:{}:*:*:*:*:*:*:*:*",
+            self.part, self.vendor, self.product
+        )
+    }
+}
+
+fn is_wildcard(value: &str) -> bool {
+    matches!(value, "*" | "-" | "")
+}
+
+/// Split on `:` except where it is escaped with a backslash.
+fn split_escaped(value: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                let current = fields.last_mut().expect("at least one field");
+                current.push('\\');
+                current.extend(chars.next());
+            }
+            ':' => fields.push(String::new()),
+            _ => fields.last_mut().expect("at least one field").push(c),
+        }
+    }
+    fields
+}
+
+/// Drop the backslashes CPE 2.3 uses to quote punctuation in versions (`1\.2\.3`).
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.extend(chars.next()),
+            _ => out.push(c),
+        }
+    }
+    out
+}
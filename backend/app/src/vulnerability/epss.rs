@@ -0,0 +1,104 @@
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+};
+
+use anyhow::Context;
+use chrono::{DateTime, NaiveDate, Utc};
+use flate2::read::GzDecoder;
+use sea_orm::{DatabaseConnection, EntityTrait, Set, TransactionTrait};
+use serde::Deserialize;
+
+use crate::entity::epss_score;
+
+/// Rows written per insert.
+const BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Default)]
+pub struct EpssSummary {
+    pub scores: usize,
+    pub model_version: Option<String>,
+    pub score_date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EpssRow {
+    cve: String,
+    epss: f64,
+    percentile: f64,
+}
+
+/// Replace the stored EPSS scores with a daily snapshot as published by FIRST
+/// (`epss_scores-<date>.csv[.gz]`). The snapshot starts with a
+/// `#model_version:...,score_date:...` comment followed by a `cve,epss,percentile` header.
+pub async fn import_file(db: &DatabaseConnection, path: &Path) -> anyhow::Result<EpssSummary> {
+    let source = path.to_path_buf();
+    let (mut summary, rows) = tokio::task::spawn_blocking(move || read_snapshot(&source))
+        .await?
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    if rows.is_empty() {
+        anyhow::bail!("no EPSS scores found in {}", path.display());
+    }
+
+    let now = Utc::now();
+    let txn = db.begin().await?;
+    epss_score::Entity::delete_many().exec(&txn).await?;
+    for batch in rows.chunks(BATCH_SIZE) {
+        let models = batch.iter().map(|row| epss_score::ActiveModel {
+            cve: Set(row.cve.clone()),
+            epss: Set(row.epss),
+            percentile: Set(row.percentile),
+            model_version: Set(summary.model_version.clone()),
+            score_date: Set(summary.score_date),
+            imported_at: Set(now),
+        });
+        epss_score::Entity::insert_many(models).exec(&txn).await?;
+    }
+    txn.commit().await?;
+
+    summary.scores = rows.len();
+    Ok(summary)
+}
+
+fn read_snapshot(path: &Path) -> anyhow::Result<(EpssSummary, Vec<EpssRow>)> {
+    let file = File::open(path)?;
+    let mut content = String::new();
+    let is_gzip = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("gz"));
+    if is_gzip {
+        GzDecoder::new(file).read_to_string(&mut content)?;
+    } else {
+        BufReader::new(file).read_to_string(&mut content)?;
+    }
+
+    let mut summary = EpssSummary::default();
+    let mut body = content.as_str();
+    if let Some(comment) = body.strip_prefix('#') {
+        let (line, rest) = comment.split_once('\n').unwrap_or((comment, ""));
+        for field in line.trim().split(',') {
+            match field.split_once(':') {
+                Some(("model_version", value)) => summary.model_version = Some(value.to_string()),
+                Some(("score_date", value)) => {
+                    summary.score_date = DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%z")
+                        .map(|date| date.date_naive())
+                        .or_else(|_| NaiveDate::parse_from_str(value, "%Y-%m-%d"))
+                        .ok();
+                }
+                _ => {}
+            }
+        }
+        body = rest;
+    }
+
+    let mut rows = Vec::new();
+    let mut reader = csv::Reader::from_reader(body.as_bytes());
+    for row in reader.deserialize::<EpssRow>() {
+        let row = row?;
+        if row.cve.starts_with("CVE-") {
+            rows.push(row);
+        }
+    }
+    Ok((summary, rows))
+}
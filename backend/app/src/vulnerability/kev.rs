@@ -0,0 +1,87 @@
+use std::{fs::File, io::BufReader, path::Path};
+
+use anyhow::Context;
+use chrono::{NaiveDate, Utc};
+use sea_orm::{DatabaseConnection, EntityTrait, Set, TransactionTrait};
+use serde::Deserialize;
+
+use crate::entity::kev_entry;
+
+/// Rows written per insert.
+const BATCH_SIZE: usize = 500;
+
+#[derive(Debug, Default)]
+pub struct KevSummary {
+    pub entries: usize,
+    pub catalog_version: Option<String>,
+}
+
+/// CISA's `known_exploited_vulnerabilities.json`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KevCatalog {
+    catalog_version: Option<String>,
+    #[serde(default)]
+    vulnerabilities: Vec<KevVulnerability>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KevVulnerability {
+    #[serde(rename = "cveID")]
+    cve_id: String,
+    vendor_project: Option<String>,
+    product: Option<String>,
+    vulnerability_name: Option<String>,
+    date_added: NaiveDate,
+    short_description: Option<String>,
+    required_action: Option<String>,
+    due_date: Option<NaiveDate>,
+    /// `Known` or `Unknown`.
+    known_ransomware_campaign_use: Option<String>,
+    notes: Option<String>,
+}
+
+/// Replace the stored catalog with the given copy of the KEV catalog.
+pub async fn import_file(db: &DatabaseConnection, path: &Path) -> anyhow::Result<KevSummary> {
+    let source = path.to_path_buf();
+    let catalog = tokio::task::spawn_blocking(move || -> anyhow::Result<KevCatalog> {
+        Ok(serde_json::from_reader(BufReader::new(File::open(
+            &source,
+        )?))?)
+    })
+    .await?
+    .with_context(|| format!("failed to read {}", path.display()))?;
+    if catalog.vulnerabilities.is_empty() {
+        anyhow::bail!("no KEV entries found in {}", path.display());
+    }
+
+    let now = Utc::now();
+    let txn = db.begin().await?;
+    kev_entry::Entity::delete_many().exec(&txn).await?;
+    for batch in catalog.vulnerabilities.chunks(BATCH_SIZE) {
+        let models = batch.iter().map(|entry| kev_entry::ActiveModel {
+            cve: Set(entry.cve_id.clone()),
+            vendor_project: Set(entry.vendor_project.clone()),
+            product: Set(entry.product.clone()),
+            vulnerability_name: Set(entry.vulnerability_name.clone()),
+            short_description: Set(entry.short_description.clone()),
+            required_action: Set(entry.required_action.clone()),
+            date_added: Set(entry.date_added),
+            due_date: Set(entry.due_date),
+            known_ransomware: Set(entry
+                .known_ransomware_campaign_use
+                .as_deref()
+                .is_some_and(|usage| usage.eq_ignore_ascii_case("known"))),
+            notes: Set(entry.notes.clone().filter(|notes| !notes.is_empty())),
+            imported_at: Set(now),
+        });
+        kev_entry::Entity::insert_many(models).exec(&txn).await?;
+    }
+    txn.commit().await?;
+
+    Ok(KevSummary {
+        entries: catalog.vulnerabilities.len(),
+        catalog_version: catalog.catalog_version,
+    })
+}
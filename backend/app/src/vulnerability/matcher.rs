@@ -0,0 +1,271 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+};
+
+use chrono::Utc;
+use sea_orm::{
+    ColumnTrait, DatabaseBackend, DatabaseConnection, DbErr, EntityTrait, FromQueryResult,
+    QueryFilter, Set, Statement, TransactionTrait,
+};
+use serde_json::Value;
+use tracing::warn;
+
+use super::alerts::raise_kev_alerts;
+use crate::{
+    database::insert_in_batches,
+    entity::vulnerability_finding,
+    version::{compare, Ecosystem},
+};
+
+#[derive(Debug, FromQueryResult)]
+struct CandidateRow {
+    project_id: i32,
+    package_id: i32,
+    package_version_id: Option<i32>,
+    purl: String,
+    purl_type: String,
+    version: Option<String>,
+    vulnerability_id: i32,
+    range_type: String,
+    introduced: Option<String>,
+    fixed: Option<String>,
+    last_affected: Option<String>,
+    versions: Option<Value>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct CpeCandidateRow {
+    project_id: i32,
+    package_id: i32,
+    package_version_id: Option<i32>,
+    purl: String,
+    purl_type: String,
+    version: Option<String>,
+    vulnerability_id: i32,
+    exact_version: Option<String>,
+    version_start_including: Option<String>,
+    version_start_excluding: Option<String>,
+    version_end_including: Option<String>,
+    version_end_excluding: Option<String>,
+}
+
+/// A component paired with one advisory, accumulated over all its candidate rows.
+struct Hit<'a> {
+    project_id: i32,
+    package_id: i32,
+    package_version_id: Option<i32>,
+    purl_type: &'a str,
+    version: Option<&'a str>,
+    affected: bool,
+    fixed: BTreeSet<String>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct ScanRow {
+    id: i32,
+}
+
+/// Match every component of a scan against the imported affected ranges and NVD CPE
+/// criteria and replace the scan's findings. Components cover direct and transitive
+/// dependencies alike. New findings listed in the KEV catalog raise alerts.
+pub async fn match_scan(db: &DatabaseConnection, scan_id: i32) -> Result<usize, DbErr> {
+    let rows = CandidateRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT DISTINCT d.project_id, d.package_id, d.package_version_id, \
+                COALESCE(v.purl, p.purl) AS purl, p.purl_type, \
+                COALESCE(v.version, d.resolved_version) AS version, \
+                r.vulnerability_id, r.range_type, r.introduced, r.fixed, r.last_affected, \
+                r.versions \
+         FROM direct_dependencies d \
+         JOIN packages p ON p.id = d.package_id \
+         LEFT JOIN package_versions v ON v.id = d.package_version_id \
+         JOIN affected_ranges r ON r.package_purl = p.purl \
+         JOIN vulnerabilities vu ON vu.id = r.vulnerability_id AND vu.withdrawn_at IS NULL \
+         WHERE d.scan_id = $1",
+        [scan_id.into()],
+    ))
+    .all(db)
+    .await?;
+
+    // CPE criteria are only consulted when no OSV advisory for the package covers the
+    // same CVE, since OSV ranges are ecosystem-precise and NVD ones are not.
+    let cpe_rows = CpeCandidateRow::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        "SELECT DISTINCT d.project_id, d.package_id, d.package_version_id, \
+                COALESCE(v.purl, p.purl) AS purl, p.purl_type, \
+                COALESCE(v.version, d.resolved_version) AS version, \
+                m.vulnerability_id, m.version AS exact_version, m.version_start_including, \
+                m.version_start_excluding, m.version_end_including, m.version_end_excluding \
+         FROM direct_dependencies d \
+         JOIN packages p ON p.id = d.package_id \
+         LEFT JOIN package_versions v ON v.id = d.package_version_id \
+         JOIN cpe_matches m ON m.package_cpe = p.cpe \
+         JOIN vulnerabilities vu ON vu.id = m.vulnerability_id AND vu.withdrawn_at IS NULL \
+         WHERE d.scan_id = $1 \
+           AND NOT EXISTS (SELECT 1 FROM affected_ranges r \
+                           JOIN vulnerabilities o ON o.id = r.vulnerability_id \
+                           WHERE r.package_purl = p.purl \
+                             AND (o.id = vu.id OR o.aliases @> jsonb_build_array(vu.osv_id)))",
+        [scan_id.into()],
+    ))
+    .all(db)
+    .await?;
+
+    // (vulnerability, purl) -> hit
+    let mut matches: BTreeMap<(i32, &str), Hit> = BTreeMap::new();
+    for row in &rows {
+        let hit = matches
+            .entry((row.vulnerability_id, row.purl.as_str()))
+            .or_insert_with(|| Hit {
+                project_id: row.project_id,
+                package_id: row.package_id,
+                package_version_id: row.package_version_id,
+                purl_type: &row.purl_type,
+                version: row.version.as_deref(),
+                affected: false,
+                fixed: BTreeSet::new(),
+            });
+        // Only the ranges that contain the version say which release fixes it.
+        if range_matches(row) {
+            hit.affected = true;
+            hit.fixed.extend(row.fixed.clone());
+        }
+    }
+    for row in &cpe_rows {
+        let hit = matches
+            .entry((row.vulnerability_id, row.purl.as_str()))
+            .or_insert_with(|| Hit {
+                project_id: row.project_id,
+                package_id: row.package_id,
+                package_version_id: row.package_version_id,
+                purl_type: &row.purl_type,
+                version: row.version.as_deref(),
+                affected: false,
+                fixed: BTreeSet::new(),
+            });
+        if cpe_matches(row) {
+            hit.affected = true;
+            hit.fixed.extend(row.version_end_excluding.clone());
+        }
+    }
+
+    let now = Utc::now();
+    let findings: Vec<vulnerability_finding::ActiveModel> = matches
+        .into_iter()
+        .filter(|(_, hit)| hit.affected)
+        .map(|((vulnerability_id, purl), hit)| {
+            let ecosystem = Ecosystem::from_purl_type(hit.purl_type);
+            let mut fixed: Vec<String> = hit.fixed.into_iter().collect();
+            fixed.sort_by(|a, b| compare(ecosystem, a, b));
+            vulnerability_finding::ActiveModel {
+                vulnerability_id: Set(vulnerability_id),
+                project_id: Set(hit.project_id),
+                scan_id: Set(scan_id),
+                package_id: Set(hit.package_id),
+                package_version_id: Set(hit.package_version_id),
+                purl: Set(purl.to_string()),
+                version: Set(hit.version.map(str::to_string)),
+                fixed_versions: Set(serde_json::json!(fixed)),
+                created_at: Set(now),
+                ..Default::default()
+            }
+        })
+        .collect();
+    let count = findings.len();
+
+    let txn = db.begin().await?;
+    vulnerability_finding::Entity::delete_many()
+        .filter(vulnerability_finding::Column::ScanId.eq(scan_id))
+        .exec(&txn)
+        .await?;
+    insert_in_batches(&txn, findings).await?;
+    txn.commit().await?;
+
+    if let Err(err) = raise_kev_alerts(db, scan_id).await {
+        warn!(error = ?err, scan_id, "failed to raise KEV alerts");
+    }
+
+    Ok(count)
+}
+
+/// Re-run matching for the latest successful scan of every project, e.g. after the
+/// vulnerability database was refreshed.
+pub async fn rematch_latest_scans(db: &DatabaseConnection) -> Result<usize, DbErr> {
+    let scans = ScanRow::find_by_statement(Statement::from_string(
+        DatabaseBackend::Postgres,
+        "SELECT MAX(id) AS id FROM scans WHERE status = 'success' GROUP BY project_id",
+    ))
+    .all(db)
+    .await?;
+
+    let mut total = 0;
+    for scan in scans {
+        total += match_scan(db, scan.id).await?;
+    }
+    Ok(total)
+}
+
+fn range_matches(row: &CandidateRow) -> bool {
+    let Some(version) = row.version.as_deref() else {
+        return false;
+    };
+
+    if row.range_type == "VERSIONS" {
+        return row
+            .versions
+            .as_ref()
+            .and_then(Value::as_array)
+            .is_some_and(|versions| versions.iter().any(|v| v.as_str() == Some(version)));
+    }
+
+    // SEMVER ranges follow SemVer 2.0 regardless of the package's ecosystem.
+    let ecosystem = match row.range_type.as_str() {
+        "SEMVER" => Ecosystem::Cargo,
+        _ => Ecosystem::from_purl_type(&row.purl_type),
+    };
+    let cmp = |other: &str| compare(ecosystem, version, other);
+
+    let above_floor = match row.introduced.as_deref() {
+        None | Some("0") => true,
+        Some(introduced) => cmp(introduced) != Ordering::Less,
+    };
+    let below_ceiling = match (row.fixed.as_deref(), row.last_affected.as_deref()) {
+        (Some(fixed), _) => cmp(fixed) == Ordering::Less,
+        (None, Some(last)) => cmp(last) != Ordering::Greater,
+        (None, None) => true,
+    };
+    above_floor && below_ceiling
+}
+
+/// NVD criteria compare versions with the package's own ecosystem rules.
+fn cpe_matches(row: &CpeCandidateRow) -> bool {
+    let Some(version) = row.version.as_deref() else {
+        return false;
+    };
+    let ecosystem = Ecosystem::from_purl_type(&row.purl_type);
+    let cmp = |other: &str| compare(ecosystem, version, other);
+
+    if row
+        .exact_version
+        .as_deref()
+        .is_some_and(|exact| cmp(exact) != Ordering::Equal)
+    {
+        return false;
+    }
+    row.version_start_including
+        .as_deref()
+        .is_none_or(|start| cmp(start) != Ordering::Less)
+        && row
+            .version_start_excluding
+            .as_deref()
+            .is_none_or(|start| cmp(start) == Ordering::Greater)
+        && row
+            .version_end_including
+            .as_deref()
+            .is_none_or(|end| cmp(end) != Ordering::Greater)
+        && row
+            .version_end_excluding
+            .as_deref()
+            .is_none_or(|end| cmp(end) == Ordering::Less)
+}
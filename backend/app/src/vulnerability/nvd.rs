@@ -0,0 +1,401 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use flate2::read::GzDecoder;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, FromQueryResult, QueryFilter,
+    QuerySelect, Set, TransactionTrait,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::warn;
+use walkdir::WalkDir;
+
+use super::{cpe::Cpe, cvss, osv::ImportSummary};
+use crate::entity::{cpe_match, vulnerability};
+
+/// Records written per transaction.
+const BATCH_SIZE: usize = 500;
+
+/// Value of `vulnerabilities.source` for records imported here.
+pub const SOURCE: &str = "nvd";
+
+/// An NVD CVE API 2.0 feed file (`nvdcve-2.0-<year>.json`).
+#[derive(Debug, Deserialize)]
+struct NvdFeed {
+    #[serde(default)]
+    vulnerabilities: Vec<NvdItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NvdItem {
+    cve: NvdCve,
+}
+
+/// One CVE in the NVD 2.0 schema; only the fields check-deps uses.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NvdCve {
+    pub id: String,
+    pub published: Option<String>,
+    pub last_modified: Option<String>,
+    pub vuln_status: Option<String>,
+    #[serde(default)]
+    pub descriptions: Vec<NvdDescription>,
+    #[serde(default)]
+    pub metrics: NvdMetrics,
+    #[serde(default)]
+    pub configurations: Vec<NvdConfiguration>,
+    pub references: Option<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NvdDescription {
+    pub lang: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NvdMetrics {
+    #[serde(default)]
+    pub cvss_metric_v31: Vec<NvdCvssMetric>,
+    #[serde(default)]
+    pub cvss_metric_v30: Vec<NvdCvssMetric>,
+    #[serde(default)]
+    pub cvss_metric_v2: Vec<NvdCvssMetric>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NvdCvssMetric {
+    /// `Primary` for NVD's own assessment, `Secondary` for the CNA's.
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub cvss_data: NvdCvssData,
+    /// CVSS v2 keeps the rating next to `cvssData`.
+    pub base_severity: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NvdCvssData {
+    pub vector_string: String,
+    pub base_score: f64,
+    pub base_severity: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NvdConfiguration {
+    #[serde(default)]
+    pub nodes: Vec<NvdNode>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NvdNode {
+    #[serde(default)]
+    pub cpe_match: Vec<NvdCpeMatch>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NvdCpeMatch {
+    pub vulnerable: bool,
+    pub criteria: String,
+    pub version_start_including: Option<String>,
+    pub version_start_excluding: Option<String>,
+    pub version_end_including: Option<String>,
+    pub version_end_excluding: Option<String>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct KnownRecord {
+    id: i32,
+    osv_id: String,
+    source: String,
+    modified_at: Option<DateTime<Utc>>,
+}
+
+/// Import every NVD feed below `dir`: `*.json` files as well as the `*.json.gz` and
+/// `*.json.zip` archives NVD publishes. NVD-owned CVEs whose `lastModified` did not move
+/// are skipped, as are CVEs without a vulnerable CPE criterion. CVEs that an OSV
+/// advisory owns are always re-imported, since their stored modification time is OSV's.
+pub async fn import_feeds(db: &DatabaseConnection, dir: &Path) -> anyhow::Result<ImportSummary> {
+    let files: Vec<PathBuf> = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| FeedKind::of(path).is_some())
+        .collect();
+    if files.is_empty() {
+        anyhow::bail!(
+            "no NVD feeds (*.json, *.json.gz or *.json.zip) found in {}",
+            dir.display()
+        );
+    }
+
+    let mut known: HashMap<String, (i32, bool, Option<DateTime<Utc>>)> =
+        vulnerability::Entity::find()
+            .select_only()
+            .column(vulnerability::Column::Id)
+            .column(vulnerability::Column::OsvId)
+            .column(vulnerability::Column::Source)
+            .column(vulnerability::Column::ModifiedAt)
+            .into_model::<KnownRecord>()
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|row| (row.osv_id, (row.id, row.source == SOURCE, row.modified_at)))
+            .collect();
+
+    let mut summary = ImportSummary::default();
+    for path in files {
+        let source = path.clone();
+        let records = tokio::task::spawn_blocking(move || read_feed(&source))
+            .await?
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let total = records.len();
+        summary.files += 1;
+        summary.records += total;
+
+        let changed: Vec<NvdCve> = records
+            .into_iter()
+            .filter(|cve| match known.get(&cve.id) {
+                Some((_, true, Some(stored))) => {
+                    timestamp(cve.last_modified.as_deref()).is_none_or(|m| m > *stored)
+                }
+                _ => true,
+            })
+            .collect();
+        summary.unchanged += total - changed.len();
+
+        for batch in changed.chunks(BATCH_SIZE) {
+            let txn = db.begin().await?;
+            for cve in batch {
+                let existing = known.get(&cve.id).map(|(id, own, _)| (*id, *own));
+                match store_record(&txn, cve, existing).await? {
+                    Some(id) => {
+                        let own = existing.is_none_or(|(_, own)| own);
+                        let modified = timestamp(cve.last_modified.as_deref());
+                        known.insert(cve.id.clone(), (id, own, modified));
+                        summary.imported += 1;
+                    }
+                    None => summary.skipped += 1,
+                }
+            }
+            txn.commit().await?;
+        }
+        tracing::info!("Imported NVD feed {}", path.display());
+    }
+
+    Ok(summary)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FeedKind {
+    Json,
+    Gzip,
+    Zip,
+}
+
+impl FeedKind {
+    fn of(path: &Path) -> Option<FeedKind> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if name.ends_with(".json.gz") {
+            Some(FeedKind::Gzip)
+        } else if name.ends_with(".json.zip") {
+            Some(FeedKind::Zip)
+        } else if name.ends_with(".json") {
+            Some(FeedKind::Json)
+        } else {
+            None
+        }
+    }
+}
+
+fn read_feed(path: &Path) -> anyhow::Result<Vec<NvdCve>> {
+    let file = File::open(path)?;
+    let feeds: Vec<NvdFeed> = match FeedKind::of(path) {
+        Some(FeedKind::Gzip) => vec![serde_json::from_reader(BufReader::new(GzDecoder::new(
+            file,
+        )))?],
+        Some(FeedKind::Zip) => {
+            let mut archive = zip::ZipArchive::new(file)?;
+            let mut feeds = Vec::new();
+            let mut buffer = Vec::new();
+            for index in 0..archive.len() {
+                let mut entry = archive.by_index(index)?;
+                if !entry.is_file() || !entry.name().ends_with(".json") {
+                    continue;
+                }
+                buffer.clear();
+                entry.read_to_end(&mut buffer)?;
+                feeds.push(serde_json::from_slice(&buffer)?);
+            }
+            feeds
+        }
+        _ => vec![serde_json::from_reader(BufReader::new(file))?],
+    };
+    Ok(feeds
+        .into_iter()
+        .flat_map(|feed| feed.vulnerabilities)
+        .map(|item| item.cve)
+        .collect())
+}
+
+/// Upsert one CVE and replace its CPE criteria. Returns `None` when the CVE has no
+/// usable vulnerable criterion, in which case nothing is written.
+///
+/// When the id is already known from an OSV dump only the criteria are replaced; the
+/// OSV record's metadata wins.
+async fn store_record<C: sea_orm::ConnectionTrait>(
+    db: &C,
+    cve: &NvdCve,
+    existing: Option<(i32, bool)>,
+) -> anyhow::Result<Option<i32>> {
+    let criteria = cpe_matches(cve);
+    if criteria.is_empty() {
+        return Ok(None);
+    }
+
+    let now = Utc::now();
+    let id = match existing {
+        Some((id, false)) => id,
+        existing => {
+            let (cvss_vector, cvss_score, severity) = score(&cve.metrics);
+            let mut model = vulnerability::ActiveModel {
+                osv_id: Set(cve.id.clone()),
+                source: Set(SOURCE.to_string()),
+                summary: Set(None),
+                details: Set(description(cve)),
+                severity: Set(severity),
+                cvss_score: Set(cvss_score),
+                cvss_vector: Set(cvss_vector),
+                aliases: Set(serde_json::json!([])),
+                references: Set(cve.references.clone()),
+                published_at: Set(timestamp(cve.published.as_deref())),
+                modified_at: Set(timestamp(cve.last_modified.as_deref())),
+                withdrawn_at: Set((cve.vuln_status.as_deref() == Some("Rejected"))
+                    .then(|| timestamp(cve.last_modified.as_deref()).unwrap_or(now))),
+                updated_at: Set(now),
+                ..Default::default()
+            };
+            match existing {
+                Some((id, _)) => {
+                    model.id = Set(id);
+                    model.update(db).await?;
+                    id
+                }
+                None => {
+                    model.created_at = Set(now);
+                    model.insert(db).await?.id
+                }
+            }
+        }
+    };
+
+    cpe_match::Entity::delete_many()
+        .filter(cpe_match::Column::VulnerabilityId.eq(id))
+        .exec(db)
+        .await?;
+    let models = criteria
+        .into_iter()
+        .map(|criterion| cpe_match::ActiveModel {
+            vulnerability_id: Set(id),
+            created_at: Set(now),
+            ..criterion
+        });
+    cpe_match::Entity::insert_many(models).exec(db).await?;
+
+    Ok(Some(id))
+}
+
+/// English description, or the first one when none is in English.
+fn description(cve: &NvdCve) -> Option<String> {
+    cve.descriptions
+        .iter()
+        .find(|description| description.lang == "en")
+        .or_else(|| cve.descriptions.first())
+        .map(|description| description.value.clone())
+}
+
+/// Vector, base score and rating of the best available assessment: CVSS v3.1 before
+/// v3.0, NVD's own before the CNA's. CVSS v2 only contributes a rating.
+fn score(metrics: &NvdMetrics) -> (Option<String>, Option<f64>, Option<String>) {
+    let primary_first = |list: &[NvdCvssMetric]| -> Option<NvdCvssMetric> {
+        list.iter()
+            .find(|metric| metric.kind.as_deref() == Some("Primary"))
+            .or_else(|| list.first())
+            .cloned()
+    };
+
+    if let Some(metric) =
+        primary_first(&metrics.cvss_metric_v31).or_else(|| primary_first(&metrics.cvss_metric_v30))
+    {
+        let data = metric.cvss_data;
+        let score = cvss::base_score(&data.vector_string).unwrap_or(data.base_score);
+        let severity = data
+            .base_severity
+            .map(|severity| severity.to_ascii_lowercase())
+            .unwrap_or_else(|| cvss::severity(score).to_string());
+        return (Some(data.vector_string), Some(score), Some(severity));
+    }
+
+    let severity = primary_first(&metrics.cvss_metric_v2)
+        .and_then(|metric| metric.base_severity.or(metric.cvss_data.base_severity))
+        .map(|severity| severity.to_ascii_lowercase());
+    (None, None, severity)
+}
+
+/// Vulnerable criteria of every configuration node. Criteria with `vulnerable: false`
+/// only describe the platform a product must run on and are not matched; neither are
+/// the AND/OR operators between nodes, so running-on constraints are not enforced.
+fn cpe_matches(cve: &NvdCve) -> Vec<cpe_match::ActiveModel> {
+    let mut rows: Vec<cpe_match::ActiveModel> = Vec::new();
+    let criteria = cve
+        .configurations
+        .iter()
+        .flat_map(|configuration| &configuration.nodes)
+        .flat_map(|node| &node.cpe_match)
+        .filter(|criterion| criterion.vulnerable);
+    for criterion in criteria {
+        let Some(cpe) = Cpe::parse(&criterion.criteria) else {
+            warn!(cve = %cve.id, criteria = %criterion.criteria, "skipping unparsable CPE");
+            continue;
+        };
+        let model = cpe_match::ActiveModel {
+            criteria: Set(criterion.criteria.clone()),
+            package_cpe: Set(cpe.versionless()),
+            version: Set(cpe.version),
+            version_start_including: Set(criterion.version_start_including.clone()),
+            version_start_excluding: Set(criterion.version_start_excluding.clone()),
+            version_end_including: Set(criterion.version_end_including.clone()),
+            version_end_excluding: Set(criterion.version_end_excluding.clone()),
+            ..Default::default()
+        };
+        if !rows.contains(&model) {
+            rows.push(model);
+        }
+    }
+    rows
+}
+
+/// NVD timestamps carry no offset (`2021-12-10T10:15:09.143`) and are UTC.
+fn timestamp(value: Option<&str>) -> Option<DateTime<Utc>> {
+    let value = value?;
+    DateTime::parse_from_rfc3339(value)
+        .map(|parsed| parsed.with_timezone(&Utc))
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+                .map(|naive| naive.and_utc())
+        })
+        .ok()
+}
@@ -0,0 +1,422 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use packageurl::PackageUrl;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, FromQueryResult, QueryFilter,
+    QuerySelect, Set, TransactionTrait,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::warn;
+use walkdir::WalkDir;
+
+use super::cvss;
+use crate::{
+    entity::{affected_range, vulnerability},
+    purl::base_purl,
+};
+
+/// Records written per transaction.
+const BATCH_SIZE: usize = 500;
+
+/// One advisory in the OSV schema; only the fields check-deps uses.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvRecord {
+    pub id: String,
+    pub summary: Option<String>,
+    pub details: Option<String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub modified: Option<DateTime<Utc>>,
+    pub published: Option<DateTime<Utc>>,
+    pub withdrawn: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub severity: Vec<OsvSeverity>,
+    #[serde(default)]
+    pub affected: Vec<OsvAffected>,
+    pub references: Option<Value>,
+    pub database_specific: Option<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvSeverity {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub score: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvAffected {
+    pub package: Option<OsvPackage>,
+    #[serde(default)]
+    pub ranges: Vec<OsvRange>,
+    #[serde(default)]
+    pub versions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvPackage {
+    pub ecosystem: String,
+    pub name: String,
+    pub purl: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OsvRange {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub events: Vec<OsvEvent>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OsvEvent {
+    pub introduced: Option<String>,
+    pub fixed: Option<String>,
+    pub last_affected: Option<String>,
+}
+
+/// A closed or half-open interval built from a range's event list.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Interval {
+    pub introduced: Option<String>,
+    pub fixed: Option<String>,
+    pub last_affected: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ImportSummary {
+    pub files: usize,
+    pub records: usize,
+    pub imported: usize,
+    pub unchanged: usize,
+    pub skipped: usize,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct KnownRecord {
+    id: i32,
+    osv_id: String,
+    modified_at: Option<DateTime<Utc>>,
+}
+
+/// Import every OSV dump below `dir`: per-ecosystem `all.zip` archives and loose
+/// `*.json` records. Advisories whose `modified` timestamp did not move are skipped.
+pub async fn import_dir(db: &DatabaseConnection, dir: &Path) -> anyhow::Result<ImportSummary> {
+    let files: Vec<PathBuf> = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| matches!(extension(path).as_deref(), Some("zip" | "json")))
+        .collect();
+    if files.is_empty() {
+        anyhow::bail!("no OSV dumps (*.zip or *.json) found in {}", dir.display());
+    }
+
+    let mut known: HashMap<String, (i32, Option<DateTime<Utc>>)> = vulnerability::Entity::find()
+        .select_only()
+        .column(vulnerability::Column::Id)
+        .column(vulnerability::Column::OsvId)
+        .column(vulnerability::Column::ModifiedAt)
+        .into_model::<KnownRecord>()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|row| (row.osv_id, (row.id, row.modified_at)))
+        .collect();
+
+    let mut summary = ImportSummary::default();
+    for path in files {
+        let source = path.clone();
+        let records = tokio::task::spawn_blocking(move || read_dump(&source))
+            .await?
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let total = records.len();
+        summary.files += 1;
+        summary.records += total;
+
+        let changed: Vec<OsvRecord> = records
+            .into_iter()
+            .filter(|record| match known.get(&record.id) {
+                Some((_, Some(stored))) => record.modified.is_none_or(|m| m > *stored),
+                _ => true,
+            })
+            .collect();
+        summary.unchanged += total - changed.len();
+
+        for batch in changed.chunks(BATCH_SIZE) {
+            let txn = db.begin().await?;
+            for record in batch {
+                match store_record(&txn, record, known.get(&record.id).map(|(id, _)| *id)).await? {
+                    Some(id) => {
+                        known.insert(record.id.clone(), (id, record.modified));
+                        summary.imported += 1;
+                    }
+                    None => summary.skipped += 1,
+                }
+            }
+            txn.commit().await?;
+        }
+        tracing::info!("Imported OSV dump {}", path.display());
+    }
+
+    Ok(summary)
+}
+
+fn extension(path: &Path) -> Option<String> {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+}
+
+/// Parse a zip archive of OSV records or a single JSON record. Broken entries are
+/// logged and skipped so one bad advisory does not abort the import.
+fn read_dump(path: &Path) -> anyhow::Result<Vec<OsvRecord>> {
+    if extension(path).as_deref() != Some("zip") {
+        let record = serde_json::from_reader(File::open(path)?)?;
+        return Ok(vec![record]);
+    }
+
+    let mut archive = zip::ZipArchive::new(File::open(path)?)?;
+    let mut records = Vec::with_capacity(archive.len());
+    let mut buffer = Vec::new();
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        if !entry.is_file() || !entry.name().ends_with(".json") {
+            continue;
+        }
+        buffer.clear();
+        entry.read_to_end(&mut buffer)?;
+        match serde_json::from_slice::<OsvRecord>(&buffer) {
+            Ok(record) => records.push(record),
+            Err(err) => warn!(error = %err, entry = entry.name(), "skipping unreadable OSV record"),
+        }
+    }
+    Ok(records)
+}
+
+/// Upsert one advisory and replace its ranges. Returns `None` when no affected package
+/// could be mapped to a purl, in which case nothing is written.
+async fn store_record<C: sea_orm::ConnectionTrait>(
+    db: &C,
+    record: &OsvRecord,
+    existing_id: Option<i32>,
+) -> anyhow::Result<Option<i32>> {
+    let ranges = affected_ranges(record);
+    if ranges.is_empty() {
+        return Ok(None);
+    }
+
+    let (cvss_vector, cvss_score) = record
+        .severity
+        .iter()
+        .filter(|severity| severity.kind.starts_with("CVSS_V3"))
+        .find_map(|severity| {
+            cvss::base_score(&severity.score).map(|score| (severity.score.clone(), score))
+        })
+        .map_or((None, None), |(vector, score)| (Some(vector), Some(score)));
+    let severity = cvss_score
+        .map(|score| cvss::severity(score).to_string())
+        .or_else(|| database_severity(record));
+
+    let now = Utc::now();
+    let mut model = vulnerability::ActiveModel {
+        osv_id: Set(record.id.clone()),
+        source: Set("osv".to_string()),
+        summary: Set(record.summary.clone()),
+        details: Set(record.details.clone()),
+        severity: Set(severity),
+        cvss_score: Set(cvss_score),
+        cvss_vector: Set(cvss_vector),
+        aliases: Set(serde_json::json!(record.aliases)),
+        references: Set(record.references.clone()),
+        published_at: Set(record.published),
+        modified_at: Set(record.modified),
+        withdrawn_at: Set(record.withdrawn),
+        updated_at: Set(now),
+        ..Default::default()
+    };
+    let id = match existing_id {
+        Some(id) => {
+            model.id = Set(id);
+            model.update(db).await?;
+            affected_range::Entity::delete_many()
+                .filter(affected_range::Column::VulnerabilityId.eq(id))
+                .exec(db)
+                .await?;
+            id
+        }
+        None => {
+            model.created_at = Set(now);
+            model.insert(db).await?.id
+        }
+    };
+
+    let models = ranges.into_iter().map(|range| affected_range::ActiveModel {
+        vulnerability_id: Set(id),
+        created_at: Set(now),
+        ..range
+    });
+    affected_range::Entity::insert_many(models).exec(db).await?;
+
+    Ok(Some(id))
+}
+
+/// GHSA-style `database_specific.severity` (`CRITICAL`, `HIGH`, `MODERATE`, `LOW`).
+fn database_severity(record: &OsvRecord) -> Option<String> {
+    let severity = record
+        .database_specific
+        .as_ref()?
+        .get("severity")?
+        .as_str()?
+        .to_ascii_lowercase();
+    Some(match severity.as_str() {
+        "moderate" => "medium".to_string(),
+        _ => severity,
+    })
+}
+
+/// Range rows of every affected package that maps to a purl; GIT ranges are dropped
+/// because they cannot be matched against released versions.
+fn affected_ranges(record: &OsvRecord) -> Vec<affected_range::ActiveModel> {
+    let mut rows = Vec::new();
+    for affected in &record.affected {
+        let Some(package) = &affected.package else {
+            continue;
+        };
+        let Some(package_purl) = package_purl(package) else {
+            continue;
+        };
+        let row = |kind: &str| affected_range::ActiveModel {
+            ecosystem: Set(package.ecosystem.clone()),
+            package_name: Set(package.name.clone()),
+            package_purl: Set(package_purl.clone()),
+            range_type: Set(kind.to_string()),
+            introduced: Set(None),
+            fixed: Set(None),
+            last_affected: Set(None),
+            versions: Set(None),
+            ..Default::default()
+        };
+
+        for range in &affected.ranges {
+            if !matches!(range.kind.as_str(), "SEMVER" | "ECOSYSTEM") {
+                continue;
+            }
+            for interval in intervals(&range.events) {
+                rows.push(affected_range::ActiveModel {
+                    introduced: Set(interval.introduced),
+                    fixed: Set(interval.fixed),
+                    last_affected: Set(interval.last_affected),
+                    ..row(&range.kind)
+                });
+            }
+        }
+        if !affected.versions.is_empty() {
+            rows.push(affected_range::ActiveModel {
+                versions: Set(Some(serde_json::json!(affected.versions))),
+                ..row("VERSIONS")
+            });
+        }
+    }
+    rows
+}
+
+/// Turn an OSV event list into intervals: each `introduced` opens one, the next
+/// `fixed` or `last_affected` closes it, and a trailing open interval is unbounded.
+pub fn intervals(events: &[OsvEvent]) -> Vec<Interval> {
+    let mut intervals = Vec::new();
+    let mut open: Option<Interval> = None;
+    for event in events {
+        if let Some(introduced) = &event.introduced {
+            if let Some(interval) = open.take() {
+                intervals.push(interval);
+            }
+            open = Some(Interval {
+                introduced: Some(introduced.clone()),
+                ..Default::default()
+            });
+        }
+        if event.fixed.is_some() || event.last_affected.is_some() {
+            let mut interval = open.take().unwrap_or_default();
+            interval.fixed = event.fixed.clone();
+            interval.last_affected = event.last_affected.clone();
+            intervals.push(interval);
+        }
+    }
+    intervals.extend(open);
+    intervals
+}
+
+/// Versionless purl for an OSV package, from its own `purl` when present.
+pub fn package_purl(package: &OsvPackage) -> Option<String> {
+    if let Some(parsed) = package
+        .purl
+        .as_deref()
+        .and_then(|purl| PackageUrl::from_str(purl).ok())
+    {
+        return Some(base_purl(&parsed));
+    }
+
+    // Distribution ecosystems carry a release suffix (`Debian:12`).
+    let ecosystem = package.ecosystem.split(':').next().unwrap_or_default();
+    let name = package.name.as_str();
+    let (ty, namespace, name) = match ecosystem {
+        "npm" => match name.strip_prefix('@').and_then(|n| n.split_once('/')) {
+            Some((scope, name)) => ("npm", Some(format!("@{scope}")), name.to_string()),
+            None => ("npm", None, name.to_string()),
+        },
+        "PyPI" => ("pypi", None, normalize_pypi(name)),
+        "Maven" => {
+            let (group, artifact) = name.split_once(':')?;
+            ("maven", Some(group.to_string()), artifact.to_string())
+        }
+        "crates.io" => ("cargo", None, name.to_string()),
+        "Go" => match name.rsplit_once('/') {
+            Some((namespace, name)) => ("golang", Some(namespace.to_string()), name.to_string()),
+            None => ("golang", None, name.to_string()),
+        },
+        "RubyGems" => ("gem", None, name.to_string()),
+        "NuGet" => ("nuget", None, name.to_string()),
+        "Packagist" => {
+            let (vendor, name) = name.split_once('/')?;
+            ("composer", Some(vendor.to_string()), name.to_string())
+        }
+        "Pub" => ("pub", None, name.to_string()),
+        "Hex" => ("hex", None, name.to_string()),
+        "Hackage" => ("hackage", None, name.to_string()),
+        "CRAN" => ("cran", None, name.to_string()),
+        "SwiftURL" => ("swift", None, name.to_string()),
+        _ => return None,
+    };
+
+    let mut purl = PackageUrl::new(ty, name).ok()?;
+    if let Some(namespace) = namespace {
+        purl.with_namespace(namespace).ok()?;
+    }
+    Some(base_purl(&purl))
+}
+
+/// PEP 503 normalisation, which is also what purl requires for PyPI names.
+fn normalize_pypi(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+        }
+    }
+    normalized
+}
@@ -0,0 +1,28 @@
+//! Exploit likelihood of advisories from the imported EPSS scores and KEV catalog.
+//!
+//! Both are keyed on CVE ids, so an advisory is looked up under its own id and every
+//! alias. The combined risk score scales the CVSS base score (or a stand-in for the
+//! rating when there is none) by the exploit likelihood:
+//!
+//! `risk = cvss * 10 * (0.2 + 0.8 * likelihood)`
+//!
+//! where the likelihood is 1 for advisories in the KEV catalog and the EPSS probability
+//! otherwise. Scores run from 0 to 100; a critical advisory nobody is expected to
+//! exploit keeps a fifth of its weight.
+
+/// Lateral join over advisories `vu` adding `ex.epss`, `ex.epss_percentile`,
+/// `ex.kev_date_added`, `ex.kev_due_date`, `ex.kev_ransomware` and `ex.risk_score`.
+pub const EXPLOIT_JOIN: &str = "LEFT JOIN LATERAL ( \
+     SELECT MAX(es.epss) AS epss, MAX(es.percentile) AS epss_percentile, \
+            MIN(ke.date_added) AS kev_date_added, MIN(ke.due_date) AS kev_due_date, \
+            COALESCE(bool_or(ke.known_ransomware), false) AS kev_ransomware, \
+            ROUND((COALESCE(vu.cvss_score, CASE vu.severity WHEN 'critical' THEN 9.5 \
+                WHEN 'high' THEN 7.5 WHEN 'medium' THEN 5.0 WHEN 'low' THEN 2.5 ELSE 5.0 END) \
+                * 10 * (0.2 + 0.8 * CASE WHEN MIN(ke.date_added) IS NOT NULL THEN 1 \
+                ELSE COALESCE(MAX(es.epss), 0) END))::numeric, 1)::double precision \
+                AS risk_score \
+     FROM (SELECT vu.osv_id AS cve \
+           UNION SELECT jsonb_array_elements_text(vu.aliases)) ids \
+     LEFT JOIN epss_scores es ON es.cve = ids.cve \
+     LEFT JOIN kev_entries ke ON ke.cve = ids.cve \
+     ) ex ON true";
@@ -1,12 +1,46 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_table;
+mod m20261018_000001_create_dependency_edges;
+mod m20261018_000002_create_package_versions;
+mod m20261018_000003_create_dependency_edges_archive;
+mod m20261018_000004_create_package_search_indexes;
+mod m20261018_000005_add_project_team;
+mod m20261018_000006_create_vulnerabilities;
+mod m20261018_000007_create_cpe_matches;
+mod m20261018_000008_create_vulnerability_analyses;
+mod m20261018_000009_create_exploit_data;
+mod m20261018_000010_add_package_version_licenses;
+mod m20261018_000011_create_license_violations;
+mod m20261018_000012_add_project_tags;
+mod m20261018_000013_create_policy_evaluations;
+mod m20261018_000014_create_policy_waivers;
+mod m20261018_000015_create_registry_versions;
+mod m20261018_000016_create_package_health_findings;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_table::Migration)]
+        vec![
+            Box::new(m20220101_000001_create_table::Migration),
+            Box::new(m20261018_000001_create_dependency_edges::Migration),
+            Box::new(m20261018_000002_create_package_versions::Migration),
+            Box::new(m20261018_000003_create_dependency_edges_archive::Migration),
+            Box::new(m20261018_000004_create_package_search_indexes::Migration),
+            Box::new(m20261018_000005_add_project_team::Migration),
+            Box::new(m20261018_000006_create_vulnerabilities::Migration),
+            Box::new(m20261018_000007_create_cpe_matches::Migration),
+            Box::new(m20261018_000008_create_vulnerability_analyses::Migration),
+            Box::new(m20261018_000009_create_exploit_data::Migration),
+            Box::new(m20261018_000010_add_package_version_licenses::Migration),
+            Box::new(m20261018_000011_create_license_violations::Migration),
+            Box::new(m20261018_000012_add_project_tags::Migration),
+            Box::new(m20261018_000013_create_policy_evaluations::Migration),
+            Box::new(m20261018_000014_create_policy_waivers::Migration),
+            Box::new(m20261018_000015_create_registry_versions::Migration),
+            Box::new(m20261018_000016_create_package_health_findings::Migration),
+        ]
     }
 }
@@ -0,0 +1,93 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("dependency_edges")
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new("id")
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new("scan_id").integer().not_null())
+                    .col(ColumnDef::new("project_id").integer().not_null())
+                    .col(ColumnDef::new("parent_package_id").integer().null())
+                    .col(ColumnDef::new("child_package_id").integer().not_null())
+                    .col(ColumnDef::new("resolved_version").string().null())
+                    .col(ColumnDef::new("scope").string().null())
+                    .col(
+                        ColumnDef::new("created_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-dependency-edges-scan_id")
+                            .from("dependency_edges", "scan_id")
+                            .to("scans", "id"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-dependency-edges-project_id")
+                            .from("dependency_edges", "project_id")
+                            .to("projects", "id"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-dependency-edges-parent_package_id")
+                            .from("dependency_edges", "parent_package_id")
+                            .to("packages", "id"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-dependency-edges-child_package_id")
+                            .from("dependency_edges", "child_package_id")
+                            .to("packages", "id"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Recursive walks join on (scan, parent) going down and (scan, child) going up.
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-dependency-edges-scan-parent")
+                    .table("dependency_edges")
+                    .col("scan_id")
+                    .col("parent_package_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-dependency-edges-scan-child")
+                    .table("dependency_edges")
+                    .col("scan_id")
+                    .col("child_package_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("dependency_edges").to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
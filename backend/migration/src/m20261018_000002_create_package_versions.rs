@@ -0,0 +1,254 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("package_versions")
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new("id")
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new("package_id").integer().not_null())
+                    .col(
+                        ColumnDef::new("purl")
+                            .text()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new("version").string().null())
+                    .col(
+                        ColumnDef::new("first_seen_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new("last_seen_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new("created_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new("updated_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-package-versions-package_id")
+                            .from("package_versions", "package_id")
+                            .to("packages", "id"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-package-versions-package-version")
+                    .table("package_versions")
+                    .col("package_id")
+                    .col("version")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("direct_dependencies")
+                    .add_column(ColumnDef::new("package_version_id").integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk-direct-dependencies-package_version_id")
+                    .from("direct_dependencies", "package_version_id")
+                    .to("package_versions", "id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("dependency_edges")
+                    .add_column(ColumnDef::new("parent_version_id").integer().null())
+                    .add_column(ColumnDef::new("child_version_id").integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk-dependency-edges-parent_version_id")
+                    .from("dependency_edges", "parent_version_id")
+                    .to("package_versions", "id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk-dependency-edges-child_version_id")
+                    .from("dependency_edges", "child_version_id")
+                    .to("package_versions", "id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-dependency-edges-scan-parent-version")
+                    .table("dependency_edges")
+                    .col("scan_id")
+                    .col("parent_version_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-dependency-edges-scan-child-version")
+                    .table("dependency_edges")
+                    .col("scan_id")
+                    .col("child_version_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        // Rows written before this migration keyed `packages` on the full, versioned purl,
+        // so each of them maps to exactly one version.
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            "INSERT INTO package_versions \
+                 (package_id, purl, version, first_seen_at, last_seen_at, created_at, updated_at) \
+             SELECT p.id, p.purl, MAX(d.resolved_version), \
+                    COALESCE(MIN(d.created_at), p.created_at), \
+                    COALESCE(MAX(d.created_at), p.updated_at), NOW(), NOW() \
+             FROM packages p \
+             LEFT JOIN direct_dependencies d ON d.package_id = p.id \
+             GROUP BY p.id \
+             ON CONFLICT (purl) DO NOTHING",
+        )
+        .await?;
+
+        db.execute_unprepared(
+            "UPDATE direct_dependencies d SET package_version_id = v.id \
+             FROM package_versions v \
+             WHERE v.package_id = d.package_id AND d.package_version_id IS NULL",
+        )
+        .await?;
+
+        db.execute_unprepared(
+            "UPDATE dependency_edges e SET child_version_id = v.id \
+             FROM package_versions v WHERE v.package_id = e.child_package_id",
+        )
+        .await?;
+
+        db.execute_unprepared(
+            "UPDATE dependency_edges e SET parent_version_id = v.id \
+             FROM package_versions v WHERE v.package_id = e.parent_package_id",
+        )
+        .await?;
+
+        // `packages` is now keyed on the versionless purl (`purl::base_purl`): strip the
+        // version, qualifiers and subpath, merge the rows that collapse onto the same
+        // package into one and repoint everything that referenced the others.
+        db.execute_unprepared(
+            "CREATE TEMPORARY TABLE package_bases AS \
+             SELECT id, purl, \
+                    replace(regexp_replace(regexp_replace(purl, '[?#].*$', ''), \
+                                           '@[^@/]*$', ''), '/@', '/%40') AS base \
+             FROM packages",
+        )
+        .await?;
+
+        db.execute_unprepared(
+            "CREATE TEMPORARY TABLE package_merges AS \
+             SELECT b.id, s.survivor_id \
+             FROM package_bases b \
+             JOIN (SELECT base, \
+                          COALESCE(MIN(id) FILTER (WHERE purl = base), MIN(id)) AS survivor_id \
+                   FROM package_bases GROUP BY base) s ON s.base = b.base \
+             WHERE b.id <> s.survivor_id",
+        )
+        .await?;
+
+        for (table, column) in [
+            ("package_versions", "package_id"),
+            ("direct_dependencies", "package_id"),
+            ("dependency_edges", "parent_package_id"),
+            ("dependency_edges", "child_package_id"),
+        ] {
+            db.execute_unprepared(&format!(
+                "UPDATE {table} t SET {column} = m.survivor_id \
+                 FROM package_merges m WHERE t.{column} = m.id"
+            ))
+            .await?;
+        }
+
+        db.execute_unprepared("DELETE FROM packages p USING package_merges m WHERE p.id = m.id")
+            .await?;
+
+        db.execute_unprepared(
+            "UPDATE packages p SET purl = b.base, updated_at = NOW() \
+             FROM package_bases b WHERE p.id = b.id AND p.purl <> b.base",
+        )
+        .await?;
+
+        db.execute_unprepared("DROP TABLE package_merges, package_bases")
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("dependency_edges")
+                    .drop_column("parent_version_id")
+                    .drop_column("child_version_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("direct_dependencies")
+                    .drop_column("package_version_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("package_versions").to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
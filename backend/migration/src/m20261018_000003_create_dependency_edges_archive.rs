@@ -0,0 +1,58 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Edges of superseded scans are moved here when graph.retention=archive.
+        manager
+            .create_table(
+                Table::create()
+                    .table("dependency_edges_archive")
+                    .if_not_exists()
+                    .col(ColumnDef::new("id").integer().not_null().primary_key())
+                    .col(ColumnDef::new("scan_id").integer().not_null())
+                    .col(ColumnDef::new("project_id").integer().not_null())
+                    .col(ColumnDef::new("parent_package_id").integer().null())
+                    .col(ColumnDef::new("child_package_id").integer().not_null())
+                    .col(ColumnDef::new("parent_version_id").integer().null())
+                    .col(ColumnDef::new("child_version_id").integer().null())
+                    .col(ColumnDef::new("resolved_version").string().null())
+                    .col(ColumnDef::new("scope").string().null())
+                    .col(
+                        ColumnDef::new("created_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new("archived_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-dependency-edges-archive-scan")
+                    .table("dependency_edges_archive")
+                    .col("scan_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("dependency_edges_archive").to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-packages-type-namespace-name")
+                    .table("packages")
+                    .if_not_exists()
+                    .col("purl_type")
+                    .col("namespace")
+                    .col("name")
+                    .to_owned(),
+            )
+            .await?;
+
+        // Trigram indexes back the fuzzy package search.
+        let db = manager.get_connection();
+        db.execute_unprepared("CREATE EXTENSION IF NOT EXISTS pg_trgm")
+            .await?;
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS \"idx-packages-name-trgm\" \
+             ON packages USING gin (name gin_trgm_ops)",
+        )
+        .await?;
+        db.execute_unprepared(
+            "CREATE INDEX IF NOT EXISTS \"idx-packages-namespace-trgm\" \
+             ON packages USING gin (namespace gin_trgm_ops)",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        db.execute_unprepared("DROP INDEX IF EXISTS \"idx-packages-namespace-trgm\"")
+            .await?;
+        db.execute_unprepared("DROP INDEX IF EXISTS \"idx-packages-name-trgm\"")
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-packages-type-namespace-name")
+                    .table("packages")
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
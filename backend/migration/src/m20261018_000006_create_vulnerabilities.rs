@@ -0,0 +1,226 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("vulnerabilities")
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new("id")
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new("osv_id").string().not_null().unique_key())
+                    .col(ColumnDef::new("summary").text().null())
+                    .col(ColumnDef::new("details").text().null())
+                    .col(ColumnDef::new("severity").string().null())
+                    .col(ColumnDef::new("cvss_score").double().null())
+                    .col(ColumnDef::new("cvss_vector").string().null())
+                    .col(
+                        ColumnDef::new("aliases")
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust("'[]'::jsonb")),
+                    )
+                    .col(ColumnDef::new("references").json_binary().null())
+                    .col(
+                        ColumnDef::new("published_at")
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new("modified_at")
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new("withdrawn_at")
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new("created_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new("updated_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("affected_ranges")
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new("id")
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new("vulnerability_id").integer().not_null())
+                    .col(ColumnDef::new("ecosystem").string().not_null())
+                    .col(ColumnDef::new("package_name").string().not_null())
+                    .col(ColumnDef::new("package_purl").text().not_null())
+                    .col(ColumnDef::new("range_type").string().not_null())
+                    .col(ColumnDef::new("introduced").string().null())
+                    .col(ColumnDef::new("fixed").string().null())
+                    .col(ColumnDef::new("last_affected").string().null())
+                    .col(ColumnDef::new("versions").json_binary().null())
+                    .col(
+                        ColumnDef::new("created_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-affected-ranges-vulnerability_id")
+                            .from("affected_ranges", "vulnerability_id")
+                            .to("vulnerabilities", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-affected-ranges-package_purl")
+                    .table("affected_ranges")
+                    .col("package_purl")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("vulnerability_findings")
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new("id")
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new("vulnerability_id").integer().not_null())
+                    .col(ColumnDef::new("project_id").integer().not_null())
+                    .col(ColumnDef::new("scan_id").integer().not_null())
+                    .col(ColumnDef::new("package_id").integer().not_null())
+                    .col(ColumnDef::new("package_version_id").integer().null())
+                    .col(ColumnDef::new("purl").text().not_null())
+                    .col(ColumnDef::new("version").string().null())
+                    .col(
+                        ColumnDef::new("fixed_versions")
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust("'[]'::jsonb")),
+                    )
+                    .col(
+                        ColumnDef::new("created_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-vulnerability-findings-vulnerability_id")
+                            .from("vulnerability_findings", "vulnerability_id")
+                            .to("vulnerabilities", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-vulnerability-findings-project_id")
+                            .from("vulnerability_findings", "project_id")
+                            .to("projects", "id"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-vulnerability-findings-scan_id")
+                            .from("vulnerability_findings", "scan_id")
+                            .to("scans", "id"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-vulnerability-findings-package_id")
+                            .from("vulnerability_findings", "package_id")
+                            .to("packages", "id"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-vulnerability-findings-package_version_id")
+                            .from("vulnerability_findings", "package_version_id")
+                            .to("package_versions", "id"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-vulnerability-findings-scan-vulnerability-purl")
+                    .table("vulnerability_findings")
+                    .col("scan_id")
+                    .col("vulnerability_id")
+                    .col("purl")
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-vulnerability-findings-project_id")
+                    .table("vulnerability_findings")
+                    .col("project_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-vulnerability-findings-package_id")
+                    .table("vulnerability_findings")
+                    .col("package_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("vulnerability_findings").to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("affected_ranges").to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("vulnerabilities").to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("packages")
+                    .add_column(ColumnDef::new("cpe").text().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-packages-cpe")
+                    .table("packages")
+                    .col("cpe")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("vulnerabilities")
+                    .add_column(ColumnDef::new("source").string().not_null().default("osv"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("cpe_matches")
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new("id")
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new("vulnerability_id").integer().not_null())
+                    .col(ColumnDef::new("criteria").text().not_null())
+                    .col(ColumnDef::new("package_cpe").text().not_null())
+                    .col(ColumnDef::new("version").string().null())
+                    .col(ColumnDef::new("version_start_including").string().null())
+                    .col(ColumnDef::new("version_start_excluding").string().null())
+                    .col(ColumnDef::new("version_end_including").string().null())
+                    .col(ColumnDef::new("version_end_excluding").string().null())
+                    .col(
+                        ColumnDef::new("created_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-cpe-matches-vulnerability_id")
+                            .from("cpe_matches", "vulnerability_id")
+                            .to("vulnerabilities", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-cpe-matches-package_cpe")
+                    .table("cpe_matches")
+                    .col("package_cpe")
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("cpe_matches").to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("vulnerabilities")
+                    .drop_column("source")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-packages-cpe")
+                    .table("packages")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("packages")
+                    .drop_column("cpe")
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
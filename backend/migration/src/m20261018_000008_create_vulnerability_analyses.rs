@@ -0,0 +1,132 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("vulnerability_analyses")
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new("id")
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new("project_id").integer().not_null())
+                    .col(ColumnDef::new("vulnerability_id").integer().not_null())
+                    .col(ColumnDef::new("purl").text().not_null())
+                    .col(ColumnDef::new("state").string().not_null())
+                    .col(ColumnDef::new("justification").string().null())
+                    .col(ColumnDef::new("detail").text().null())
+                    .col(ColumnDef::new("author_id").string().not_null())
+                    .col(ColumnDef::new("author").string().not_null())
+                    .col(
+                        ColumnDef::new("created_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new("updated_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-vulnerability-analyses-project_id")
+                            .from("vulnerability_analyses", "project_id")
+                            .to("projects", "id"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-vulnerability-analyses-vulnerability_id")
+                            .from("vulnerability_analyses", "vulnerability_id")
+                            .to("vulnerabilities", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-vulnerability-analyses-project-vulnerability-purl")
+                    .table("vulnerability_analyses")
+                    .col("project_id")
+                    .col("vulnerability_id")
+                    .col("purl")
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("vulnerability_analysis_events")
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new("id")
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new("analysis_id").integer().not_null())
+                    .col(ColumnDef::new("state").string().not_null())
+                    .col(ColumnDef::new("justification").string().null())
+                    .col(ColumnDef::new("detail").text().null())
+                    .col(ColumnDef::new("author_id").string().not_null())
+                    .col(ColumnDef::new("author").string().not_null())
+                    .col(ColumnDef::new("source").string().not_null())
+                    .col(
+                        ColumnDef::new("created_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-vulnerability-analysis-events-analysis_id")
+                            .from("vulnerability_analysis_events", "analysis_id")
+                            .to("vulnerability_analyses", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-vulnerability-analysis-events-analysis_id")
+                    .table("vulnerability_analysis_events")
+                    .col("analysis_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table("vulnerability_analysis_events")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("vulnerability_analyses").to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
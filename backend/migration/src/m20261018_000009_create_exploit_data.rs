@@ -0,0 +1,151 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("epss_scores")
+                    .if_not_exists()
+                    .col(ColumnDef::new("cve").string().not_null().primary_key())
+                    .col(ColumnDef::new("epss").double().not_null())
+                    .col(ColumnDef::new("percentile").double().not_null())
+                    .col(ColumnDef::new("model_version").string().null())
+                    .col(ColumnDef::new("score_date").date().null())
+                    .col(
+                        ColumnDef::new("imported_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("kev_entries")
+                    .if_not_exists()
+                    .col(ColumnDef::new("cve").string().not_null().primary_key())
+                    .col(ColumnDef::new("vendor_project").string().null())
+                    .col(ColumnDef::new("product").string().null())
+                    .col(ColumnDef::new("vulnerability_name").text().null())
+                    .col(ColumnDef::new("short_description").text().null())
+                    .col(ColumnDef::new("required_action").text().null())
+                    .col(ColumnDef::new("date_added").date().not_null())
+                    .col(ColumnDef::new("due_date").date().null())
+                    .col(
+                        ColumnDef::new("known_ransomware")
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new("notes").text().null())
+                    .col(
+                        ColumnDef::new("imported_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("kev_alerts")
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new("id")
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new("project_id").integer().not_null())
+                    .col(ColumnDef::new("scan_id").integer().not_null())
+                    .col(ColumnDef::new("vulnerability_id").integer().not_null())
+                    .col(ColumnDef::new("cve").string().not_null())
+                    .col(ColumnDef::new("purl").text().not_null())
+                    .col(ColumnDef::new("due_date").date().null())
+                    .col(
+                        ColumnDef::new("known_ransomware")
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new("created_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new("notified_at")
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new("acknowledged_at")
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .col(ColumnDef::new("acknowledged_by").string().null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-kev-alerts-project_id")
+                            .from("kev_alerts", "project_id")
+                            .to("projects", "id"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-kev-alerts-scan_id")
+                            .from("kev_alerts", "scan_id")
+                            .to("scans", "id"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-kev-alerts-vulnerability_id")
+                            .from("kev_alerts", "vulnerability_id")
+                            .to("vulnerabilities", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-kev-alerts-project-vulnerability-purl")
+                    .table("kev_alerts")
+                    .col("project_id")
+                    .col("vulnerability_id")
+                    .col("purl")
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("kev_alerts").to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("kev_entries").to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("epss_scores").to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("license_violations")
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new("id")
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new("project_id").integer().not_null())
+                    .col(ColumnDef::new("scan_id").integer().not_null())
+                    .col(ColumnDef::new("package_version_id").integer().null())
+                    .col(ColumnDef::new("purl").text().not_null())
+                    .col(ColumnDef::new("scope").string().null())
+                    .col(ColumnDef::new("license_expression").text().null())
+                    .col(ColumnDef::new("outcome").string().not_null())
+                    .col(
+                        ColumnDef::new("licenses")
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust("'[]'::jsonb")),
+                    )
+                    .col(ColumnDef::new("reason").text().not_null())
+                    .col(
+                        ColumnDef::new("paths")
+                            .json_binary()
+                            .not_null()
+                            .default(Expr::cust("'[]'::jsonb")),
+                    )
+                    .col(
+                        ColumnDef::new("created_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-license-violations-project_id")
+                            .from("license_violations", "project_id")
+                            .to("projects", "id"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-license-violations-scan_id")
+                            .from("license_violations", "scan_id")
+                            .to("scans", "id"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-license-violations-scan_id")
+                    .table("license_violations")
+                    .col("scan_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("license_violations").to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("policy_waivers")
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new("id")
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new("project_id").integer().not_null())
+                    .col(ColumnDef::new("rule").string().not_null())
+                    .col(ColumnDef::new("purl").text().not_null())
+                    .col(ColumnDef::new("justification").text().not_null())
+                    .col(ColumnDef::new("approver_id").string().not_null())
+                    .col(ColumnDef::new("approver").string().not_null())
+                    .col(
+                        ColumnDef::new("expires_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new("reminded_at").timestamp_with_time_zone())
+                    .col(ColumnDef::new("revoked_at").timestamp_with_time_zone())
+                    .col(ColumnDef::new("revoked_by").string())
+                    .col(
+                        ColumnDef::new("created_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-policy-waivers-project_id")
+                            .from("policy_waivers", "project_id")
+                            .to("projects", "id"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-policy-waivers-project_id")
+                    .table("policy_waivers")
+                    .col("project_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-policy-waivers-expires_at")
+                    .table("policy_waivers")
+                    .col("expires_at")
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("policy_waivers").to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
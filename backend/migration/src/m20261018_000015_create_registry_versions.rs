@@ -0,0 +1,62 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("registry_versions")
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new("id")
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new("package_id").integer().not_null())
+                    .col(ColumnDef::new("version").string().not_null())
+                    .col(ColumnDef::new("published_at").timestamp_with_time_zone())
+                    .col(ColumnDef::new("yanked").boolean().not_null().default(false))
+                    .col(
+                        ColumnDef::new("imported_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-registry-versions-package_id")
+                            .from("registry_versions", "package_id")
+                            .to("packages", "id"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-registry-versions-package_id-version")
+                    .table("registry_versions")
+                    .col("package_id")
+                    .col("version")
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("registry_versions").to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
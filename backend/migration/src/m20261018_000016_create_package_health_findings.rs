@@ -0,0 +1,96 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("registry_versions")
+                    .add_column(ColumnDef::new("yanked_reason").text())
+                    .add_column(ColumnDef::new("deprecated").text())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("package_health_findings")
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new("id")
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new("project_id").integer().not_null())
+                    .col(ColumnDef::new("scan_id").integer().not_null())
+                    .col(ColumnDef::new("package_id").integer().not_null())
+                    .col(ColumnDef::new("purl").text().not_null())
+                    .col(ColumnDef::new("version").string())
+                    .col(ColumnDef::new("kind").string().not_null())
+                    .col(ColumnDef::new("detail").text().not_null())
+                    .col(ColumnDef::new("last_release_at").timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new("created_at")
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-package-health-findings-project_id")
+                            .from("package_health_findings", "project_id")
+                            .to("projects", "id"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-package-health-findings-scan_id")
+                            .from("package_health_findings", "scan_id")
+                            .to("scans", "id"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-package-health-findings-package_id")
+                            .from("package_health_findings", "package_id")
+                            .to("packages", "id"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-package-health-findings-scan_id")
+                    .table("package_health_findings")
+                    .col("scan_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("package_health_findings").to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("registry_versions")
+                    .drop_column("yanked_reason")
+                    .drop_column("deprecated")
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}